@@ -3,7 +3,7 @@ extern crate criterion;
 extern crate noise;
 
 use criterion::{black_box, Criterion};
-use noise::{NoiseFn, Worley};
+use noise::{NoiseFn, RangeFunction, Worley};
 
 criterion_group!(
     worley_range,
@@ -29,11 +29,17 @@ criterion_group!(
     bench_worley3_value_64x64,
     bench_worley4_value_64x64
 );
+criterion_group!(
+    worley_range_function_64x64,
+    bench_worley2_euclidean_64x64,
+    bench_worley2_euclidean_squared_64x64
+);
 criterion_main!(
     worley_range,
     worley_value,
     worley_range_64x64,
-    worley_value_64x64
+    worley_value_64x64,
+    worley_range_function_64x64
 );
 
 fn bench_worley2_range(c: &mut Criterion) {
@@ -155,3 +161,38 @@ fn bench_worley4_value_64x64(c: &mut Criterion) {
         })
     });
 }
+
+fn bench_worley2_range_function_64x64(
+    c: &mut Criterion,
+    name: &str,
+    range_function: RangeFunction,
+) {
+    let worley = Worley::new()
+        .enable_range(true)
+        .set_range_function(range_function);
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            for y in 0i8..64 {
+                for x in 0i8..64 {
+                    black_box(worley.get([x as f64, y as f64]));
+                }
+            }
+        })
+    });
+}
+
+fn bench_worley2_euclidean_64x64(c: &mut Criterion) {
+    bench_worley2_range_function_64x64(
+        c,
+        "worley 2d - euclidean (64x64)",
+        RangeFunction::Euclidean,
+    );
+}
+
+fn bench_worley2_euclidean_squared_64x64(c: &mut Criterion) {
+    bench_worley2_range_function_64x64(
+        c,
+        "worley 2d - euclidean squared (64x64)",
+        RangeFunction::EuclideanSquared,
+    );
+}
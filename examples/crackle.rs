@@ -0,0 +1,24 @@
+extern crate noise;
+
+use noise::{utils::*, Crackle, Fbm, MultiFractal};
+
+fn main() {
+    let fbm = Fbm::new().set_frequency(2.0).set_octaves(5);
+    let crackle = Crackle::new(&fbm).set_frequency(1.5).set_strength(0.8);
+
+    let gradient = ColorGradient::new()
+        .clear_gradient()
+        .add_gradient_point(-1.0, [64, 44, 30, 255])
+        .add_gradient_point(0.0, [128, 98, 70, 255])
+        .add_gradient_point(1.0, [196, 170, 132, 255]);
+
+    let map = PlaneMapBuilder::new(&crackle)
+        .set_size(512, 512)
+        .set_x_bounds(-2.0, 2.0)
+        .set_y_bounds(-2.0, 2.0)
+        .build();
+
+    let mut renderer = ImageRenderer::new().set_gradient(gradient);
+    renderer.enable_light();
+    renderer.render(&map).write_to_file("crackle.png");
+}
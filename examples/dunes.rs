@@ -0,0 +1,23 @@
+extern crate noise;
+
+use noise::{utils::*, Dunes};
+
+fn main() {
+    let dunes = Dunes::new().set_wind_direction(0.4).set_dune_frequency(0.6);
+
+    let gradient = ColorGradient::new()
+        .clear_gradient()
+        .add_gradient_point(-1.0, [166, 112, 62, 255])
+        .add_gradient_point(0.0, [214, 163, 96, 255])
+        .add_gradient_point(1.0, [248, 222, 164, 255]);
+
+    let map = PlaneMapBuilder::new(&dunes)
+        .set_size(512, 512)
+        .set_x_bounds(-5.0, 5.0)
+        .set_y_bounds(-5.0, 5.0)
+        .build();
+
+    let mut renderer = ImageRenderer::new().set_gradient(gradient);
+    renderer.enable_light();
+    renderer.render(&map).write_to_file("dunes.png");
+}
@@ -0,0 +1,43 @@
+extern crate noise;
+
+use noise::{utils::*, DomainWarp, Fbm, MultiFractal, RidgedMulti, Seedable};
+
+fn main() {
+    let fbm = Fbm::new().set_octaves(5);
+    let warp = Fbm::new().set_seed(1).set_octaves(4);
+
+    // A single warp bends the fBm into flowing shapes.
+    let warped = fbm.warp(0.8, &warp);
+
+    PlaneMapBuilder::new(&warped)
+        .set_size(512, 512)
+        .build()
+        .write_to_file("warped_fbm.png");
+
+    // Warping the warp function as well gives the swirly look of iterated
+    // domain warping.
+    let inner_warp = Fbm::new().set_seed(2).set_octaves(4);
+    let twice_warped = fbm.warp(0.8, warp.warp(1.5, &inner_warp));
+
+    PlaneMapBuilder::new(&twice_warped)
+        .set_size(512, 512)
+        .build()
+        .write_to_file("warped_fbm_twice.png");
+
+    // Warping ridged noise produces eroded-looking mountain ranges.
+    let ridged = RidgedMulti::new();
+    let warped_ridged = DomainWarp::new(&ridged, &warp).set_warp_strength(0.5);
+
+    let mut renderer =
+        ImageRenderer::new().set_gradient(ColorGradient::new().build_terrain_gradient());
+    renderer.enable_light();
+    renderer
+        .render(
+            &PlaneMapBuilder::new(&warped_ridged)
+                .set_size(512, 512)
+                .set_x_bounds(-2.0, 2.0)
+                .set_y_bounds(-2.0, 2.0)
+                .build(),
+        )
+        .write_to_file("warped_ridged.png");
+}
@@ -0,0 +1,208 @@
+#ifndef NOISE_H
+#define NOISE_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Fbm Fbm;
+
+typedef struct OpenSimplex OpenSimplex;
+
+typedef struct Perlin Perlin;
+
+/**
+ * Samples the generator at `len` 2-dimensional points, writing the
+ * value for the point `(xs[i], ys[i])` to `out[i]`.
+ *
+ * # Safety
+ *
+ * `generator` must be a valid pointer returned by the matching `new`
+ * function, `xs` and `ys` must point to `len` readable values and
+ * `out` must point to `len` writable values.
+ */
+void noise_fbm_fill2(const Fbm *generator,
+                     const double *xs,
+                     const double *ys,
+                     double *out,
+                     uintptr_t len);
+
+/**
+ * Releases a generator created by the matching `new` function. Passing
+ * a null pointer does nothing.
+ *
+ * # Safety
+ *
+ * `generator` must be null or a pointer returned by the matching `new`
+ * function that has not been freed yet.
+ */
+void noise_fbm_free(Fbm *generator);
+
+/**
+ * Samples the generator at a 2-dimensional point.
+ *
+ * # Safety
+ *
+ * `generator` must be a valid pointer returned by the matching `new`
+ * function.
+ */
+double noise_fbm_get2(const Fbm *generator, double x, double y);
+
+/**
+ * Samples the generator at a 3-dimensional point.
+ *
+ * # Safety
+ *
+ * `generator` must be a valid pointer returned by the matching `new`
+ * function.
+ */
+double noise_fbm_get3(const Fbm *generator, double x, double y, double z);
+
+/**
+ * Samples the generator at a 4-dimensional point.
+ *
+ * # Safety
+ *
+ * `generator` must be a valid pointer returned by the matching `new`
+ * function.
+ */
+double noise_fbm_get4(const Fbm *generator, double x, double y, double z, double w);
+
+/**
+ * Creates a new generator with the given seed. The returned pointer
+ * must be released with the matching `free` function.
+ */
+Fbm *noise_fbm_new(uint32_t seed);
+
+/**
+ * Samples the generator at `len` 2-dimensional points, writing the
+ * value for the point `(xs[i], ys[i])` to `out[i]`.
+ *
+ * # Safety
+ *
+ * `generator` must be a valid pointer returned by the matching `new`
+ * function, `xs` and `ys` must point to `len` readable values and
+ * `out` must point to `len` writable values.
+ */
+void noise_open_simplex_fill2(const OpenSimplex *generator,
+                              const double *xs,
+                              const double *ys,
+                              double *out,
+                              uintptr_t len);
+
+/**
+ * Releases a generator created by the matching `new` function. Passing
+ * a null pointer does nothing.
+ *
+ * # Safety
+ *
+ * `generator` must be null or a pointer returned by the matching `new`
+ * function that has not been freed yet.
+ */
+void noise_open_simplex_free(OpenSimplex *generator);
+
+/**
+ * Samples the generator at a 2-dimensional point.
+ *
+ * # Safety
+ *
+ * `generator` must be a valid pointer returned by the matching `new`
+ * function.
+ */
+double noise_open_simplex_get2(const OpenSimplex *generator, double x, double y);
+
+/**
+ * Samples the generator at a 3-dimensional point.
+ *
+ * # Safety
+ *
+ * `generator` must be a valid pointer returned by the matching `new`
+ * function.
+ */
+double noise_open_simplex_get3(const OpenSimplex *generator, double x, double y, double z);
+
+/**
+ * Samples the generator at a 4-dimensional point.
+ *
+ * # Safety
+ *
+ * `generator` must be a valid pointer returned by the matching `new`
+ * function.
+ */
+double noise_open_simplex_get4(const OpenSimplex *generator,
+                               double x,
+                               double y,
+                               double z,
+                               double w);
+
+/**
+ * Creates a new generator with the given seed. The returned pointer
+ * must be released with the matching `free` function.
+ */
+OpenSimplex *noise_open_simplex_new(uint32_t seed);
+
+/**
+ * Samples the generator at `len` 2-dimensional points, writing the
+ * value for the point `(xs[i], ys[i])` to `out[i]`.
+ *
+ * # Safety
+ *
+ * `generator` must be a valid pointer returned by the matching `new`
+ * function, `xs` and `ys` must point to `len` readable values and
+ * `out` must point to `len` writable values.
+ */
+void noise_perlin_fill2(const Perlin *generator,
+                        const double *xs,
+                        const double *ys,
+                        double *out,
+                        uintptr_t len);
+
+/**
+ * Releases a generator created by the matching `new` function. Passing
+ * a null pointer does nothing.
+ *
+ * # Safety
+ *
+ * `generator` must be null or a pointer returned by the matching `new`
+ * function that has not been freed yet.
+ */
+void noise_perlin_free(Perlin *generator);
+
+/**
+ * Samples the generator at a 2-dimensional point.
+ *
+ * # Safety
+ *
+ * `generator` must be a valid pointer returned by the matching `new`
+ * function.
+ */
+double noise_perlin_get2(const Perlin *generator, double x, double y);
+
+/**
+ * Samples the generator at a 3-dimensional point.
+ *
+ * # Safety
+ *
+ * `generator` must be a valid pointer returned by the matching `new`
+ * function.
+ */
+double noise_perlin_get3(const Perlin *generator, double x, double y, double z);
+
+/**
+ * Samples the generator at a 4-dimensional point.
+ *
+ * # Safety
+ *
+ * `generator` must be a valid pointer returned by the matching `new`
+ * function.
+ */
+double noise_perlin_get4(const Perlin *generator, double x, double y, double z, double w);
+
+/**
+ * Creates a new generator with the given seed. The returned pointer
+ * must be released with the matching `free` function.
+ */
+Perlin *noise_perlin_new(uint32_t seed);
+
+#endif /* NOISE_H */
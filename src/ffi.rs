@@ -0,0 +1,234 @@
+//! C interface to the core generators, enabled by the `ffi` feature.
+//!
+//! Each generator is exposed as an opaque pointer. It is created with
+//! `noise_<generator>_new`, sampled with `noise_<generator>_get2`/`get3`/`get4`
+//! or in bulk with `noise_<generator>_fill2`, and must be released with the
+//! matching `noise_<generator>_free`.
+//!
+//! To link the library from C, build it as a C library, e.g. with
+//! `cargo rustc --release --features ffi --crate-type cdylib`. The C header is
+//! `noise.h` at the root of the crate. It is generated with
+//! `cbindgen --config cbindgen.toml --output noise.h`, and has to be
+//! regenerated whenever the exported functions change.
+
+use crate::noise_fns::{Fbm, NoiseFn, OpenSimplex, Perlin, Seedable};
+use std::slice;
+
+macro_rules! ffi_generator {
+    (
+        $generator:ty,
+        $new:ident,
+        $free:ident,
+        $get2:ident,
+        $get3:ident,
+        $get4:ident,
+        $fill2:ident
+    ) => {
+        /// Creates a new generator with the given seed. The returned pointer
+        /// must be released with the matching `free` function.
+        #[no_mangle]
+        pub extern "C" fn $new(seed: u32) -> *mut $generator {
+            Box::into_raw(Box::new(<$generator>::new().set_seed(seed)))
+        }
+
+        /// Releases a generator created by the matching `new` function. Passing
+        /// a null pointer does nothing.
+        ///
+        /// # Safety
+        ///
+        /// `generator` must be null or a pointer returned by the matching `new`
+        /// function that has not been freed yet.
+        #[no_mangle]
+        pub unsafe extern "C" fn $free(generator: *mut $generator) {
+            if !generator.is_null() {
+                drop(Box::from_raw(generator));
+            }
+        }
+
+        /// Samples the generator at a 2-dimensional point.
+        ///
+        /// # Safety
+        ///
+        /// `generator` must be a valid pointer returned by the matching `new`
+        /// function.
+        #[no_mangle]
+        pub unsafe extern "C" fn $get2(generator: *const $generator, x: f64, y: f64) -> f64 {
+            (*generator).get([x, y])
+        }
+
+        /// Samples the generator at a 3-dimensional point.
+        ///
+        /// # Safety
+        ///
+        /// `generator` must be a valid pointer returned by the matching `new`
+        /// function.
+        #[no_mangle]
+        pub unsafe extern "C" fn $get3(
+            generator: *const $generator,
+            x: f64,
+            y: f64,
+            z: f64,
+        ) -> f64 {
+            (*generator).get([x, y, z])
+        }
+
+        /// Samples the generator at a 4-dimensional point.
+        ///
+        /// # Safety
+        ///
+        /// `generator` must be a valid pointer returned by the matching `new`
+        /// function.
+        #[no_mangle]
+        pub unsafe extern "C" fn $get4(
+            generator: *const $generator,
+            x: f64,
+            y: f64,
+            z: f64,
+            w: f64,
+        ) -> f64 {
+            (*generator).get([x, y, z, w])
+        }
+
+        /// Samples the generator at `len` 2-dimensional points, writing the
+        /// value for the point `(xs[i], ys[i])` to `out[i]`.
+        ///
+        /// # Safety
+        ///
+        /// `generator` must be a valid pointer returned by the matching `new`
+        /// function, `xs` and `ys` must point to `len` readable values and
+        /// `out` must point to `len` writable values.
+        #[no_mangle]
+        pub unsafe extern "C" fn $fill2(
+            generator: *const $generator,
+            xs: *const f64,
+            ys: *const f64,
+            out: *mut f64,
+            len: usize,
+        ) {
+            if len == 0 {
+                return;
+            }
+
+            let generator = &*generator;
+            let xs = slice::from_raw_parts(xs, len);
+            let ys = slice::from_raw_parts(ys, len);
+            let out = slice::from_raw_parts_mut(out, len);
+
+            for ((value, &x), &y) in out.iter_mut().zip(xs).zip(ys) {
+                *value = generator.get([x, y]);
+            }
+        }
+    };
+}
+
+ffi_generator!(
+    Perlin,
+    noise_perlin_new,
+    noise_perlin_free,
+    noise_perlin_get2,
+    noise_perlin_get3,
+    noise_perlin_get4,
+    noise_perlin_fill2
+);
+
+ffi_generator!(
+    OpenSimplex,
+    noise_open_simplex_new,
+    noise_open_simplex_free,
+    noise_open_simplex_get2,
+    noise_open_simplex_get3,
+    noise_open_simplex_get4,
+    noise_open_simplex_fill2
+);
+
+ffi_generator!(
+    Fbm,
+    noise_fbm_new,
+    noise_fbm_free,
+    noise_fbm_get2,
+    noise_fbm_get3,
+    noise_fbm_get4,
+    noise_fbm_fill2
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perlin_round_trip() {
+        let perlin = Perlin::new().set_seed(3);
+
+        unsafe {
+            let generator = noise_perlin_new(3);
+
+            assert_eq!(
+                noise_perlin_get2(generator, 0.3, 0.7),
+                perlin.get([0.3, 0.7])
+            );
+            assert_eq!(
+                noise_perlin_get3(generator, 0.3, 0.7, 1.1),
+                perlin.get([0.3, 0.7, 1.1])
+            );
+            assert_eq!(
+                noise_perlin_get4(generator, 0.3, 0.7, 1.1, 2.5),
+                perlin.get([0.3, 0.7, 1.1, 2.5])
+            );
+
+            let xs = [0.1, 0.5, 2.3];
+            let ys = [4.2, -1.5, 0.9];
+            let mut out = [0.0; 3];
+            noise_perlin_fill2(generator, xs.as_ptr(), ys.as_ptr(), out.as_mut_ptr(), 3);
+            for i in 0..3 {
+                assert_eq!(out[i], perlin.get([xs[i], ys[i]]));
+            }
+
+            // Empty fills must not touch the (dangling) buffers.
+            noise_perlin_fill2(
+                generator,
+                std::ptr::null(),
+                std::ptr::null(),
+                std::ptr::null_mut(),
+                0,
+            );
+
+            noise_perlin_free(generator);
+            noise_perlin_free(std::ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn fbm_round_trip() {
+        let fbm = Fbm::new().set_seed(9);
+
+        unsafe {
+            let generator = noise_fbm_new(9);
+            assert_eq!(noise_fbm_get2(generator, 0.3, 0.7), fbm.get([0.3, 0.7]));
+            noise_fbm_free(generator);
+        }
+    }
+
+    #[test]
+    fn header_declares_every_export() {
+        let header = include_str!("../noise.h");
+        let mut declared: Vec<&str> = header
+            .split('(')
+            .filter_map(|before| {
+                let start = before.rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))?;
+                Some(&before[start + 1..])
+            })
+            .filter(|name| name.starts_with("noise_"))
+            .collect();
+        declared.sort();
+
+        let mut exported = Vec::new();
+        for generator in &["perlin", "open_simplex", "fbm"] {
+            for function in &["new", "free", "get2", "get3", "get4", "fill2"] {
+                exported.push(format!("noise_{}_{}", generator, function));
+            }
+        }
+        exported.sort();
+
+        assert_eq!(declared, exported);
+    }
+}
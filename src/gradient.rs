@@ -3,7 +3,7 @@
 pub(crate) fn get2(index: usize) -> [f64; 2] {
     // Vectors are combinations of -1, 0, and 1
     // Precompute the normalized element
-    const DIAG : f64 = std::f64::consts::FRAC_1_SQRT_2;
+    const DIAG : f64 = core::f64::consts::FRAC_1_SQRT_2;
 
     match index % 8 {
         0 => [  1.0,   0.0],
@@ -18,12 +18,50 @@ pub(crate) fn get2(index: usize) -> [f64; 2] {
     }
 }
 
+/// Returns one of `count` evenly spaced 2D unit vectors.
+///
+/// A `count` of 8 returns exactly the same vectors as `get2`. Counts of 16
+/// and 32 select from a finer table of directions, which reduces the
+/// directional bias of the resulting noise.
+#[inline(always)]
+pub(crate) fn get2_n(index: usize, count: usize) -> [f64; 2] {
+    match count {
+        8 => get2(index),
+        16 => GRADIENTS_2D[(index % 16) * 2],
+        32 => GRADIENTS_2D[index % 32],
+        _ => panic!("Unsupported 2D gradient count {}", count),
+    }
+}
+
+// 32 unit vectors spaced evenly around the circle, starting at [1, 0].
+#[rustfmt::skip]
+const GRADIENTS_2D: [[f64; 2]; 32] = {
+    const C1: f64 = 0.980_785_280_403_230_4; // cos(pi / 16)
+    const C2: f64 = 0.923_879_532_511_286_7; // cos(pi / 8)
+    const C3: f64 = 0.831_469_612_302_545_2; // cos(3 * pi / 16)
+    const DIAG: f64 = core::f64::consts::FRAC_1_SQRT_2;
+    const S3: f64 = 0.555_570_233_019_602_2; // sin(3 * pi / 16)
+    const S2: f64 = 0.382_683_432_365_089_8; // sin(pi / 8)
+    const S1: f64 = 0.195_090_322_016_128_3; // sin(pi / 16)
+
+    [
+        [  1.0,   0.0], [   C1,    S1], [   C2,    S2], [   C3,    S3],
+        [ DIAG,  DIAG], [   S3,    C3], [   S2,    C2], [   S1,    C1],
+        [  0.0,   1.0], [  -S1,    C1], [  -S2,    C2], [  -S3,    C3],
+        [-DIAG,  DIAG], [  -C3,    S3], [  -C2,    S2], [  -C1,    S1],
+        [ -1.0,   0.0], [  -C1,   -S1], [  -C2,   -S2], [  -C3,   -S3],
+        [-DIAG, -DIAG], [  -S3,   -C3], [  -S2,   -C2], [  -S1,   -C1],
+        [  0.0,  -1.0], [   S1,   -C1], [   S2,   -C2], [   S3,   -C3],
+        [ DIAG, -DIAG], [   C3,   -S3], [   C2,   -S2], [   C1,   -S1],
+    ]
+};
+
 #[inline(always)]
 #[rustfmt::skip]
 pub(crate) fn get3(index: usize) -> [f64; 3] {
     // Vectors are combinations of -1, 0, and 1
     // Precompute the normalized elements
-    const DIAG : f64 = std::f64::consts::FRAC_1_SQRT_2;
+    const DIAG : f64 = core::f64::consts::FRAC_1_SQRT_2;
     const DIAG2 : f64 = 0.577_350_269_189_625_8;
 
     match index % 32 {
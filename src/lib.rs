@@ -8,13 +8,77 @@
 //! let perlin = Perlin::new();
 //! let val = perlin.get([42.4, 37.7, 2.8]);
 //! ```
+//!
+//! # Coordinate range
+//!
+//! Noise functions are continuous for input coordinates (after any frequency
+//! scaling) up to about 2^52 in magnitude on 64-bit targets, although the
+//! precision of the fractional part, and with it the quality of the noise,
+//! degrades long before that. Lattice coordinates beyond 2^52 (2^30 on
+//! 32-bit targets) saturate instead of overflowing, so extreme or infinite
+//! inputs produce well-defined, constant output rather than undefined
+//! behavior or wrapping artifacts.
+//!
+//! # Determinism
+//!
+//! For a given seed and input value, every noise function produces the same
+//! output on every run. Outputs are bit-identical across platforms that
+//! implement IEEE 754 double precision arithmetic, including a correctly
+//! rounded `mul_add`, which covers all common 64-bit targets and WebAssembly.
+//! Bit-exact output is not guaranteed on targets that evaluate floating point
+//! with excess precision, such as 32-bit x86 without SSE2, and `f64` methods
+//! like `sin` and `powf` call into the platform's math library, which may
+//! round differently. Outputs may also change between releases; see
+//! `ALGORITHM_VERSION`.
+//!
+//! # `no_std`
+//!
+//! The noise functions work without the standard library. Disable the default
+//! features and enable `libm`, which provides the floating point functions
+//! that `std` otherwise supplies, to build for `no_std` targets with an
+//! allocator. This needs Rust 1.36 or later for the `alloc` crate. The `utils`
+//! module, `Profiled`, and the `image` and `ffi` features need `std`. Outputs
+//! computed with `libm` may differ in the last bits from those computed with
+//! `std`.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+// The test harness links `std`, whose `f64` methods then take precedence over
+// `no_std::Float`, leaving imports that only bring in that trait unused.
+#![cfg_attr(all(test, not(feature = "std")), allow(unused_imports))]
 #![deny(missing_copy_implementations)]
 
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("noise requires either the `std` or the `libm` feature");
+
+#[cfg(all(feature = "image", not(feature = "std")))]
+compile_error!("the `image` feature requires the `std` feature");
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+
+pub use crate::math::{scale_shift, scale_shift_to_range, CellRng};
 pub use crate::noise_fns::*;
+#[cfg(feature = "std")]
+pub use crate::permutationtable::permutation_table_image;
+pub use crate::permutationtable::HashMode;
+
+/// Version of the noise algorithms implemented by this crate.
+///
+/// Outputs for a given seed and point are stable for as long as this value
+/// stays the same. It is incremented whenever a change alters the output of
+/// an existing noise function, so applications that persist seeds (e.g. saved
+/// worlds) can store it alongside the seed and detect when regenerated terrain
+/// would differ.
+pub const ALGORITHM_VERSION: u32 = 4;
 
+#[cfg(feature = "ffi")]
+pub mod ffi;
 mod gradient;
 mod math;
+#[cfg(not(feature = "std"))]
+mod no_std;
 mod noise_fns;
 mod permutationtable;
+pub mod presets;
+#[cfg(feature = "std")]
 pub mod utils;
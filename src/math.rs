@@ -1,7 +1,9 @@
 //! An ultra-light private math library to make our short lives easier as we
 //! implement super-complex noise stuff.
 
-use std::ops::{Add, Mul, Sub};
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use core::ops::{Add, Mul, Sub};
 
 /// Cast a numeric type without having to unwrap - we don't expect any overflow
 /// errors...
@@ -278,47 +280,146 @@ pub(crate) fn to_f64_4(x: [isize; 4]) -> [f64; 4] {
     [x[0] as f64, x[1] as f64, x[2] as f64, x[3] as f64]
 }
 
+/// Largest magnitude of the lattice coordinates produced by `to_isize`.
+///
+/// Beyond 2^52 an f64 has no fractional bits left, so noise functions no
+/// longer vary between lattice points and there is no detail to preserve. The
+/// remaining headroom keeps the arithmetic on neighboring lattice points from
+/// overflowing.
+#[cfg(target_pointer_width = "64")]
+const LATTICE_LIMIT: f64 = 4_503_599_627_370_496.0;
+#[cfg(not(target_pointer_width = "64"))]
+const LATTICE_LIMIT: f64 = 1_073_741_824.0;
+
+/// Converts a lattice coordinate to an isize, saturating at
+/// `±LATTICE_LIMIT` instead of overflowing. NaN converts to 0.
 // isize doesn't implement From<f64>
+#[inline]
+pub(crate) fn to_isize(x: f64) -> isize {
+    if x.is_nan() {
+        0
+    } else {
+        clamp(x, -LATTICE_LIMIT, LATTICE_LIMIT) as isize
+    }
+}
+
 #[inline]
 pub(crate) fn to_isize2(x: [f64; 2]) -> [isize; 2] {
-    [x[0] as isize, x[1] as isize]
+    map2(x, to_isize)
 }
 
-#[cfg(not(target_os = "emscripten"))]
+/// Returns `|value| * n - 1`.
+///
+/// With `n = 2` this maps the magnitude of a value in [0,1] onto [-1,1],
+/// which is how the billow and ridged fractals rescale their octaves. The
+/// sign of `value` is discarded and the result is not clamped.
 #[inline]
-pub(crate) fn scale_shift(value: f64, n: f64) -> f64 {
+pub fn scale_shift(value: f64, n: f64) -> f64 {
     value.abs().mul_add(n, -1.0_f64)
 }
 
-#[cfg(target_os = "emscripten")]
+/// Linearly remaps `value` from the range [in_lo, in_hi] onto
+/// [out_lo, out_hi].
+///
+/// Values outside of the input range are extrapolated rather than clamped,
+/// and either range may be reversed.
 #[inline]
-pub(crate) fn scale_shift(value: f64, n: f64) -> f64 {
-    (value.abs() * n) + -1.0_f64
+pub fn scale_shift_to_range(value: f64, in_lo: f64, in_hi: f64, out_lo: f64, out_hi: f64) -> f64 {
+    out_lo + (value - in_lo) * (out_hi - out_lo) / (in_hi - in_lo)
+}
+
+/// Advances a SplitMix64 generator and returns its next output.
+#[inline]
+pub(crate) fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Deterministic stream of random values in [0,1) for a single cell of an
+/// integer lattice.
+///
+/// The stream depends only on the seed and the cell coordinates, so cellular
+/// noise functions can use it to place feature points or assign cell values
+/// reproducibly. Cells of any dimension are supported.
+#[derive(Clone, Copy, Debug)]
+pub struct CellRng {
+    state: u64,
+}
+
+impl CellRng {
+    pub fn new(seed: u32, cell: &[isize]) -> Self {
+        let mut state = u64::from(seed);
+        for &coord in cell {
+            let mut mixed = state ^ coord as u64;
+            state = splitmix64(&mut mixed);
+        }
+
+        Self { state }
+    }
+}
+
+impl Iterator for CellRng {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        // Use the top 53 bits, the precision of an f64 mantissa.
+        let bits = splitmix64(&mut self.state) >> 11;
+
+        Some(bits as f64 / (1u64 << 53) as f64)
+    }
 }
 
 #[inline]
 pub(crate) fn to_isize3(x: [f64; 3]) -> [isize; 3] {
-    [x[0] as isize, x[1] as isize, x[2] as isize]
+    map3(x, to_isize)
 }
 
 #[inline]
 pub(crate) fn to_isize4(x: [f64; 4]) -> [isize; 4] {
-    [x[0] as isize, x[1] as isize, x[2] as isize, x[3] as isize]
+    map4(x, to_isize)
 }
 
-pub mod interpolate {
-    /// Performs linear interpolation between two values.
-    #[cfg(not(target_os = "emscripten"))]
-    #[inline]
-    pub(crate) fn linear(a: f64, b: f64, x: f64) -> f64 {
-        x.mul_add(b - a, a)
+/// Converts a latitude and longitude, in degrees, to a point on the unit
+/// sphere.
+#[inline]
+pub(crate) fn lat_lon_to_xyz(lat: f64, lon: f64) -> [f64; 3] {
+    let r = lat.to_radians().cos();
+    let x = r * lon.to_radians().cos();
+    let y = lat.to_radians().sin();
+    let z = r * lon.to_radians().sin();
+
+    [x, y, z]
+}
+
+/// Perlin's bias function. Remaps `t` in the [0,1] range so that 0.5 maps to
+/// `b`, while 0 and 1 are left unchanged.
+#[inline]
+pub(crate) fn bias(b: f64, t: f64) -> f64 {
+    t.powf(b.ln() / 0.5_f64.ln())
+}
+
+/// Perlin's gain function. Remaps `t` in the [0,1] range with an S-shaped
+/// curve whose steepness around 0.5 is controlled by `g`; 0.5 is the identity.
+#[inline]
+pub(crate) fn gain(g: f64, t: f64) -> f64 {
+    if t < 0.5 {
+        bias(1.0 - g, 2.0 * t) / 2.0
+    } else {
+        1.0 - bias(1.0 - g, 2.0 - 2.0 * t) / 2.0
     }
+}
+
+pub mod interpolate {
+    #[cfg(not(feature = "std"))]
+    use crate::no_std::*;
 
     /// Performs linear interpolation between two values.
-    #[cfg(target_os = "emscripten")]
     #[inline]
     pub(crate) fn linear(a: f64, b: f64, x: f64) -> f64 {
-        (x * (b - a)) + a
+        x.mul_add(b - a, a)
     }
 
     /// Performs cubic interpolation between two values bound between two other
@@ -354,3 +455,64 @@ pub mod interpolate {
         x * x * x * (x * (x * 6.0 - 15.0) + 10.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bias_and_gain() {
+        for &t in &[0.0, 0.1, 0.25, 0.5, 0.8, 1.0] {
+            assert!((bias(0.5, t) - t).abs() < 1e-12);
+            assert!((gain(0.5, t) - t).abs() < 1e-12);
+        }
+
+        assert!((bias(0.25, 0.5) - 0.25).abs() < 1e-12);
+        assert!((bias(0.8, 0.5) - 0.8).abs() < 1e-12);
+        assert_eq!(gain(0.9, 0.5), 0.5);
+        assert!((gain(0.75, 0.25) - 0.125).abs() < 1e-12);
+    }
+
+    #[test]
+    fn to_isize_saturates() {
+        assert_eq!(to_isize(-3.0), -3);
+        assert_eq!(to_isize(core::f64::NAN), 0);
+        assert_eq!(to_isize(core::isize::MAX as f64), LATTICE_LIMIT as isize);
+        assert_eq!(to_isize(core::f64::NEG_INFINITY), -LATTICE_LIMIT as isize);
+
+        // Neighboring lattice points of a saturated coordinate don't overflow.
+        assert!(to_isize(1e300).checked_add(2).is_some());
+    }
+
+    #[test]
+    fn scale_shift_values() {
+        assert_eq!(scale_shift(0.0, 2.0), -1.0);
+        assert_eq!(scale_shift(0.5, 2.0), 0.0);
+        assert_eq!(scale_shift(-0.5, 2.0), 0.0);
+        assert_eq!(scale_shift(1.0, 2.0), 1.0);
+        assert_eq!(scale_shift(1.0, 3.0), 2.0);
+    }
+
+    #[test]
+    fn scale_shift_to_range_values() {
+        assert_eq!(scale_shift_to_range(0.0, -1.0, 1.0, 0.0, 1.0), 0.5);
+        assert_eq!(scale_shift_to_range(-1.0, -1.0, 1.0, 0.0, 255.0), 0.0);
+        assert_eq!(scale_shift_to_range(1.0, -1.0, 1.0, 0.0, 255.0), 255.0);
+        assert_eq!(scale_shift_to_range(2.0, 0.0, 1.0, 0.0, 10.0), 20.0);
+        assert_eq!(scale_shift_to_range(0.25, 0.0, 1.0, 1.0, -1.0), 0.5);
+    }
+
+    #[test]
+    fn cell_rng() {
+        let sequence = |seed, cell: &[isize]| CellRng::new(seed, cell).take(8).collect::<Vec<_>>();
+
+        let values = sequence(0, &[3, -7]);
+        assert_eq!(values, sequence(0, &[3, -7]));
+        assert!(values.iter().all(|v| (0.0..1.0).contains(v)));
+
+        assert_ne!(values, sequence(0, &[-7, 3]));
+        assert_ne!(values, sequence(0, &[3, -6]));
+        assert_ne!(values, sequence(1, &[3, -7]));
+        assert_ne!(values, sequence(0, &[3, -7, 0]));
+    }
+}
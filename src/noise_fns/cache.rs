@@ -1,5 +1,7 @@
-use crate::noise_fns::NoiseFn;
-use std::cell::{Cell, RefCell};
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
+use core::cell::{Cell, RefCell};
 
 /// Noise function that caches the last output value generated by the source
 /// function.
@@ -53,6 +55,10 @@ where
             }
         }
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Cache").set_children(vec![self.source.describe()])
+    }
 }
 
 impl<Source> NoiseFn<[f64; 3]> for Cache<Source>
@@ -74,6 +80,10 @@ where
             }
         }
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Cache").set_children(vec![self.source.describe()])
+    }
 }
 
 impl<Source> NoiseFn<[f64; 4]> for Cache<Source>
@@ -95,4 +105,8 @@ where
             }
         }
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Cache").set_children(vec![self.source.describe()])
+    }
 }
@@ -1,11 +1,65 @@
 pub use self::add::*;
+pub use self::combine::*;
+pub use self::detail::*;
+pub use self::ensemble::*;
 pub use self::max::*;
 pub use self::min::*;
 pub use self::multiply::*;
 pub use self::power::*;
+pub use self::spectral_stack::*;
+pub use self::weighted_sum::*;
 
 mod add;
+mod combine;
+mod detail;
+mod ensemble;
 mod max;
 mod min;
 mod multiply;
 mod power;
+mod spectral_stack;
+mod weighted_sum;
+
+use crate::noise_fns::{NodeDesc, NoiseFn};
+use core::ops::{Deref, DerefMut};
+
+// `Add`, `Max`, `Min`, `Multiply` and `Power` are a `Combine` with a fixed
+// operation. They dereference to it for the sources and only replace its name
+// in the description.
+macro_rules! impl_combine_wrapper {
+    ($($wrapper:ident),*) => {
+        $(
+            impl<'a, T> Deref for $wrapper<'a, T> {
+                type Target = Combine<'a, T, fn(f64, f64) -> f64>;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.combine
+                }
+            }
+
+            impl<'a, T> DerefMut for $wrapper<'a, T> {
+                fn deref_mut(&mut self) -> &mut Self::Target {
+                    &mut self.combine
+                }
+            }
+
+            impl<'a, T> NoiseFn<T> for $wrapper<'a, T>
+            where
+                T: Copy,
+            {
+                fn get(&self, point: T) -> f64 {
+                    self.combine.get(point)
+                }
+
+                fn describe(&self) -> NodeDesc {
+                    NodeDesc {
+                        name: stringify!($wrapper).into(),
+                        ..self.combine.describe()
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_combine_wrapper!(Add, Max, Min, Multiply, Power);
@@ -0,0 +1,95 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
+
+/// Noise function that combines the output values from two source functions
+/// with a user supplied function.
+///
+/// This covers any element-wise binary operation that doesn't have a
+/// dedicated combiner, such as subtraction or averaging.
+pub struct Combine<'a, T, F>
+where
+    F: Fn(f64, f64) -> f64,
+{
+    /// Outputs a value.
+    pub source1: &'a dyn NoiseFn<T>,
+
+    /// Outputs a value.
+    pub source2: &'a dyn NoiseFn<T>,
+
+    /// Function combining the output of `source1` (first argument) with the
+    /// output of `source2` (second argument).
+    pub combiner: F,
+}
+
+impl<'a, T, F> Combine<'a, T, F>
+where
+    F: Fn(f64, f64) -> f64,
+{
+    pub fn new(source1: &'a dyn NoiseFn<T>, source2: &'a dyn NoiseFn<T>, combiner: F) -> Self {
+        Self {
+            source1,
+            source2,
+            combiner,
+        }
+    }
+}
+
+impl<'a, T, F> NoiseFn<T> for Combine<'a, T, F>
+where
+    T: Copy,
+    F: Fn(f64, f64) -> f64,
+{
+    fn get(&self, point: T) -> f64 {
+        (self.combiner)(self.source1.get(point), self.source2.get(point))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Combine")
+            .set_children(vec![self.source1.describe(), self.source2.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Add, Max, Min, Multiply, Negate, Perlin, Power, Seedable};
+
+    #[test]
+    fn subtraction_matches_add_negate() {
+        let a = Perlin::new();
+        let b = Perlin::new().set_seed(1);
+        let negated = Negate::new(&b);
+        let sum = Add::new(&a, &negated);
+        let difference = Combine::new(&a, &b, |x, y| x - y);
+
+        for &point in &[[0.3, 0.7], [1.5, -2.25], [-10.1, 4.4]] {
+            assert_eq!(difference.get(point), sum.get(point));
+        }
+    }
+
+    #[test]
+    fn fixed_combiners_keep_their_operation_and_name() {
+        let a = Perlin::new();
+        let b = Perlin::new().set_seed(1);
+        let add = Add::new(&a, &b);
+        let max = Max::new(&a, &b);
+        let min = Min::new(&a, &b);
+        let multiply = Multiply::new(&a, &b);
+        let power = Power::new(&a, &b);
+
+        for &point in &[[0.3, 0.7], [1.5, -2.25], [-10.1, 4.4]] {
+            let (x, y) = (a.get(point), b.get(point));
+            assert_eq!(add.get(point), x + y);
+            assert_eq!(max.get(point), x.max(y));
+            assert_eq!(min.get(point), x.min(y));
+            assert_eq!(multiply.get(point), x * y);
+            assert_eq!(power.get(point).to_bits(), x.powf(y).to_bits());
+        }
+
+        let desc = multiply.describe();
+        assert_eq!(desc.name, "Multiply");
+        assert_eq!(desc.children.len(), 2);
+        assert_eq!(add.source2.describe().name, "Perlin");
+    }
+}
@@ -0,0 +1,101 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that adds the output value from a detail function to a
+/// base function, scaled by a mask function.
+///
+/// The output is `base + detail * mask * strength`, where the mask value is
+/// mapped from [-1,1] onto [0,1] and clamped to that range. Where the mask is
+/// -1.0 the base shows unchanged, and where it is 1.0 the full detail is
+/// added. This is useful for roughening only some areas of a terrain, such as
+/// rocky regions.
+pub struct Detail<'a, T> {
+    /// Outputs the value the detail is added to.
+    pub base: &'a dyn NoiseFn<T>,
+
+    /// Outputs the detail to add, usually high-frequency noise.
+    pub detail: &'a dyn NoiseFn<T>,
+
+    /// Determines how much of the detail is added.
+    pub mask: &'a dyn NoiseFn<T>,
+
+    /// Scale of the detail where the mask is fully on. Default is 1.0.
+    pub strength: f64,
+}
+
+impl<'a, T> Detail<'a, T> {
+    pub const DEFAULT_STRENGTH: f64 = 1.0;
+
+    pub fn new(
+        base: &'a dyn NoiseFn<T>,
+        detail: &'a dyn NoiseFn<T>,
+        mask: &'a dyn NoiseFn<T>,
+    ) -> Self {
+        Self {
+            base,
+            detail,
+            mask,
+            strength: Self::DEFAULT_STRENGTH,
+        }
+    }
+
+    pub fn set_strength(self, strength: f64) -> Self {
+        Self { strength, ..self }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Detail<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        let base = self.base.get(point);
+        let mask = math::clamp(self.mask.get(point) * 0.5 + 0.5, 0.0, 1.0);
+
+        if mask == 0.0 {
+            return base;
+        }
+
+        base + self.detail.get(point) * mask * self.strength
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Detail").set_children(vec![
+            self.base.describe(),
+            self.detail.describe(),
+            self.mask.describe(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Constant, Perlin, Seedable};
+
+    #[test]
+    fn mask_scales_detail() {
+        let base = Perlin::new();
+        let detail = Perlin::new().set_seed(1);
+        let off = Constant::new(-1.0);
+        let on = Constant::new(1.0);
+        let half = Constant::new(0.0);
+
+        let masked = Detail::new(&base, &detail, &off).set_strength(0.5);
+        let full = Detail::new(&base, &detail, &on).set_strength(0.5);
+        let partial = Detail::new(&base, &detail, &half).set_strength(0.5);
+
+        for i in 0..20 {
+            let point = [i as f64 * 0.37 - 2.0, i as f64 * 0.23];
+            let (b, d) = (base.get(point), detail.get(point));
+
+            assert_eq!(masked.get(point), b);
+            assert_eq!(full.get(point), b + d * 0.5);
+            assert_eq!(partial.get(point), b + d * 0.25);
+        }
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
+
+/// Operation used by an `Ensemble` to fold the output value of a layer into
+/// the result of the layers before it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EnsembleOp {
+    /// Adds the weighted value to the result.
+    Add,
+
+    /// Multiplies the result by the weighted value.
+    Multiply,
+
+    /// Keeps the smaller of the result and the weighted value.
+    Min,
+
+    /// Keeps the larger of the result and the weighted value.
+    Max,
+}
+
+/// Noise function that folds the output values from a list of source
+/// functions into a single value.
+///
+/// Each layer holds a source function, the operation combining it with the
+/// result so far, and a weight its output value is multiplied by. The first
+/// layer's weighted value starts the result, so its operation is ignored.
+/// Without any layers, the output is 0.0.
+///
+/// The layers own their source functions, so a whole graph of independently
+/// seeded generators can be built and kept in one value.
+pub struct Ensemble<'a, T> {
+    /// The layers, in the order they are folded.
+    pub layers: Vec<(Box<dyn NoiseFn<T> + 'a>, EnsembleOp, f64)>,
+}
+
+impl<'a, T> Ensemble<'a, T> {
+    pub fn new() -> Self {
+        Self { layers: Vec::new() }
+    }
+
+    /// Appends a layer combining `source` with the previous layers using
+    /// `op`.
+    pub fn add_layer<Source>(mut self, source: Source, op: EnsembleOp, weight: f64) -> Self
+    where
+        Source: NoiseFn<T> + 'a,
+    {
+        self.layers.push((Box::new(source), op, weight));
+        self
+    }
+}
+
+impl<'a, T> Default for Ensemble<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Ensemble<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        let mut layers = self.layers.iter();
+
+        let mut result = match layers.next() {
+            Some((source, _, weight)) => source.get(point) * weight,
+            None => return 0.0,
+        };
+
+        for (source, op, weight) in layers {
+            let value = source.get(point) * weight;
+
+            result = match op {
+                EnsembleOp::Add => result + value,
+                EnsembleOp::Multiply => result * value,
+                EnsembleOp::Min => result.min(value),
+                EnsembleOp::Max => result.max(value),
+            };
+        }
+
+        result
+    }
+
+    fn describe(&self) -> NodeDesc {
+        let children = self.layers.iter().map(|(source, ..)| source.describe());
+
+        NodeDesc::new("Ensemble").set_children(children.collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Constant, Perlin, Seedable};
+
+    #[test]
+    fn folds_layers() {
+        let sum = Ensemble::new()
+            .add_layer(Constant::new(0.25), EnsembleOp::Add, 1.0)
+            .add_layer(Constant::new(0.5), EnsembleOp::Add, 1.0);
+        assert_eq!(sum.get([0.3, 0.7]), 0.75);
+
+        let perlin = Perlin::new().set_seed(3);
+        let ensemble = Ensemble::new()
+            .add_layer(Perlin::new(), EnsembleOp::Add, 0.5)
+            .add_layer(perlin, EnsembleOp::Max, 2.0)
+            .add_layer(Constant::new(0.5), EnsembleOp::Multiply, 1.0);
+        let point = [1.3, -0.4, 2.2];
+        let expected = (Perlin::new().get(point) * 0.5).max(perlin.get(point) * 2.0) * 0.5;
+        assert_eq!(ensemble.get(point), expected);
+
+        assert_eq!(Ensemble::<[f64; 2]>::new().get([1.0, 2.0]), 0.0);
+    }
+}
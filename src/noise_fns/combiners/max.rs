@@ -1,26 +1,15 @@
-use crate::noise_fns::NoiseFn;
+use crate::noise_fns::{Combine, NoiseFn};
 
 /// Noise function that outputs the larger of the two output values from two source
 /// functions.
 pub struct Max<'a, T> {
-    /// Outputs a value.
-    pub source1: &'a dyn NoiseFn<T>,
-
-    /// Outputs a value.
-    pub source2: &'a dyn NoiseFn<T>,
+    pub(super) combine: Combine<'a, T, fn(f64, f64) -> f64>,
 }
 
 impl<'a, T> Max<'a, T> {
     pub fn new(source1: &'a dyn NoiseFn<T>, source2: &'a dyn NoiseFn<T>) -> Self {
-        Self { source1, source2 }
-    }
-}
-
-impl<'a, T> NoiseFn<T> for Max<'a, T>
-where
-    T: Copy,
-{
-    fn get(&self, point: T) -> f64 {
-        (self.source1.get(point)).max(self.source2.get(point))
+        Self {
+            combine: Combine::new(source1, source2, |a, b| a.max(b)),
+        }
     }
 }
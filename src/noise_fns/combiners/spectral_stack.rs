@@ -0,0 +1,119 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that sums a stack of octaves, each with its own source
+/// function, frequency and amplitude.
+///
+/// Each octave samples its source at the input value scaled by the octave's
+/// frequency and multiplies the result by the octave's amplitude. Unlike
+/// `Fbm`, `Billow` and `RidgedMulti`, where every octave uses the same kind
+/// of source and the frequencies and amplitudes follow from the lacunarity
+/// and persistence, the octaves here are specified individually. This allows
+/// fully custom spectra, such as mixing Perlin and Worley octaves or leaving
+/// gaps between frequencies.
+///
+/// The sum is not normalized, so amplitudes that sum to at most 1.0 keep the
+/// output in the [-1,1] range. Without any octaves, the output is 0.0.
+pub struct SpectralStack<'a, T> {
+    /// The octaves as (source, frequency, amplitude) triples.
+    pub octaves: Vec<(Box<dyn NoiseFn<T> + 'a>, f64, f64)>,
+}
+
+impl<'a, T> SpectralStack<'a, T> {
+    pub fn new() -> Self {
+        Self {
+            octaves: Vec::new(),
+        }
+    }
+
+    /// Appends an octave sampling `source` at `frequency`, scaled by
+    /// `amplitude`.
+    pub fn add_octave<Source>(mut self, source: Source, frequency: f64, amplitude: f64) -> Self
+    where
+        Source: NoiseFn<T> + 'a,
+    {
+        self.octaves.push((Box::new(source), frequency, amplitude));
+        self
+    }
+
+    fn sum<F>(&self, scale: F) -> f64
+    where
+        F: Fn(f64) -> T,
+    {
+        self.octaves
+            .iter()
+            .map(|(source, frequency, amplitude)| source.get(scale(*frequency)) * amplitude)
+            .sum()
+    }
+
+    fn describe_octaves(&self) -> NodeDesc {
+        let children = self.octaves.iter().map(|(source, ..)| source.describe());
+
+        NodeDesc::new("SpectralStack").set_children(children.collect())
+    }
+}
+
+impl<'a, T> Default for SpectralStack<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> NoiseFn<[f64; 2]> for SpectralStack<'a, [f64; 2]> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.sum(|frequency| math::mul2(point, frequency))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        self.describe_octaves()
+    }
+}
+
+impl<'a> NoiseFn<[f64; 3]> for SpectralStack<'a, [f64; 3]> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.sum(|frequency| math::mul3(point, frequency))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        self.describe_octaves()
+    }
+}
+
+impl<'a> NoiseFn<[f64; 4]> for SpectralStack<'a, [f64; 4]> {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.sum(|frequency| math::mul4(point, frequency))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        self.describe_octaves()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Perlin, Worley};
+
+    #[test]
+    fn sums_scaled_octaves() {
+        let perlin = Perlin::new();
+        let worley = Worley::new();
+        let stack = SpectralStack::new()
+            .add_octave(perlin, 1.5, 0.75)
+            .add_octave(worley, 7.0, 0.25);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.37 - 4.0, i as f64 * 0.19];
+            let expected = perlin.get(math::mul2(point, 1.5)) * 0.75
+                + worley.get(math::mul2(point, 7.0)) * 0.25;
+
+            assert_eq!(stack.get(point), expected);
+        }
+
+        assert_eq!(SpectralStack::new().get([1.0, 2.0, 3.0]), 0.0);
+    }
+}
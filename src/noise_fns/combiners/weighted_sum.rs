@@ -0,0 +1,67 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
+
+/// Noise function that outputs the weighted sum of the output values from any
+/// number of source functions.
+///
+/// Each source function is paired with a weight that its output value is
+/// multiplied by before being added to the result. If there are no source
+/// functions, this noise function outputs 0.0.
+pub struct WeightedSum<'a, T> {
+    /// Pairs of source functions and their weights.
+    pub sources: Vec<(&'a dyn NoiseFn<T>, f64)>,
+}
+
+impl<'a, T> WeightedSum<'a, T> {
+    pub fn new(sources: Vec<(&'a dyn NoiseFn<T>, f64)>) -> Self {
+        Self { sources }
+    }
+
+    /// Adds a source function with the given weight to the sum.
+    pub fn add_source(mut self, source: &'a dyn NoiseFn<T>, weight: f64) -> Self {
+        self.sources.push((source, weight));
+
+        self
+    }
+}
+
+impl<'a, T> NoiseFn<T> for WeightedSum<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        self.sources
+            .iter()
+            .map(|(source, weight)| source.get(point) * weight)
+            .sum()
+    }
+
+    fn describe(&self) -> NodeDesc {
+        let children = self.sources.iter().map(|(source, _)| source.describe());
+
+        NodeDesc::new("WeightedSum").set_children(children.collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    #[test]
+    fn weighted_constants() {
+        let a = Constant::new(1.0);
+        let b = Constant::new(-2.0);
+        let sum = WeightedSum::new(vec![(&a, 0.25), (&b, 0.75)]);
+
+        assert_eq!(sum.get([0.5, 0.5]), 0.25 - 1.5);
+    }
+
+    #[test]
+    fn empty_is_zero() {
+        let sum = WeightedSum::<[f64; 3]>::new(Vec::new());
+
+        assert_eq!(sum.get([1.0, 2.0, 3.0]), 0.0);
+    }
+}
@@ -0,0 +1,259 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{
+    Abs, Add, Clamp, Exponent, Max, Min, Multiply, Negate, NodeDesc, NoiseFn, ScaleBias,
+};
+
+/// Extension trait for chaining modifiers and combiners onto a noise function.
+///
+/// The modifiers and combiners borrow their source functions, so building a
+/// graph out of them requires a binding for every intermediate function. The
+/// methods of this trait take ownership of their source functions instead and
+/// return the result boxed, which allows writing the graph as a single chain:
+///
+/// ```rust
+/// use noise::{NoiseFn, NoiseFnExt, Perlin};
+///
+/// let noise = Perlin::new().abs().scale_bias(2.0, -1.0).clamp(-0.5, 0.5);
+/// let value = noise.get([0.3, 0.7]);
+/// ```
+///
+/// Each method evaluates the corresponding modifier or combiner, so the chain
+/// outputs the same values as the nested construction. Pass a reference to
+/// use a noise function in a chain without giving up ownership of it.
+pub trait NoiseFnExt<T>: NoiseFn<T> + Sized {
+    /// Chains an `Abs` modifier.
+    fn abs<'a>(self) -> Box<dyn NoiseFn<T> + 'a>
+    where
+        Self: 'a,
+        T: Copy + 'a,
+    {
+        chain(self, "Abs", |source, point| Abs::new(source).get(point))
+    }
+
+    /// Chains a `Negate` modifier.
+    fn negate<'a>(self) -> Box<dyn NoiseFn<T> + 'a>
+    where
+        Self: 'a,
+        T: Copy + 'a,
+    {
+        chain(self, "Negate", |source, point| {
+            Negate::new(source).get(point)
+        })
+    }
+
+    /// Chains a `Clamp` modifier with the given bounds.
+    fn clamp<'a>(self, lower_bound: f64, upper_bound: f64) -> Box<dyn NoiseFn<T> + 'a>
+    where
+        Self: 'a,
+        T: Copy + 'a,
+    {
+        chain(self, "Clamp", move |source, point| {
+            Clamp::new(source)
+                .set_bounds(lower_bound, upper_bound)
+                .get(point)
+        })
+    }
+
+    /// Chains a `ScaleBias` modifier with the given scale and bias.
+    fn scale_bias<'a>(self, scale: f64, bias: f64) -> Box<dyn NoiseFn<T> + 'a>
+    where
+        Self: 'a,
+        T: Copy + 'a,
+    {
+        chain(self, "ScaleBias", move |source, point| {
+            ScaleBias::new(source)
+                .set_scale(scale)
+                .set_bias(bias)
+                .get(point)
+        })
+    }
+
+    /// Chains an `Exponent` modifier with the given exponent.
+    fn exponent<'a>(self, exponent: f64) -> Box<dyn NoiseFn<T> + 'a>
+    where
+        Self: 'a,
+        T: Copy + 'a,
+    {
+        chain(self, "Exponent", move |source, point| {
+            Exponent::new(source).set_exponent(exponent).get(point)
+        })
+    }
+
+    /// Chains an `Add` combiner with `other`.
+    fn add<'a, Other>(self, other: Other) -> Box<dyn NoiseFn<T> + 'a>
+    where
+        Self: 'a,
+        Other: NoiseFn<T> + 'a,
+        T: Copy + 'a,
+    {
+        combine(self, other, "Add", |a, b, point| Add::new(a, b).get(point))
+    }
+
+    /// Chains a `Multiply` combiner with `other`.
+    fn multiply<'a, Other>(self, other: Other) -> Box<dyn NoiseFn<T> + 'a>
+    where
+        Self: 'a,
+        Other: NoiseFn<T> + 'a,
+        T: Copy + 'a,
+    {
+        combine(self, other, "Multiply", |a, b, point| {
+            Multiply::new(a, b).get(point)
+        })
+    }
+
+    /// Chains a `Max` combiner with `other`.
+    fn max<'a, Other>(self, other: Other) -> Box<dyn NoiseFn<T> + 'a>
+    where
+        Self: 'a,
+        Other: NoiseFn<T> + 'a,
+        T: Copy + 'a,
+    {
+        combine(self, other, "Max", |a, b, point| Max::new(a, b).get(point))
+    }
+
+    /// Chains a `Min` combiner with `other`.
+    fn min<'a, Other>(self, other: Other) -> Box<dyn NoiseFn<T> + 'a>
+    where
+        Self: 'a,
+        Other: NoiseFn<T> + 'a,
+        T: Copy + 'a,
+    {
+        combine(self, other, "Min", |a, b, point| Min::new(a, b).get(point))
+    }
+}
+
+impl<T, M> NoiseFn<T> for Box<M>
+where
+    M: NoiseFn<T> + ?Sized,
+{
+    #[inline]
+    fn get(&self, point: T) -> f64 {
+        M::get(self, point)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        M::describe(self)
+    }
+
+    fn is_periodic(&self) -> Option<[f64; 2]> {
+        M::is_periodic(self)
+    }
+}
+
+impl<T, M> NoiseFnExt<T> for M where M: NoiseFn<T> {}
+
+/// Noise function that applies `apply` to a source function it owns.
+struct Chain<Source, F> {
+    name: &'static str,
+    source: Source,
+    apply: F,
+}
+
+impl<T, Source, F> NoiseFn<T> for Chain<Source, F>
+where
+    Source: NoiseFn<T>,
+    F: Fn(&dyn NoiseFn<T>, T) -> f64,
+{
+    fn get(&self, point: T) -> f64 {
+        (self.apply)(&self.source, point)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new(self.name).set_children(vec![self.source.describe()])
+    }
+}
+
+/// Noise function that applies `apply` to two source functions it owns.
+struct Chain2<Source1, Source2, F> {
+    name: &'static str,
+    source1: Source1,
+    source2: Source2,
+    apply: F,
+}
+
+impl<T, Source1, Source2, F> NoiseFn<T> for Chain2<Source1, Source2, F>
+where
+    Source1: NoiseFn<T>,
+    Source2: NoiseFn<T>,
+    F: Fn(&dyn NoiseFn<T>, &dyn NoiseFn<T>, T) -> f64,
+{
+    fn get(&self, point: T) -> f64 {
+        (self.apply)(&self.source1, &self.source2, point)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new(self.name)
+            .set_children(vec![self.source1.describe(), self.source2.describe()])
+    }
+}
+
+fn chain<'a, T, Source, F>(source: Source, name: &'static str, apply: F) -> Box<dyn NoiseFn<T> + 'a>
+where
+    Source: NoiseFn<T> + 'a,
+    F: Fn(&dyn NoiseFn<T>, T) -> f64 + 'a,
+    T: 'a,
+{
+    Box::new(Chain {
+        name,
+        source,
+        apply,
+    })
+}
+
+fn combine<'a, T, Source1, Source2, F>(
+    source1: Source1,
+    source2: Source2,
+    name: &'static str,
+    apply: F,
+) -> Box<dyn NoiseFn<T> + 'a>
+where
+    Source1: NoiseFn<T> + 'a,
+    Source2: NoiseFn<T> + 'a,
+    F: Fn(&dyn NoiseFn<T>, &dyn NoiseFn<T>, T) -> f64 + 'a,
+    T: 'a,
+{
+    Box::new(Chain2 {
+        name,
+        source1,
+        source2,
+        apply,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Perlin, Seedable};
+
+    #[test]
+    fn chain_matches_nested_construction() {
+        let perlin = Perlin::new();
+        let other = Perlin::new().set_seed(1);
+
+        let abs = Abs::new(&perlin);
+        let scaled = ScaleBias::new(&abs).set_scale(2.0).set_bias(-0.5);
+        let sum = Add::new(&scaled, &other);
+        let nested = Clamp::new(&sum).set_bounds(-0.75, 0.75);
+
+        let chained = (&perlin)
+            .abs()
+            .scale_bias(2.0, -0.5)
+            .add(other)
+            .clamp(-0.75, 0.75);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.37 - 4.0, i as f64 * 0.19, 0.3];
+
+            assert_eq!(chained.get(point), nested.get(point));
+        }
+        assert_eq!(chained.describe(), nested.describe());
+
+        let owned = Perlin::new().negate().max(Perlin::new().set_seed(2));
+        let point = [0.4, 1.3];
+        assert_eq!(
+            owned.get(point),
+            (-perlin.get(point)).max(Perlin::new().set_seed(2).get(point))
+        );
+    }
+}
@@ -1,19 +1,35 @@
 pub use self::checkerboard::*;
 pub use self::constant::*;
+pub use self::coordinate::*;
 pub use self::cylinders::*;
+pub use self::dunes::*;
+pub use self::evolving_perlin::*;
 pub use self::fractals::*;
+pub use self::image_source::*;
+pub use self::linear::*;
 pub use self::open_simplex::*;
+pub use self::passthrough::*;
 pub use self::perlin::*;
+pub use self::point_cloud::*;
 pub use self::super_simplex::*;
 pub use self::value::*;
+pub use self::waves::*;
 pub use self::worley::*;
 
 mod checkerboard;
 mod constant;
+mod coordinate;
 mod cylinders;
+mod dunes;
+mod evolving_perlin;
 mod fractals;
+mod image_source;
+mod linear;
 mod open_simplex;
+mod passthrough;
 mod perlin;
+mod point_cloud;
 mod super_simplex;
 mod value;
+mod waves;
 mod worley;
@@ -1,4 +1,6 @@
-use crate::noise_fns::NoiseFn;
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
 
 /// Noise function that outputs a checkerboard pattern.
 ///
@@ -48,18 +50,30 @@ impl NoiseFn<[f64; 2]> for Checkerboard {
     fn get(&self, point: [f64; 2]) -> f64 {
         calculate_checkerboard(&point, self.size)
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Checkerboard")
+    }
 }
 
 impl NoiseFn<[f64; 3]> for Checkerboard {
     fn get(&self, point: [f64; 3]) -> f64 {
         calculate_checkerboard(&point, self.size)
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Checkerboard")
+    }
 }
 
 impl NoiseFn<[f64; 4]> for Checkerboard {
     fn get(&self, point: [f64; 4]) -> f64 {
         calculate_checkerboard(&point, self.size)
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Checkerboard")
+    }
 }
 
 fn calculate_checkerboard(point: &[f64], size: usize) -> f64 {
@@ -1,4 +1,4 @@
-use crate::noise_fns::NoiseFn;
+use crate::noise_fns::{NodeDesc, NoiseFn};
 
 /// Noise function that outputs a constant value.
 ///
@@ -23,4 +23,8 @@ impl<T: Copy> NoiseFn<T> for Constant {
     fn get(&self, _point: T) -> f64 {
         self.value
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Constant")
+    }
 }
@@ -0,0 +1,128 @@
+use crate::noise_fns::{NodeDesc, NoiseFn};
+
+/// Axis of the input point that a `Coordinate` function outputs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+/// Noise function that outputs one coordinate of the input point, mapped
+/// linearly from `range` onto [-1,1].
+///
+/// Values outside of `range` map outside of [-1,1]; they are not clamped.
+/// With the range set to the bounds of a `PlaneMapBuilder`, the X axis
+/// outputs the normalized column position of each cell. This makes it the
+/// simplest non-constant source, useful for building custom ramps and for
+/// testing selectors and modifiers such as `Select`, `Curve` and `Terrace`.
+#[derive(Clone, Copy, Debug)]
+pub struct Coordinate {
+    /// Axis whose coordinate is output. Default is `Axis::X`.
+    pub axis: Axis,
+
+    /// Interval mapped onto [-1,1]. Default is (-1.0, 1.0), which outputs the
+    /// coordinate unchanged.
+    pub range: (f64, f64),
+}
+
+impl Coordinate {
+    pub const DEFAULT_AXIS: Axis = Axis::X;
+    pub const DEFAULT_RANGE: (f64, f64) = (-1.0, 1.0);
+
+    pub fn new() -> Self {
+        Self {
+            axis: Self::DEFAULT_AXIS,
+            range: Self::DEFAULT_RANGE,
+        }
+    }
+
+    pub fn set_axis(self, axis: Axis) -> Self {
+        Self { axis, ..self }
+    }
+
+    pub fn set_range(self, lower: f64, upper: f64) -> Self {
+        assert!(lower != upper, "range must not be empty");
+
+        Self {
+            range: (lower, upper),
+            ..self
+        }
+    }
+
+    fn map(&self, coordinate: f64) -> f64 {
+        let (lower, upper) = self.range;
+
+        (coordinate - lower) / (upper - lower) * 2.0 - 1.0
+    }
+}
+
+impl Default for Coordinate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 2-dimensional points lie in the z = 0 plane.
+impl NoiseFn<[f64; 2]> for Coordinate {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get([point[0], point[1], 0.0])
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Coordinate")
+    }
+}
+
+impl NoiseFn<[f64; 3]> for Coordinate {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let coordinate = match self.axis {
+            Axis::X => point[0],
+            Axis::Y => point[1],
+            Axis::Z => point[2],
+        };
+
+        self.map(coordinate)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Coordinate")
+    }
+}
+
+impl NoiseFn<[f64; 4]> for Coordinate {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.get([point[0], point[1], point[2]])
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Coordinate")
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::utils::{NoiseMapBuilder, PlaneMapBuilder};
+
+    #[test]
+    fn x_axis_reproduces_column_positions() {
+        let coordinate = Coordinate::new().set_range(2.0, 6.0);
+        let map = PlaneMapBuilder::new(&coordinate)
+            .set_size(8, 4)
+            .set_x_bounds(2.0, 6.0)
+            .build();
+
+        for y in 0..4 {
+            for x in 0..8 {
+                let expected = x as f64 / 8.0 * 2.0 - 1.0;
+
+                assert!((map.get_value(x, y) - expected).abs() < 1e-12);
+            }
+        }
+
+        let y_axis = Coordinate::new().set_axis(Axis::Y);
+        assert_eq!(y_axis.get([0.3, 0.7, -0.2]), 0.7);
+        assert_eq!(y_axis.set_axis(Axis::Z).get([0.3, 0.7]), 0.0);
+    }
+}
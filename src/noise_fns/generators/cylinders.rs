@@ -1,14 +1,22 @@
-use crate::noise_fns::NoiseFn;
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
 
 /// Noise function that outputs concentric cylinders.
 ///
 /// This noise function outputs concentric cylinders centered on the origin. The
 /// cylinders are oriented along the z axis similar to the concentric rings of
 /// a tree. Each cylinder extends infinitely along the z axis.
+///
+/// Different frequencies along the x and y axes squash the cylinders into
+/// elliptical ones.
 #[derive(Clone, Copy, Debug)]
 pub struct Cylinders {
-    /// Frequency of the concentric objects.
+    /// Frequency of the concentric objects along the x axis.
     pub frequency: f64,
+
+    /// Frequency of the concentric objects along the y axis.
+    pub y_frequency: f64,
 }
 
 impl Cylinders {
@@ -17,11 +25,21 @@ impl Cylinders {
     pub fn new() -> Self {
         Self {
             frequency: Self::DEFAULT_FREQUENCY,
+            y_frequency: Self::DEFAULT_FREQUENCY,
         }
     }
 
+    /// Sets the frequency along both axes.
     pub fn set_frequency(self, frequency: f64) -> Self {
-        Self { frequency }
+        self.set_frequencies([frequency; 2])
+    }
+
+    /// Sets the frequencies along the x and y axes separately.
+    pub fn set_frequencies(self, frequencies: [f64; 2]) -> Self {
+        Self {
+            frequency: frequencies[0],
+            y_frequency: frequencies[1],
+        }
     }
 }
 
@@ -33,26 +51,38 @@ impl Default for Cylinders {
 
 impl NoiseFn<[f64; 2]> for Cylinders {
     fn get(&self, point: [f64; 2]) -> f64 {
-        calculate_cylinders(&point, self.frequency)
+        calculate_cylinders(&point, [self.frequency, self.y_frequency])
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Cylinders")
     }
 }
 
 impl NoiseFn<[f64; 3]> for Cylinders {
     fn get(&self, point: [f64; 3]) -> f64 {
-        calculate_cylinders(&point, self.frequency)
+        calculate_cylinders(&point, [self.frequency, self.y_frequency])
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Cylinders")
     }
 }
 
 impl NoiseFn<[f64; 4]> for Cylinders {
     fn get(&self, point: [f64; 4]) -> f64 {
-        calculate_cylinders(&point, self.frequency)
+        calculate_cylinders(&point, [self.frequency, self.y_frequency])
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Cylinders")
     }
 }
 
-fn calculate_cylinders(point: &[f64], frequency: f64) -> f64 {
-    // Scale the inputs by the frequency.
-    let x = point[0] * frequency;
-    let y = point[1] * frequency;
+fn calculate_cylinders(point: &[f64], frequencies: [f64; 2]) -> f64 {
+    // Scale the inputs by the frequencies.
+    let x = point[0] * frequencies[0];
+    let y = point[1] * frequencies[1];
 
     // Calculate the distance of the point from the origin.
     let dist_from_center = (x.powi(2) + y.powi(2)).sqrt();
@@ -64,3 +94,30 @@ fn calculate_cylinders(point: &[f64], frequency: f64) -> f64 {
     // Shift the result to be in the -1.0 to +1.0 range.
     1.0 - (nearest_dist * 4.0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frequencies_make_rings_elliptical() {
+        let circular = Cylinders::new();
+        let elliptical = Cylinders::new().set_frequencies([1.0, 2.0]);
+
+        for i in 1..20 {
+            let radius = i as f64 * 0.07;
+
+            // The rings are twice as narrow along the y axis as along x.
+            assert_eq!(
+                elliptical.get([radius, 0.0]),
+                elliptical.get([0.0, radius / 2.0])
+            );
+            assert_eq!(circular.get([radius, 0.0]), circular.get([0.0, radius]));
+        }
+        assert_ne!(elliptical.get([0.3, 0.0]), elliptical.get([0.0, 0.3]));
+
+        let uniform = Cylinders::new().set_frequency(2.0);
+        assert_eq!(uniform.frequency, 2.0);
+        assert_eq!(uniform.y_frequency, 2.0);
+    }
+}
@@ -0,0 +1,172 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{NodeDesc, NoiseFn, Perlin, Seedable},
+};
+use core::f64::consts::PI;
+
+/// Noise function that outputs a field of sand dunes.
+///
+/// Dune crests are sharp ridges running perpendicular to the wind direction,
+/// spaced `1 / dune_frequency` apart. The crests are bent by low-frequency
+/// Perlin noise so they meander, and their height is modulated by
+/// high-frequency Perlin detail to add ripples.
+#[derive(Clone, Copy, Debug)]
+pub struct Dunes {
+    /// Direction the wind blows toward, in radians counter-clockwise from the
+    /// positive x axis.
+    pub wind_direction: f64,
+
+    /// Number of dune crests per unit length along the wind direction.
+    pub dune_frequency: f64,
+
+    /// Frequency of the Perlin detail applied on top of the dunes.
+    pub detail_frequency: f64,
+
+    perlin: Perlin,
+}
+
+impl Dunes {
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_WIND_DIRECTION: f64 = 0.0;
+    pub const DEFAULT_DUNE_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_DETAIL_FREQUENCY: f64 = 8.0;
+
+    // How far the crests meander, in dune spacings.
+    const MEANDER: f64 = 0.35;
+
+    // Fraction of the dune height that is modulated by the detail.
+    const DETAIL_WEIGHT: f64 = 0.2;
+
+    pub fn new() -> Self {
+        Self {
+            wind_direction: Self::DEFAULT_WIND_DIRECTION,
+            dune_frequency: Self::DEFAULT_DUNE_FREQUENCY,
+            detail_frequency: Self::DEFAULT_DETAIL_FREQUENCY,
+            perlin: Perlin::new().set_seed(Self::DEFAULT_SEED),
+        }
+    }
+
+    pub fn set_wind_direction(self, wind_direction: f64) -> Self {
+        Self {
+            wind_direction,
+            ..self
+        }
+    }
+
+    pub fn set_dune_frequency(self, dune_frequency: f64) -> Self {
+        Self {
+            dune_frequency,
+            ..self
+        }
+    }
+
+    pub fn set_detail_frequency(self, detail_frequency: f64) -> Self {
+        Self {
+            detail_frequency,
+            ..self
+        }
+    }
+}
+
+impl Default for Dunes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Seedable for Dunes {
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            perlin: self.perlin.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.perlin.seed()
+    }
+}
+
+impl NoiseFn<[f64; 2]> for Dunes {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let (sin, cos) = self.wind_direction.sin_cos();
+
+        // Position along the wind, measured in dune spacings, with the crests
+        // bent by low-frequency noise.
+        let along = (point[0] * cos + point[1] * sin) * self.dune_frequency;
+        let meander = self
+            .perlin
+            .get(math::mul2(point, self.dune_frequency * 0.25));
+        let phase = along + meander * Self::MEANDER;
+
+        // Sharp crest at every whole phase, falling off to flat troughs.
+        let ridge = 1.0 - (phase * PI).sin().abs();
+        let ridge = ridge * ridge;
+
+        // Offset the detail so it's decorrelated from the meander.
+        let detail = self.perlin.get(math::add2(
+            math::mul2(point, self.detail_frequency),
+            [31.7, 17.3],
+        ));
+        let height = ridge * (1.0 - Self::DETAIL_WEIGHT + Self::DETAIL_WEIGHT * detail);
+
+        height * 2.0 - 1.0
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Dunes")
+    }
+}
+
+/// The dunes form a heightfield over the xy plane, so the z coordinate is
+/// ignored.
+impl NoiseFn<[f64; 3]> for Dunes {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get([point[0], point[1]])
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Dunes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Mean absolute change in output over a small step along `step`.
+    fn variation(dunes: &Dunes, step: [f64; 2]) -> f64 {
+        let mut total = 0.0;
+        for y in 0..40 {
+            for x in 0..40 {
+                let point = [x as f64 * 0.173, y as f64 * 0.173];
+                total += (dunes.get(math::add2(point, step)) - dunes.get(point)).abs();
+            }
+        }
+        total
+    }
+
+    #[test]
+    fn wind_direction_rotates_crests() {
+        let dunes = Dunes::new().set_detail_frequency(0.0);
+
+        // Wind along x: crests run along y, so output changes faster along x.
+        assert!(variation(&dunes, [0.05, 0.0]) > variation(&dunes, [0.0, 0.05]) * 2.0);
+
+        // Wind along y: the pattern is rotated by 90 degrees.
+        let dunes = dunes.set_wind_direction(PI / 2.0);
+        assert!(variation(&dunes, [0.0, 0.05]) > variation(&dunes, [0.05, 0.0]) * 2.0);
+    }
+
+    #[test]
+    fn output_in_range() {
+        let dunes = Dunes::new().set_seed(3);
+
+        for i in 0..500 {
+            let value = dunes.get([i as f64 * 0.37 - 20.0, i as f64 * 0.11]);
+            assert!((-1.0..=1.0).contains(&value));
+        }
+    }
+}
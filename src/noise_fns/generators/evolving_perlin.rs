@@ -0,0 +1,106 @@
+use crate::noise_fns::{NodeDesc, NoiseFn, Perlin, Seedable};
+
+/// Noise function that morphs between two realizations of Perlin noise.
+///
+/// The output is a linear blend of Perlin noise with two different seeds,
+/// controlled by `t`: at `t = 0.0` the output is the noise of the first seed,
+/// at `t = 1.0` the noise of the second seed. Slowly advancing `t` makes
+/// terrain evolve smoothly from one world into the other.
+///
+/// Halfway between the seeds the two sources partially cancel out, so the
+/// output has a smaller amplitude than at either end.
+#[derive(Clone, Copy, Debug)]
+pub struct EvolvingPerlin {
+    /// Blend factor between the first and the second seed.
+    pub t: f64,
+
+    first: Perlin,
+    second: Perlin,
+}
+
+impl EvolvingPerlin {
+    pub const DEFAULT_SEEDS: (u32, u32) = (0, 1);
+    pub const DEFAULT_T: f64 = 0.0;
+
+    pub fn new() -> Self {
+        Self {
+            t: Self::DEFAULT_T,
+            first: Perlin::new().set_seed(Self::DEFAULT_SEEDS.0),
+            second: Perlin::new().set_seed(Self::DEFAULT_SEEDS.1),
+        }
+    }
+
+    pub fn set_seeds(self, first: u32, second: u32) -> Self {
+        Self {
+            first: self.first.set_seed(first),
+            second: self.second.set_seed(second),
+            ..self
+        }
+    }
+
+    pub fn seeds(&self) -> (u32, u32) {
+        (self.first.seed(), self.second.seed())
+    }
+
+    pub fn set_t(self, t: f64) -> Self {
+        Self { t, ..self }
+    }
+
+    fn blend(&self, first: f64, second: f64) -> f64 {
+        // Weighting both ends keeps the output identical to the source noise
+        // at t = 0 and t = 1.
+        first * (1.0 - self.t) + second * self.t
+    }
+}
+
+impl Default for EvolvingPerlin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Setting the seed uses `seed` for the first realization and the next seed
+/// for the second one.
+impl Seedable for EvolvingPerlin {
+    fn set_seed(self, seed: u32) -> Self {
+        self.set_seeds(seed, seed.wrapping_add(1))
+    }
+
+    fn seed(&self) -> u32 {
+        self.first.seed()
+    }
+}
+
+impl<T> NoiseFn<T> for EvolvingPerlin
+where
+    T: Copy,
+    Perlin: NoiseFn<T>,
+{
+    fn get(&self, point: T) -> f64 {
+        self.blend(self.first.get(point), self.second.get(point))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("EvolvingPerlin")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn endpoints_match_seeds() {
+        let noise = EvolvingPerlin::new().set_seeds(7, 42);
+        let first = Perlin::new().set_seed(7);
+        let second = Perlin::new().set_seed(42);
+
+        for &point in &[[0.3, 0.7, 1.1], [12.5, -3.25, 0.5], [-100.1, 4.9, 7.7]] {
+            assert_eq!(noise.set_t(0.0).get(point), first.get(point));
+            assert_eq!(noise.set_t(1.0).get(point), second.get(point));
+
+            let halfway = noise.set_t(0.5).get(point);
+            assert!((halfway - (first.get(point) + second.get(point)) / 2.0).abs() < 1e-12);
+        }
+    }
+}
@@ -1,16 +1,49 @@
 pub use self::basicmulti::*;
 pub use self::billow::*;
 pub use self::fbm::*;
+pub use self::fractal::*;
 pub use self::hybridmulti::*;
 pub use self::ridgedmulti::*;
 
 mod basicmulti;
 mod billow;
 mod fbm;
+mod fractal;
 mod hybridmulti;
 mod ridgedmulti;
 
-use crate::noise_fns::{Perlin, Seedable};
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{NodeDesc, NoiseFn, Perlin, Seedable},
+};
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+/// Error returned by the fallible setters of the fractal functions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FractalError {
+    /// The requested number of octaves is 0 or greater than `max`.
+    OctavesOutOfRange { requested: usize, max: usize },
+}
+
+impl fmt::Display for FractalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FractalError::OctavesOutOfRange { requested, max } => write!(
+                f,
+                "octave count {} is outside the supported range 1..={}",
+                requested, max
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FractalError {}
 
 /// Trait for `MultiFractal` functions
 pub trait MultiFractal {
@@ -21,12 +54,427 @@ pub trait MultiFractal {
     fn set_lacunarity(self, lacunarity: f64) -> Self;
 
     fn set_persistence(self, persistence: f64) -> Self;
+
+    /// Returns the amplitude each octave's signal is scaled by, one weight
+    /// per octave.
+    ///
+    /// Unless custom weights are set, these are the default weights for the
+    /// octave count and persistence passed to `set_octaves` and
+    /// `set_persistence`. The default weight of octave `x` is
+    /// `persistence^x`, except that `HybridMulti` also scales the first
+    /// octave by the persistence.
+    fn spectral_weights(&self) -> &[f64];
+
+    /// Overrides the amplitude each octave's signal is scaled by, which
+    /// allows shaping the spectrum in ways the persistence can't, such as
+    /// boosting the higher frequencies.
+    ///
+    /// There must be one weight per octave. The weights are tied to the
+    /// octave count they were set for: calling `set_octaves` with a different
+    /// count discards them, and the default weights are used again until new
+    /// weights are set, even if the original count is restored. Changing the
+    /// persistence keeps custom weights.
+    fn set_spectral_weights(self, spectral_weights: Vec<f64>) -> Self;
+}
+
+// The fractals share the same seed and octave bookkeeping and only differ in
+// how each octave is shaped and accumulated. Implementing the traits in one
+// place keeps the handling of the octave count and the per-octave sources
+// consistent between them.
+macro_rules! impl_fractal_base {
+    ($($fractal:ident),*) => {
+        $(
+            impl MultiFractal for $fractal {
+                fn set_octaves(self, octaves: usize) -> Self {
+                    let octaves = math::clamp(octaves, 1, Self::MAX_OCTAVES);
+
+                    let fractal = if self.octaves == octaves {
+                        self
+                    } else {
+                        Self {
+                            octaves,
+                            sources: build_sources(self.seed, octaves),
+                            custom_weights: false,
+                            ..self
+                        }
+                    };
+
+                    fractal.octaves_set().refresh_spectral_weights()
+                }
+
+                fn set_frequency(self, frequency: f64) -> Self {
+                    Self { frequency, ..self }
+                }
+
+                fn set_lacunarity(self, lacunarity: f64) -> Self {
+                    Self { lacunarity, ..self }
+                }
+
+                fn set_persistence(self, persistence: f64) -> Self {
+                    Self {
+                        persistence,
+                        ..self
+                    }
+                    .refresh_spectral_weights()
+                }
+
+                fn spectral_weights(&self) -> &[f64] {
+                    &self.spectral_weights
+                }
+
+                fn set_spectral_weights(self, spectral_weights: Vec<f64>) -> Self {
+                    assert_eq!(
+                        spectral_weights.len(),
+                        self.octaves,
+                        "expected one spectral weight per octave"
+                    );
+
+                    Self {
+                        spectral_weights,
+                        custom_weights: true,
+                        ..self
+                    }
+                }
+            }
+
+            impl $fractal {
+                // The default weights are computed from the persistence field
+                // when sampling, so that writing to it directly takes effect.
+                // The stored ones only back `spectral_weights`.
+                fn spectral_weight(&self, x: usize) -> f64 {
+                    if self.custom_weights {
+                        self.spectral_weights[x]
+                    } else {
+                        self.default_spectral_weight(x)
+                    }
+                }
+
+                fn refresh_spectral_weights(self) -> Self {
+                    if self.custom_weights {
+                        return self;
+                    }
+
+                    let spectral_weights = (0..self.octaves)
+                        .map(|x| self.default_spectral_weight(x))
+                        .collect();
+
+                    Self {
+                        spectral_weights,
+                        ..self
+                    }
+                }
+            }
+
+            impl Seedable for $fractal {
+                fn set_seed(self, seed: u32) -> Self {
+                    if self.seed == seed {
+                        return self;
+                    }
+
+                    Self {
+                        seed,
+                        sources: build_sources(seed, self.octaves),
+                        ..self
+                    }
+                }
+
+                fn seed(&self) -> u32 {
+                    self.seed
+                }
+            }
+        )*
+    };
+}
+
+/// Lets a fractal reset the parameters that only apply to a particular octave
+/// count, such as a faded highest octave.
+trait FractalHooks: Sized {
+    /// Called by `set_octaves` once the octave count is set.
+    fn octaves_set(self) -> Self {
+        self
+    }
+}
+
+impl FractalHooks for BasicMulti {}
+impl FractalHooks for HybridMulti {}
+
+// Each octave owns a separately seeded source, and by the 32nd octave the
+// default lacunarity has raised the frequency by a factor of about 10^10, so
+// the fractional part of the scaled input is mostly rounding error and further
+// octaves only add cost. `MAX_OCTAVES` caps the octave count at that point.
+macro_rules! impl_try_set_octaves {
+    ($($fractal:ident),*) => {
+        $(
+            impl $fractal {
+                /// Sets the number of octaves, like `MultiFractal::set_octaves`,
+                /// but returns an error instead of clamping when `octaves` is
+                /// not between 1 and `MAX_OCTAVES`.
+                pub fn try_set_octaves(self, octaves: usize) -> Result<Self, FractalError> {
+                    if octaves == 0 || octaves > Self::MAX_OCTAVES {
+                        return Err(FractalError::OctavesOutOfRange {
+                            requested: octaves,
+                            max: Self::MAX_OCTAVES,
+                        });
+                    }
+
+                    Ok(self.set_octaves(octaves))
+                }
+            }
+        )*
+    };
+}
+
+// `Fbm`, `Billow` and `RidgedMulti` are a `Fractal` with a preset octave
+// transform. They dereference to it for its fields and accessors, and forward
+// the builder methods so that those keep returning the wrapper.
+macro_rules! impl_fractal_wrapper {
+    ($($wrapper:ident),*) => {
+        $(
+            impl $wrapper {
+                /// Sets the number of octaves, like `MultiFractal::set_octaves`,
+                /// but returns an error instead of clamping when `octaves` is
+                /// not between 1 and `MAX_OCTAVES`.
+                pub fn try_set_octaves(self, octaves: usize) -> Result<Self, FractalError> {
+                    self.fractal
+                        .try_set_octaves(octaves)
+                        .map(|fractal| Self { fractal })
+                }
+
+                fn map_fractal<F>(self, f: F) -> Self
+                where
+                    F: FnOnce(Fractal) -> Fractal,
+                {
+                    Self {
+                        fractal: f(self.fractal),
+                    }
+                }
+            }
+
+            impl Default for $wrapper {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+
+            impl Deref for $wrapper {
+                type Target = Fractal;
+
+                fn deref(&self) -> &Fractal {
+                    &self.fractal
+                }
+            }
+
+            impl DerefMut for $wrapper {
+                fn deref_mut(&mut self) -> &mut Fractal {
+                    &mut self.fractal
+                }
+            }
+
+            impl MultiFractal for $wrapper {
+                fn set_octaves(self, octaves: usize) -> Self {
+                    self.map_fractal(|fractal| fractal.set_octaves(octaves))
+                }
+
+                fn set_frequency(self, frequency: f64) -> Self {
+                    self.map_fractal(|fractal| fractal.set_frequency(frequency))
+                }
+
+                fn set_lacunarity(self, lacunarity: f64) -> Self {
+                    self.map_fractal(|fractal| fractal.set_lacunarity(lacunarity))
+                }
+
+                fn set_persistence(self, persistence: f64) -> Self {
+                    self.map_fractal(|fractal| fractal.set_persistence(persistence))
+                }
+
+                fn spectral_weights(&self) -> &[f64] {
+                    self.fractal.spectral_weights()
+                }
+
+                fn set_spectral_weights(self, spectral_weights: Vec<f64>) -> Self {
+                    self.map_fractal(|fractal| fractal.set_spectral_weights(spectral_weights))
+                }
+            }
+
+            impl Seedable for $wrapper {
+                fn set_seed(self, seed: u32) -> Self {
+                    self.map_fractal(|fractal| fractal.set_seed(seed))
+                }
+
+                fn seed(&self) -> u32 {
+                    self.fractal.seed()
+                }
+            }
+
+            impl NoiseFn<[f64; 2]> for $wrapper {
+                fn get(&self, point: [f64; 2]) -> f64 {
+                    self.fractal.get(point)
+                }
+
+                fn describe(&self) -> NodeDesc {
+                    NodeDesc::new(stringify!($wrapper))
+                }
+            }
+
+            impl NoiseFn<[f64; 3]> for $wrapper {
+                fn get(&self, point: [f64; 3]) -> f64 {
+                    self.fractal.get(point)
+                }
+
+                fn describe(&self) -> NodeDesc {
+                    NodeDesc::new(stringify!($wrapper))
+                }
+            }
+
+            impl NoiseFn<[f64; 4]> for $wrapper {
+                fn get(&self, point: [f64; 4]) -> f64 {
+                    self.fractal.get(point)
+                }
+
+                fn describe(&self) -> NodeDesc {
+                    NodeDesc::new(stringify!($wrapper))
+                }
+            }
+        )*
+    };
+}
+
+impl_fractal_base!(BasicMulti, Fractal, HybridMulti);
+impl_try_set_octaves!(BasicMulti, Fractal, HybridMulti);
+impl_fractal_wrapper!(Billow, Fbm, RidgedMulti);
+
+/// Returns the divisor that scales a sum of octaves with the given amplitudes
+/// into the [-1,1] range, as used by `OctaveTransform::Identity`.
+///
+/// The octaves are largely uncorrelated, so dividing by the sum of their
+/// amplitudes would make the spread of the output shrink as octaves are
+/// added. Instead the divisor grows with the square root of the sum of the
+/// squared amplitudes, which keeps the standard deviation the same for any
+/// octave count. It is scaled to converge to the amplitude sum of infinitely
+/// many octaves, `1 / (1 - p)` for persistence `p`, so the output stays
+/// bounded by that of the full series. For a persistence of 1.0 or more the
+/// series diverges, and the amplitudes are summed directly.
+fn fbm_normalization<I>(persistence: f64, amplitudes: I) -> f64
+where
+    I: Iterator<Item = f64>,
+{
+    let persistence = persistence.abs();
+
+    if persistence >= 1.0 {
+        return amplitudes.sum();
+    }
+
+    let squares: f64 = amplitudes.map(|amplitude| amplitude * amplitude).sum();
+
+    (squares * (1.0 - persistence * persistence)).sqrt() / (1.0 - persistence)
 }
 
 fn build_sources(seed: u32, octaves: usize) -> Vec<Perlin> {
     let mut sources = Vec::with_capacity(octaves);
     for x in 0..octaves {
-        sources.push(Perlin::new().set_seed(seed + x as u32));
+        sources.push(Perlin::new().set_seed(seed.wrapping_add(x as u32)));
     }
     sources
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! check_sources {
+        ($fractal:expr) => {{
+            let fractal = $fractal.set_seed(7).set_octaves(4);
+            assert_eq!(fractal.sources.len(), 4);
+            for (x, source) in fractal.sources.iter().enumerate() {
+                assert_eq!(source.seed(), 7 + x as u32);
+            }
+
+            let fractal = fractal.set_seed(3);
+            assert_eq!(fractal.sources.len(), 4);
+            assert_eq!(fractal.sources[3].seed(), 6);
+
+            let fractal = fractal.set_octaves(100);
+            assert_eq!(fractal.octaves, 32);
+            assert_eq!(fractal.sources.len(), 32);
+        }};
+    }
+
+    #[test]
+    fn set_octaves_rebuilds_sources() {
+        check_sources!(BasicMulti::new());
+        check_sources!(Billow::new());
+        check_sources!(Fbm::new());
+        check_sources!(Fractal::new());
+        check_sources!(HybridMulti::new());
+        check_sources!(RidgedMulti::new());
+    }
+
+    macro_rules! check_spectral_weights {
+        ($fractal:expr, $first_default:expr) => {{
+            let persistence = 0.6_f64;
+            let lacunarity = 2.0_f64;
+            let fractal = $fractal
+                .set_persistence(persistence)
+                .set_lacunarity(lacunarity)
+                .set_octaves(5);
+            let weights = fractal.spectral_weights();
+            assert_eq!(weights.len(), 5);
+
+            // The default weights fall off with the frequency like fBm with
+            // the Hurst exponent the persistence and lacunarity correspond to.
+            let h = -persistence.ln() / lacunarity.ln();
+            for (x, &weight) in weights.iter().enumerate().skip($first_default) {
+                let frequency = lacunarity.powi(x as i32);
+                assert!((weight - frequency.powf(-h)).abs() < 1e-12);
+            }
+
+            let custom = fractal
+                .clone()
+                .set_spectral_weights(vec![1.0, 0.1, 0.8, 0.1, 0.6]);
+            let mut difference = 0.0;
+            for i in 0..50 {
+                let point = [i as f64 * 0.173 - 3.0, i as f64 * 0.071];
+                difference += (custom.get(point) - fractal.get(point)).abs();
+            }
+            assert!(difference > 1e-3);
+
+            let custom = custom.set_persistence(0.3);
+            assert_eq!(custom.spectral_weights(), &[1.0, 0.1, 0.8, 0.1, 0.6][..]);
+
+            let restored = custom.set_octaves(3).set_octaves(5);
+            let defaults = fractal.set_persistence(0.3);
+            assert_eq!(restored.spectral_weights(), defaults.spectral_weights());
+            assert_eq!(restored.get([0.3, 0.7]), defaults.get([0.3, 0.7]));
+        }};
+    }
+
+    #[test]
+    fn spectral_weights_default_to_power_law() {
+        check_spectral_weights!(BasicMulti::new(), 0);
+        check_spectral_weights!(Billow::new(), 0);
+        check_spectral_weights!(Fbm::new(), 0);
+        check_spectral_weights!(Fractal::new(), 0);
+        check_spectral_weights!(RidgedMulti::new(), 0);
+
+        // HybridMulti scales its first octave by the persistence as well.
+        check_spectral_weights!(HybridMulti::new(), 1);
+        let hybrid = HybridMulti::new().set_persistence(0.6);
+        assert_eq!(hybrid.spectral_weights()[0], 0.6);
+    }
+
+    #[test]
+    fn try_set_octaves_rejects_out_of_range() {
+        assert_eq!(
+            Fbm::new().try_set_octaves(64).err(),
+            Some(FractalError::OctavesOutOfRange {
+                requested: 64,
+                max: 32
+            })
+        );
+        assert!(RidgedMulti::new().try_set_octaves(0).is_err());
+
+        let fbm = Fbm::new().try_set_octaves(Fbm::MAX_OCTAVES).unwrap();
+        assert_eq!(fbm.octaves, Fbm::MAX_OCTAVES);
+        assert_eq!(Billow::new().try_set_octaves(3).unwrap().octaves, 3);
+    }
+}
@@ -1,6 +1,8 @@
 use crate::math;
 
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn, Perlin};
 
 /// Noise function that outputs heterogenous Multifractal noise.
 ///
@@ -42,15 +44,17 @@ pub struct BasicMulti {
     /// persistence produces "rougher" noise.
     pub persistence: f64,
 
-    seed: u32,
-    sources: Vec<Perlin>,
+    pub(super) seed: u32,
+    pub(super) spectral_weights: Vec<f64>,
+    pub(super) custom_weights: bool,
+    pub(super) sources: Vec<Perlin>,
 }
 
 impl BasicMulti {
     pub const DEFAULT_SEED: u32 = 0;
     pub const DEFAULT_OCTAVES: usize = 6;
     pub const DEFAULT_FREQUENCY: f64 = 2.0;
-    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
     pub const DEFAULT_PERSISTENCE: f64 = 0.5;
     pub const MAX_OCTAVES: usize = 32;
 
@@ -61,8 +65,16 @@ impl BasicMulti {
             frequency: Self::DEFAULT_FREQUENCY,
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
+            spectral_weights: Vec::new(),
+            custom_weights: false,
             sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVES),
         }
+        .refresh_spectral_weights()
+    }
+
+    /// The weight of octave `x` unless custom weights are set.
+    pub(super) fn default_spectral_weight(&self, x: usize) -> f64 {
+        self.persistence.powi(x as i32)
     }
 }
 
@@ -72,60 +84,12 @@ impl Default for BasicMulti {
     }
 }
 
-impl MultiFractal for BasicMulti {
-    fn set_octaves(self, mut octaves: usize) -> Self {
-        if self.octaves == octaves {
-            return self;
-        }
-
-        octaves = math::clamp(octaves, 1, Self::MAX_OCTAVES);
-        Self {
-            octaves,
-            sources: super::build_sources(self.seed, octaves),
-            ..self
-        }
-    }
-
-    fn set_frequency(self, frequency: f64) -> Self {
-        Self { frequency, ..self }
-    }
-
-    fn set_lacunarity(self, lacunarity: f64) -> Self {
-        Self { lacunarity, ..self }
-    }
-
-    fn set_persistence(self, persistence: f64) -> Self {
-        Self {
-            persistence,
-            ..self
-        }
-    }
-}
-
-impl Seedable for BasicMulti {
-    fn set_seed(self, seed: u32) -> Self {
-        if self.seed == seed {
-            return self;
-        }
-
-        Self {
-            seed,
-            sources: super::build_sources(seed, self.octaves),
-            ..self
-        }
-    }
-
-    fn seed(&self) -> u32 {
-        self.seed
-    }
-}
-
 /// 2-dimensional `BasicMulti` noise
 impl NoiseFn<[f64; 2]> for BasicMulti {
     fn get(&self, mut point: [f64; 2]) -> f64 {
-        // First unscaled octave of function; later octaves are scaled.
+        // First octave of function; later octaves are also scaled by the result.
         point = math::mul2(point, self.frequency);
-        let mut result = self.sources[0].get(point);
+        let mut result = self.sources[0].get(point) * self.spectral_weight(0);
 
         // Spectral construction inner loop, where the fractal is built.
         for x in 1..self.octaves {
@@ -136,7 +100,7 @@ impl NoiseFn<[f64; 2]> for BasicMulti {
             let mut signal = self.sources[x].get(point);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self.spectral_weight(x);
 
             // Scale the signal by the current 'altitude' of the function.
             signal *= result;
@@ -148,14 +112,18 @@ impl NoiseFn<[f64; 2]> for BasicMulti {
         // Scale the result to the [-1,1] range.
         result * 0.5
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("BasicMulti")
+    }
 }
 
 /// 3-dimensional `BasicMulti` noise
 impl NoiseFn<[f64; 3]> for BasicMulti {
     fn get(&self, mut point: [f64; 3]) -> f64 {
-        // First unscaled octave of function; later octaves are scaled.
+        // First octave of function; later octaves are also scaled by the result.
         point = math::mul3(point, self.frequency);
-        let mut result = self.sources[0].get(point);
+        let mut result = self.sources[0].get(point) * self.spectral_weight(0);
 
         // Spectral construction inner loop, where the fractal is built.
         for x in 1..self.octaves {
@@ -166,7 +134,7 @@ impl NoiseFn<[f64; 3]> for BasicMulti {
             let mut signal = self.sources[x].get(point);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self.spectral_weight(x);
 
             // Scale the signal by the current 'altitude' of the function.
             signal *= result;
@@ -178,14 +146,18 @@ impl NoiseFn<[f64; 3]> for BasicMulti {
         // Scale the result to the [-1,1] range.
         result * 0.5
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("BasicMulti")
+    }
 }
 
 /// 4-dimensional `BasicMulti` noise
 impl NoiseFn<[f64; 4]> for BasicMulti {
     fn get(&self, mut point: [f64; 4]) -> f64 {
-        // First unscaled octave of function; later octaves are scaled.
+        // First octave of function; later octaves are also scaled by the result.
         point = math::mul4(point, self.frequency);
-        let mut result = self.sources[0].get(point);
+        let mut result = self.sources[0].get(point) * self.spectral_weight(0);
 
         // Spectral construction inner loop, where the fractal is built.
         for x in 1..self.octaves {
@@ -196,7 +168,7 @@ impl NoiseFn<[f64; 4]> for BasicMulti {
             let mut signal = self.sources[x].get(point);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self.spectral_weight(x);
 
             // Scale the signal by the current 'altitude' of the function.
             signal *= result;
@@ -208,4 +180,8 @@ impl NoiseFn<[f64; 4]> for BasicMulti {
         // Scale the result to the [-1,1] range.
         result * 0.5
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("BasicMulti")
+    }
 }
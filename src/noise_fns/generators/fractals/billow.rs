@@ -1,5 +1,4 @@
-use crate::math::{self, scale_shift};
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+use super::{Fractal, OctaveTransform};
 
 /// Noise function that outputs "billowy" noise.
 ///
@@ -8,200 +7,25 @@ use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
 /// This noise function is nearly identical to fBm noise, except this noise
 /// function modifies each octave with an absolute-value function. See the
 /// documentation for fBm for more information.
+///
+/// This is a `Fractal` with `OctaveTransform::Abs`, and dereferences to it
+/// for its fields and accessors.
 #[derive(Clone, Debug)]
 pub struct Billow {
-    /// Total number of frequency octaves to generate the noise with.
-    ///
-    /// The number of octaves control the _amount of detail_ in the noise
-    /// function. Adding more octaves increases the detail, with the drawback
-    /// of increasing the calculation time.
-    pub octaves: usize,
-
-    /// The number of cycles per unit length that the noise function outputs.
-    pub frequency: f64,
-
-    /// A multiplier that determines how quickly the frequency increases for
-    /// each successive octave in the noise function.
-    ///
-    /// The frequency of each successive octave is equal to the product of the
-    /// previous octave's frequency and the lacunarity value.
-    ///
-    /// A lacunarity of 2.0 results in the frequency doubling every octave. For
-    /// almost all cases, 2.0 is a good value to use.
-    pub lacunarity: f64,
-
-    /// A multiplier that determines how quickly the amplitudes diminish for
-    /// each successive octave in the noise function.
-    ///
-    /// The amplitude of each successive octave is equal to the product of the
-    /// previous octave's amplitude and the persistence value. Increasing the
-    /// persistence produces "rougher" noise.
-    pub persistence: f64,
-
-    seed: u32,
-    sources: Vec<Perlin>,
+    pub(super) fractal: Fractal,
 }
 
 impl Billow {
-    pub const DEFAULT_SEED: u32 = 0;
-    pub const DEFAULT_OCTAVE_COUNT: usize = 6;
-    pub const DEFAULT_FREQUENCY: f64 = 1.0;
-    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
-    pub const DEFAULT_PERSISTENCE: f64 = 0.5;
-    pub const MAX_OCTAVES: usize = 32;
+    pub const DEFAULT_SEED: u32 = Fractal::DEFAULT_SEED;
+    pub const DEFAULT_OCTAVE_COUNT: usize = Fractal::DEFAULT_OCTAVE_COUNT;
+    pub const DEFAULT_FREQUENCY: f64 = Fractal::DEFAULT_FREQUENCY;
+    pub const DEFAULT_LACUNARITY: f64 = Fractal::DEFAULT_LACUNARITY;
+    pub const DEFAULT_PERSISTENCE: f64 = Fractal::DEFAULT_PERSISTENCE;
+    pub const MAX_OCTAVES: usize = Fractal::MAX_OCTAVES;
 
     pub fn new() -> Self {
         Self {
-            seed: Self::DEFAULT_SEED,
-            octaves: Self::DEFAULT_OCTAVE_COUNT,
-            frequency: Self::DEFAULT_FREQUENCY,
-            lacunarity: Self::DEFAULT_LACUNARITY,
-            persistence: Self::DEFAULT_PERSISTENCE,
-            sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVE_COUNT),
+            fractal: Fractal::new().set_octave_transform(OctaveTransform::Abs),
         }
     }
 }
-
-impl Default for Billow {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl MultiFractal for Billow {
-    fn set_octaves(self, mut octaves: usize) -> Self {
-        if self.octaves == octaves {
-            return self;
-        }
-
-        octaves = math::clamp(octaves, 1, Self::MAX_OCTAVES);
-        Self {
-            octaves,
-            sources: super::build_sources(self.seed, octaves),
-            ..self
-        }
-    }
-
-    fn set_frequency(self, frequency: f64) -> Self {
-        Self { frequency, ..self }
-    }
-
-    fn set_lacunarity(self, lacunarity: f64) -> Self {
-        Self { lacunarity, ..self }
-    }
-
-    fn set_persistence(self, persistence: f64) -> Self {
-        Self {
-            persistence,
-            ..self
-        }
-    }
-}
-
-impl Seedable for Billow {
-    fn set_seed(self, seed: u32) -> Self {
-        if self.seed == seed {
-            return self;
-        }
-
-        Self {
-            seed,
-            sources: super::build_sources(seed, self.octaves),
-            ..self
-        }
-    }
-
-    fn seed(&self) -> u32 {
-        self.seed
-    }
-}
-
-/// 2-dimensional Billow noise
-impl NoiseFn<[f64; 2]> for Billow {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
-        let mut result = 0.0;
-
-        point = math::mul2(point, self.frequency);
-
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
-
-            // Take the abs of the signal, then scale and shift back to
-            // the [-1,1] range.
-            signal = scale_shift(signal, 2.0);
-
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
-
-            // Add the signal to the result.
-            result += signal;
-
-            // Increase the frequency for the next octave.
-            point = math::mul2(point, self.lacunarity);
-        }
-
-        // Scale the result to the [-1,1] range.
-        result * 0.5
-    }
-}
-
-/// 3-dimensional Billow noise
-impl NoiseFn<[f64; 3]> for Billow {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
-        let mut result = 0.0;
-
-        point = math::mul3(point, self.frequency);
-
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
-
-            // Take the abs of the signal, then scale and shift back to
-            // the [-1,1] range.
-            signal = scale_shift(signal, 2.0);
-
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
-
-            // Add the signal to the result.
-            result += signal;
-
-            // Increase the frequency for the next octave.
-            point = math::mul3(point, self.lacunarity);
-        }
-
-        // Scale the result to the [-1,1] range.
-        result * 0.5
-    }
-}
-
-/// 4-dimensional Billow noise
-impl NoiseFn<[f64; 4]> for Billow {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
-        let mut result = 0.0;
-
-        point = math::mul4(point, self.frequency);
-
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
-
-            // Take the abs of the signal, then scale and shift back to
-            // the [-1,1] range.
-            signal = scale_shift(signal, 2.0);
-
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
-
-            // Add the signal to the output value.
-            result += signal;
-
-            // Increase the frequency for the next octave.
-            point = math::mul4(point, self.lacunarity);
-        }
-
-        // Scale the result to the [-1,1] range.
-        result * 0.5
-    }
-}
@@ -1,6 +1,5 @@
-use crate::math;
-
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+use super::{Fractal, OctaveTransform};
+use crate::noise_fns::DomainWarp;
 
 /// Noise function that outputs fBm (fractal Brownian motion) noise.
 ///
@@ -18,191 +17,264 @@ use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
 /// and ever-decreasing amplitude.
 ///
 /// fBm is commonly referred to as Perlin noise.
+///
+/// This is a `Fractal` with `OctaveTransform::Identity`, and dereferences to
+/// it for its fields and accessors.
 #[derive(Clone, Debug)]
 pub struct Fbm {
-    /// Total number of frequency octaves to generate the noise with.
-    ///
-    /// The number of octaves control the _amount of detail_ in the noise
-    /// function. Adding more octaves increases the detail, with the drawback
-    /// of increasing the calculation time.
-    pub octaves: usize,
-
-    /// The number of cycles per unit length that the noise function outputs.
-    pub frequency: f64,
-
-    /// A multiplier that determines how quickly the frequency increases for
-    /// each successive octave in the noise function.
-    ///
-    /// The frequency of each successive octave is equal to the product of the
-    /// previous octave's frequency and the lacunarity value.
-    ///
-    /// A lacunarity of 2.0 results in the frequency doubling every octave. For
-    /// almost all cases, 2.0 is a good value to use.
-    pub lacunarity: f64,
-
-    /// A multiplier that determines how quickly the amplitudes diminish for
-    /// each successive octave in the noise function.
-    ///
-    /// The amplitude of each successive octave is equal to the product of the
-    /// previous octave's amplitude and the persistence value. Increasing the
-    /// persistence produces "rougher" noise.
-    pub persistence: f64,
-
-    seed: u32,
-    sources: Vec<Perlin>,
+    pub(super) fractal: Fractal,
 }
 
 impl Fbm {
-    pub const DEFAULT_SEED: u32 = 0;
-    pub const DEFAULT_OCTAVE_COUNT: usize = 6;
-    pub const DEFAULT_FREQUENCY: f64 = 1.0;
-    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
-    pub const DEFAULT_PERSISTENCE: f64 = 0.5;
-    pub const MAX_OCTAVES: usize = 32;
+    pub const DEFAULT_SEED: u32 = Fractal::DEFAULT_SEED;
+    pub const DEFAULT_OCTAVE_COUNT: usize = Fractal::DEFAULT_OCTAVE_COUNT;
+    pub const DEFAULT_FREQUENCY: f64 = Fractal::DEFAULT_FREQUENCY;
+    pub const DEFAULT_LACUNARITY: f64 = Fractal::DEFAULT_LACUNARITY;
+    pub const DEFAULT_PERSISTENCE: f64 = Fractal::DEFAULT_PERSISTENCE;
+    pub const DEFAULT_OCTAVE_OFFSET: [f64; 4] = Fractal::DEFAULT_OCTAVE_OFFSET;
+    pub const DEFAULT_SLOPE_EROSION: f64 = Fractal::DEFAULT_SLOPE_EROSION;
+    pub const DEFAULT_PARTIAL_OCTAVE: f64 = Fractal::DEFAULT_PARTIAL_OCTAVE;
+    pub const MAX_OCTAVES: usize = Fractal::MAX_OCTAVES;
 
     pub fn new() -> Self {
         Self {
-            seed: Self::DEFAULT_SEED,
-            octaves: Self::DEFAULT_OCTAVE_COUNT,
-            frequency: Self::DEFAULT_FREQUENCY,
-            lacunarity: Self::DEFAULT_LACUNARITY,
-            persistence: Self::DEFAULT_PERSISTENCE,
-            sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVE_COUNT),
+            fractal: Fractal::new().set_octave_transform(OctaveTransform::Identity),
         }
     }
-}
 
-impl Default for Fbm {
-    fn default() -> Self {
-        Self::new()
+    /// Sets the offset added to the input value of each successive octave.
+    pub fn set_octave_offset(self, octave_offset: [f64; 4]) -> Self {
+        self.map_fractal(|fractal| fractal.set_octave_offset(octave_offset))
     }
-}
 
-impl MultiFractal for Fbm {
-    fn set_octaves(self, mut octaves: usize) -> Self {
-        if self.octaves == octaves {
-            return self;
-        }
-
-        octaves = math::clamp(octaves, 1, Self::MAX_OCTAVES);
-        Self {
-            octaves,
-            sources: super::build_sources(self.seed, octaves),
-            ..self
-        }
+    /// See `Fractal::set_octaves_for_resolution`.
+    pub fn set_octaves_for_resolution(self, pixels_per_unit: f64) -> Self {
+        self.map_fractal(|fractal| fractal.set_octaves_for_resolution(pixels_per_unit))
     }
 
-    fn set_frequency(self, frequency: f64) -> Self {
-        Self { frequency, ..self }
+    /// See `Fractal::set_octaves_f`.
+    pub fn set_octaves_f(self, octaves: f64) -> Self {
+        self.map_fractal(|fractal| fractal.set_octaves_f(octaves))
     }
 
-    fn set_lacunarity(self, lacunarity: f64) -> Self {
-        Self { lacunarity, ..self }
+    /// Sets how strongly steep slopes suppress the detail of higher octaves.
+    pub fn set_slope_erosion(self, slope_erosion: f64) -> Self {
+        self.map_fractal(|fractal| fractal.set_slope_erosion(slope_erosion))
     }
 
-    fn set_persistence(self, persistence: f64) -> Self {
-        Self {
-            persistence,
-            ..self
-        }
+    /// Returns this fBm with its input value warped by `warp_source`, scaled
+    /// by `warp_strength`. See `DomainWarp` for details.
+    pub fn warp<Warp>(&self, warp_strength: f64, warp_source: Warp) -> DomainWarp<&Self, Warp> {
+        DomainWarp::new(self, warp_source).set_warp_strength(warp_strength)
     }
 }
 
-impl Seedable for Fbm {
-    fn set_seed(self, seed: u32) -> Self {
-        if self.seed == seed {
-            return self;
-        }
-
-        Self {
-            seed,
-            sources: super::build_sources(seed, self.octaves),
-            ..self
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use crate::no_std::*;
+    use crate::noise_fns::{MultiFractal, NoiseFn};
+
+    #[test]
+    fn octave_offset() {
+        let fbm = Fbm::new();
+        let offset = fbm
+            .clone()
+            .set_octave_offset([0.3137, 0.7291, 0.1723, 0.5813]);
+        let point = [0.4, 1.3, 2.2];
+
+        assert_eq!(
+            fbm.get(point),
+            fbm.clone().set_octave_offset([0.0; 4]).get(point)
+        );
+        assert_ne!(fbm.get(point), offset.get(point));
     }
 
-    fn seed(&self) -> u32 {
-        self.seed
+    #[test]
+    fn slope_erosion_smooths_steep_areas() {
+        let plain = Fbm::new();
+        let eroded = Fbm::new().set_slope_erosion(2.0);
+        let base = &plain.sources[0];
+        let scale = plain.normalization();
+
+        // Collect the slope of the first octave and the detail added on top of
+        // it by the other octaves, with and without erosion.
+        let mut samples: Vec<(f64, f64, f64)> = (0..2000)
+            .map(|i| {
+                let point = [i as f64 * 0.137 + 0.05, i as f64 * 0.071 - 3.0];
+                let value = base.get(point);
+                let dx = base.get([point[0] + 1e-4, point[1]]) - value;
+                let dy = base.get([point[0], point[1] + 1e-4]) - value;
+
+                let slope = dx.hypot(dy) / 1e-4;
+                let plain_detail = (plain.get(point) - value / scale).abs();
+                let eroded_detail = (eroded.get(point) - value / scale).abs();
+                (slope, plain_detail, eroded_detail)
+            })
+            .collect();
+        samples.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        // Fraction of the detail that is kept by the erosion.
+        let kept = |samples: &[(f64, f64, f64)]| {
+            let plain: f64 = samples.iter().map(|s| s.1).sum();
+            let eroded: f64 = samples.iter().map(|s| s.2).sum();
+            eroded / plain
+        };
+        let (flat, steep) = samples.split_at(samples.len() / 2);
+
+        assert!(kept(steep) < kept(flat));
+        assert!(kept(flat) < 1.0);
+        assert_eq!(
+            Fbm::new().set_slope_erosion(0.0).get([0.4, 1.3]),
+            plain.get([0.4, 1.3])
+        );
     }
-}
-
-/// 2-dimensional Fbm noise
-impl NoiseFn<[f64; 2]> for Fbm {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
-        let mut result = 0.0;
 
-        point = math::mul2(point, self.frequency);
+    #[test]
+    fn octaves_sum_to_output() {
+        let fbm = Fbm::new()
+            .set_octave_offset([0.3137, 0.7291, 0.1723, 0.5813])
+            .set_octaves_f(4.5);
 
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
+        for i in 0..50 {
+            let point = [i as f64 * 0.173 - 3.0, i as f64 * 0.091, 0.4];
+            let sum: f64 = (0..fbm.octaves)
+                .map(|x| fbm.octave(x).get(point) * fbm.octave_amplitude(x))
+                .sum();
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            assert!((sum - fbm.get(point)).abs() < 1e-9);
 
-            // Add the signal to the result.
-            result += signal;
+            let point = [point[0], point[1]];
+            let sum: f64 = (0..fbm.octaves)
+                .map(|x| fbm.octave(x).get(point) * fbm.octave_amplitude(x))
+                .sum();
 
-            // Increase the frequency for the next octave.
-            point = math::mul2(point, self.lacunarity);
+            assert!((sum - fbm.get(point)).abs() < 1e-9);
         }
-
-        // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - self.persistence.powi(self.octaves as i32 - 1);
-        result / scale
     }
-}
-
-/// 3-dimensional Fbm noise
-impl NoiseFn<[f64; 3]> for Fbm {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
-        let mut result = 0.0;
-
-        point = math::mul3(point, self.frequency);
 
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
-
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
-
-            // Add the signal to the result.
-            result += signal;
+    #[test]
+    fn fractional_octaves() {
+        let points: Vec<[f64; 2]> = (0..2000)
+            .map(|i| [i as f64 * 0.173 + 0.05, i as f64 * 0.091 - 7.0])
+            .collect();
+        let variance = |fbm: &Fbm| {
+            let values: Vec<f64> = points.iter().map(|&p| fbm.get(p)).collect();
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+        };
+
+        let two = Fbm::new().set_octaves(2);
+        let three = Fbm::new().set_octaves(3);
+        let half = Fbm::new().set_octaves_f(2.5);
+
+        assert_eq!(half.octaves, 3);
+        for &point in &points[..20] {
+            assert_eq!(Fbm::new().set_octaves_f(2.0).get(point), two.get(point));
+        }
 
-            // Increase the frequency for the next octave.
-            point = math::mul3(point, self.lacunarity);
+        // Setting a whole octave count afterwards drops the fraction.
+        for &point in &points[..20] {
+            let six = Fbm::new().set_octaves(6);
+            assert_eq!(half.clone().set_octaves(6).get(point), six.get(point));
+            assert_eq!(half.clone().set_octaves(3).get(point), three.get(point));
+            let tried = half.clone().try_set_octaves(6).unwrap();
+            assert_eq!(tried.get(point), six.get(point));
         }
 
-        // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - self.persistence.powi(self.octaves as i32 - 1);
-        result / scale
+        let (low, high) = (variance(&two), variance(&three));
+        let middle = variance(&half);
+        assert!(low.min(high) < middle && middle < low.max(high));
     }
-}
 
-/// 4-dimensional Fbm noise
-impl NoiseFn<[f64; 4]> for Fbm {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
-        let mut result = 0.0;
-
-        point = math::mul4(point, self.frequency);
-
-        for x in 0..self.octaves {
-            // Get the signal.
-            let mut signal = self.sources[x].get(point);
-
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+    #[test]
+    fn normalization_keeps_deviation_and_bound() {
+        // The octave amplitudes sum to at most 1, so the output stays within
+        // the range of the source.
+        for &persistence in &[0.25, 0.5, 0.7, 1.3] {
+            for &octaves in &[1.0, 2.0, 3.5, 6.0, 9.25] {
+                let fbm = Fbm::new()
+                    .set_persistence(persistence)
+                    .set_octaves_f(octaves);
+                let total: f64 = (0..fbm.octaves).map(|x| fbm.octave_amplitude(x)).sum();
+
+                assert!(
+                    total <= 1.0 + 1e-12,
+                    "{} {}: {}",
+                    persistence,
+                    octaves,
+                    total
+                );
+                if persistence >= 1.0 {
+                    assert!((total - 1.0).abs() < 1e-12);
+                }
+            }
+        }
 
-            // Add the signal to the result.
-            result += signal;
+        let points: Vec<[f64; 2]> = (0..10_000)
+            .map(|i| {
+                [
+                    (i % 100) as f64 * 0.0731 + 0.05,
+                    (i / 100) as f64 * 0.0693 - 7.0,
+                ]
+            })
+            .collect();
+        let deviation = |octaves| {
+            let fbm = Fbm::new().set_octaves(octaves);
+            let values: Vec<f64> = points.iter().map(|&p| fbm.get(p)).collect();
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+            let variance =
+                values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64;
+
+            variance.sqrt()
+        };
+
+        let settled = deviation(8);
+        for octaves in 2..8 {
+            let ratio = deviation(octaves) / settled;
+            assert!(ratio > 0.9 && ratio < 1.1, "{} octaves: {}", octaves, ratio);
+        }
+    }
 
-            // Increase the frequency for the next octave.
-            point = math::mul4(point, self.lacunarity);
+    #[test]
+    fn field_writes_are_normalized() {
+        let mut written = Fbm::new();
+        written.persistence = 0.8;
+        written.partial_octave = 0.5;
+
+        for i in 0..20 {
+            let point = [i as f64 * 0.173 - 3.0, i as f64 * 0.091];
+
+            assert_eq!(
+                written.get(point),
+                Fbm::new()
+                    .set_persistence(0.8)
+                    .set_octaves_f(5.5)
+                    .get(point)
+            );
         }
+    }
 
-        // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - self.persistence.powi(self.octaves as i32 - 1);
-        result / scale
+    #[test]
+    fn octaves_for_resolution() {
+        let fbm = Fbm::new().set_frequency(1.0).set_lacunarity(2.0);
+
+        // Octave frequencies are 1, 2, 4, 8, ..., and a sampling density of 16
+        // resolves frequencies up to 8.
+        assert_eq!(fbm.clone().set_octaves_for_resolution(16.0).octaves, 4);
+        assert_eq!(fbm.clone().set_octaves_for_resolution(0.5).octaves, 1);
+
+        let mut previous = 1;
+        for i in 0..80 {
+            let octaves = fbm
+                .clone()
+                .set_octaves_for_resolution(2.0_f64.powf(i as f64 * 0.5))
+                .octaves;
+            assert!(octaves >= previous);
+            assert!(octaves <= Fbm::MAX_OCTAVES);
+            previous = octaves;
+        }
+        assert_eq!(previous, Fbm::MAX_OCTAVES);
     }
 }
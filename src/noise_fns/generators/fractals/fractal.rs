@@ -0,0 +1,642 @@
+use super::{fbm_normalization, FractalHooks};
+use crate::math::{self, scale_shift};
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{MultiFractal, NodeDesc, NoiseFn, Perlin};
+
+/// Noise function that sums octaves of Perlin noise with a selectable
+/// per-octave transform.
+///
+/// The transform decides how each octave is shaped before it is accumulated,
+/// and with it which of the classic fractals is produced:
+///
+/// * `OctaveTransform::Identity` produces fBm, as used by `Fbm`.
+/// * `OctaveTransform::Abs` produces billowy noise, as used by `Billow`.
+/// * `OctaveTransform::Ridge` produces ridged multifractal noise, as used by
+///   `RidgedMulti`.
+///
+/// `Fbm`, `Billow` and `RidgedMulti` are thin wrappers that preset the
+/// transform, so every parameter of this type works with every transform.
+/// This makes it possible to switch between the variants on a single noise
+/// function, for example to choose the look of a layer at runtime.
+#[derive(Clone, Debug)]
+pub struct Fractal {
+    /// Total number of frequency octaves to generate the noise with.
+    ///
+    /// The number of octaves control the _amount of detail_ in the noise
+    /// function. Adding more octaves increases the detail, with the drawback
+    /// of increasing the calculation time.
+    pub octaves: usize,
+
+    /// The number of cycles per unit length that the noise function outputs.
+    pub frequency: f64,
+
+    /// A multiplier that determines how quickly the frequency increases for
+    /// each successive octave in the noise function.
+    ///
+    /// The frequency of each successive octave is equal to the product of the
+    /// previous octave's frequency and the lacunarity value.
+    ///
+    /// A lacunarity of 2.0 results in the frequency doubling every octave. For
+    /// almost all cases, 2.0 is a good value to use.
+    pub lacunarity: f64,
+
+    /// A multiplier that determines how quickly the amplitudes diminish for
+    /// each successive octave in the noise function.
+    ///
+    /// The amplitude of each successive octave is equal to the product of the
+    /// previous octave's amplitude and the persistence value. Increasing the
+    /// persistence produces "rougher" noise.
+    pub persistence: f64,
+
+    /// The attenuation to apply to the weight on each octave. This reduces
+    /// the strength of each successive octave, making their respective
+    /// ridges smaller. The default attenuation is 2.0, making each octave
+    /// half the height of the previous. Only used by
+    /// `OctaveTransform::Ridge`.
+    pub attenuation: f64,
+
+    /// The value each octave's ridge is measured from. Each octave outputs
+    /// `(offset - |signal|)²`, so the default of 1.0 places the ridges where
+    /// the source crosses zero. Lower offsets move the valleys in from the
+    /// source's extremes to where `|signal|` equals the offset. At 0.5 the
+    /// zero crossings and the extremes peak at the same height of 0.25, with
+    /// valleys along `|signal| = 0.5` between them. Only used by
+    /// `OctaveTransform::Ridge`.
+    pub offset: f64,
+
+    /// Transform applied to each octave before it is accumulated.
+    pub octave_transform: OctaveTransform,
+
+    /// Offset added to the input value of each successive octave.
+    ///
+    /// Octave _n_ is sampled at the input value plus _n_ times this offset.
+    /// When the lacunarity is close to an integer, the lattices of successive
+    /// octaves line up and reinforce each other, producing visible grid
+    /// artifacts. A small offset with irregular components (for example
+    /// `[0.3137, 0.7291, 0.1723, 0.5813]`) breaks up that alignment. The
+    /// default is zero, which leaves the octaves aligned.
+    ///
+    /// Only the first _n_ components are used for _n_-dimensional input.
+    pub octave_offset: [f64; 4],
+
+    /// Offset added to the input value before it is scaled by the frequency.
+    ///
+    /// Two functions with the same seed sample the same sources, so they
+    /// output the same features. Shifting one of them by a large offset lets
+    /// them share a seed but still look unrelated, such as stacked layers
+    /// for mountains and canyons. Only the first _n_ components are used for
+    /// _n_-dimensional input.
+    pub domain_offset: [f64; 4],
+
+    /// How strongly steep slopes suppress the detail of higher octaves.
+    ///
+    /// Each octave after the first is scaled by `1 / (1 + slope_erosion *
+    /// |d|²)`, where `d` is the gradient of the octaves accumulated so far.
+    /// Steep areas, where erosion would wash fine detail away, come out
+    /// smoother, while flat areas keep their detail. The gradients are
+    /// estimated with finite differences, which costs N extra samples per
+    /// octave for N-dimensional input. The default of zero disables erosion.
+    pub slope_erosion: f64,
+
+    /// Weight of the highest octave, between 0 and 1.
+    ///
+    /// A weight below 1 fades the highest octave out, which lets the amount
+    /// of detail change smoothly instead of in whole octaves. The result is
+    /// normalized as if the octave count were fractional. `set_octaves_f`
+    /// sets this from the fractional part of the octave count. The default
+    /// of 1.0 makes every octave count fully.
+    pub partial_octave: f64,
+
+    pub(super) seed: u32,
+    pub(super) spectral_weights: Vec<f64>,
+    pub(super) custom_weights: bool,
+    pub(super) sources: Vec<Perlin>,
+}
+
+/// Per-octave transforms supported by `Fractal`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OctaveTransform {
+    /// Each octave is used as-is, producing fBm.
+    Identity,
+
+    /// The absolute value of each octave is used, producing billowy noise.
+    Abs,
+
+    /// Each octave is folded into a sharp ridge and weighted by the previous
+    /// octave, producing ridged multifractal noise.
+    Ridge,
+}
+
+impl Fractal {
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_OCTAVE_COUNT: usize = 6;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_PERSISTENCE: f64 = 0.5;
+    pub const DEFAULT_ATTENUATION: f64 = 2.0;
+    pub const DEFAULT_OFFSET: f64 = 1.0;
+    pub const DEFAULT_OCTAVE_TRANSFORM: OctaveTransform = OctaveTransform::Identity;
+    pub const DEFAULT_OCTAVE_OFFSET: [f64; 4] = [0.0; 4];
+    pub const DEFAULT_DOMAIN_OFFSET: [f64; 4] = [0.0; 4];
+    pub const DEFAULT_SLOPE_EROSION: f64 = 0.0;
+    pub const DEFAULT_PARTIAL_OCTAVE: f64 = 1.0;
+    pub const MAX_OCTAVES: usize = 32;
+
+    pub fn new() -> Self {
+        Self {
+            seed: Self::DEFAULT_SEED,
+            octaves: Self::DEFAULT_OCTAVE_COUNT,
+            frequency: Self::DEFAULT_FREQUENCY,
+            lacunarity: Self::DEFAULT_LACUNARITY,
+            persistence: Self::DEFAULT_PERSISTENCE,
+            attenuation: Self::DEFAULT_ATTENUATION,
+            offset: Self::DEFAULT_OFFSET,
+            octave_transform: Self::DEFAULT_OCTAVE_TRANSFORM,
+            octave_offset: Self::DEFAULT_OCTAVE_OFFSET,
+            domain_offset: Self::DEFAULT_DOMAIN_OFFSET,
+            slope_erosion: Self::DEFAULT_SLOPE_EROSION,
+            partial_octave: Self::DEFAULT_PARTIAL_OCTAVE,
+            spectral_weights: Vec::new(),
+            custom_weights: false,
+            sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVE_COUNT),
+        }
+        .refresh_spectral_weights()
+    }
+
+    pub fn set_attenuation(self, attenuation: f64) -> Self {
+        Self {
+            attenuation,
+            ..self
+        }
+    }
+
+    pub fn set_offset(self, offset: f64) -> Self {
+        Self { offset, ..self }
+    }
+
+    pub fn set_octave_transform(self, octave_transform: OctaveTransform) -> Self {
+        Self {
+            octave_transform,
+            ..self
+        }
+    }
+
+    /// Sets the offset added to the input value of each successive octave.
+    pub fn set_octave_offset(self, octave_offset: [f64; 4]) -> Self {
+        Self {
+            octave_offset,
+            ..self
+        }
+    }
+
+    /// Sets the offset added to the input value before sampling.
+    pub fn set_domain_offset(self, domain_offset: [f64; 4]) -> Self {
+        Self {
+            domain_offset,
+            ..self
+        }
+    }
+
+    /// Sets how strongly steep slopes suppress the detail of higher octaves.
+    pub fn set_slope_erosion(self, slope_erosion: f64) -> Self {
+        Self {
+            slope_erosion,
+            ..self
+        }
+    }
+
+    /// Sets the number of octaves to the largest count whose highest
+    /// frequency can still be resolved when sampling `pixels_per_unit`
+    /// samples per unit of input.
+    ///
+    /// By the Nyquist limit an octave needs at least two samples per cycle,
+    /// so octaves above `pixels_per_unit / 2` would only add aliasing. The
+    /// count depends on the current frequency and lacunarity, so set those
+    /// first. At least one octave is kept, and at most `MAX_OCTAVES`.
+    pub fn set_octaves_for_resolution(self, pixels_per_unit: f64) -> Self {
+        let limit = pixels_per_unit / 2.0;
+        let mut octaves = 1;
+        let mut frequency = self.frequency * self.lacunarity;
+
+        while octaves < Self::MAX_OCTAVES && frequency <= limit {
+            octaves += 1;
+            frequency *= self.lacunarity;
+        }
+
+        self.set_octaves(octaves)
+    }
+
+    /// Sets a fractional number of octaves.
+    ///
+    /// The noise uses `floor(octaves)` full octaves plus one more octave
+    /// whose amplitude is scaled by the fractional part, so animating the
+    /// count fades detail in and out smoothly. The count is clamped to
+    /// between 1 and `MAX_OCTAVES`.
+    pub fn set_octaves_f(self, octaves: f64) -> Self {
+        let octaves = math::clamp(octaves, 1.0, Self::MAX_OCTAVES as f64);
+        let whole = octaves.floor();
+        let fraction = octaves - whole;
+
+        if fraction > 0.0 {
+            Self {
+                partial_octave: fraction,
+                ..self.set_octaves(whole as usize + 1)
+            }
+        } else {
+            self.set_octaves(whole as usize)
+        }
+    }
+
+    /// Returns a noise function that outputs only the given octave, before it
+    /// is transformed and scaled by its amplitude.
+    ///
+    /// This is useful for rendering the octaves individually. With
+    /// `OctaveTransform::Identity`, multiplying the output of each octave by
+    /// `octave_amplitude` and summing them reproduces the output of this
+    /// fractal, unless slope erosion is enabled.
+    pub fn octave(&self, index: usize) -> FractalOctave<'_> {
+        assert!(index < self.octaves, "octave index out of range");
+
+        FractalOctave {
+            fractal: self,
+            index,
+        }
+    }
+
+    /// Returns the factor the given octave contributes to the output with
+    /// `OctaveTransform::Identity`, including the normalization of the sum
+    /// into the [-1,1] range.
+    pub fn octave_amplitude(&self, index: usize) -> f64 {
+        self.amplitude(index) / self.normalization()
+    }
+
+    /// The weight of octave `x` unless custom weights are set.
+    pub(super) fn default_spectral_weight(&self, x: usize) -> f64 {
+        self.persistence.powi(x as i32)
+    }
+
+    /// Returns the amplitude of octave `x`, fading out the highest octave by
+    /// `partial_octave`.
+    fn amplitude(&self, x: usize) -> f64 {
+        let amplitude = self.spectral_weight(x);
+
+        if x + 1 == self.octaves {
+            amplitude * self.partial_octave
+        } else {
+            amplitude
+        }
+    }
+
+    /// Applies the octave transform to the signal of an octave. `weight`
+    /// carries the weighting of `OctaveTransform::Ridge` from one octave to
+    /// the next, and starts at 1.0.
+    fn shape(&self, signal: f64, weight: &mut f64) -> f64 {
+        match self.octave_transform {
+            OctaveTransform::Identity => signal,
+            // Take the abs of the signal, then scale and shift back to the
+            // [-1,1] range.
+            OctaveTransform::Abs => scale_shift(signal, 2.0),
+            OctaveTransform::Ridge => {
+                // Make the ridges, and square the signal to increase their
+                // sharpness.
+                let mut signal = self.offset - signal.abs();
+                signal *= signal;
+
+                // Apply the weighting from the previous octave to the signal.
+                // Larger values have higher weights, producing sharp points
+                // along the ridges.
+                signal *= *weight;
+
+                // Weight successive contributions by the previous signal,
+                // clamped to [0,1] to prevent the result from diverging.
+                *weight = math::clamp(signal / self.attenuation, 0.0, 1.0);
+
+                signal
+            }
+        }
+    }
+
+    /// Scales and shifts the accumulated octaves into the [-1,1] range.
+    fn normalize(&self, result: f64) -> f64 {
+        match self.octave_transform {
+            OctaveTransform::Identity => result / self.normalization(),
+            OctaveTransform::Abs => result * 0.5,
+            OctaveTransform::Ridge => {
+                let (lower, upper) = self.ridge_bounds();
+
+                scale_shift(result - lower, 2.0 / (upper - lower))
+            }
+        }
+    }
+
+    /// Returns the divisor that scales the sum of the octaves into the [-1,1]
+    /// range for `OctaveTransform::Identity`. See `fbm_normalization`.
+    ///
+    /// It depends on the public fields, so it is computed on every sample
+    /// instead of being cached.
+    pub(super) fn normalization(&self) -> f64 {
+        fbm_normalization(
+            self.persistence,
+            (0..self.octaves).map(|x| self.amplitude(x)),
+        )
+    }
+
+    /// Calculates the smallest and largest values the octave loop can
+    /// accumulate with `OctaveTransform::Ridge`, used to normalize the result
+    /// into the [-1,1] range.
+    ///
+    /// Each octave's signal peaks at the weight carried over from the previous
+    /// octave, so the bounds are found by running the weighting with every
+    /// source outputting its ridge maximum. With the source in [-1,1], the
+    /// ridge peaks at whichever of 0 and 1 is further from the offset. The
+    /// signal is never negative, so octaves with a positive amplitude only
+    /// raise the upper bound and octaves with a negative one only lower the
+    /// lower bound, which stays at 0.0 for the default weighting.
+    ///
+    /// The bounds depend on the public fields, so they are computed on every
+    /// sample instead of being cached.
+    pub(super) fn ridge_bounds(&self) -> (f64, f64) {
+        let peak = self.offset.powi(2).max((self.offset - 1.0).powi(2));
+        let (mut lower, mut upper) = (0.0, 0.0);
+        let mut weight = 1.0;
+
+        for x in 0..self.octaves {
+            let signal = peak * weight;
+
+            weight = math::clamp(signal / self.attenuation, 0.0, 1.0);
+
+            let contribution = signal * self.amplitude(x);
+            if contribution < 0.0 {
+                lower += contribution;
+            } else {
+                upper += contribution;
+            }
+        }
+
+        (lower, upper)
+    }
+
+    /// Sums the octaves at `point`, of which only the first `dimensions` axes
+    /// are used. `sample` evaluates the source of an octave at a point with
+    /// the same axes in use.
+    fn get_octaves<F>(&self, point: [f64; 4], dimensions: usize, sample: F) -> f64
+    where
+        F: Fn(&Perlin, [f64; 4]) -> f64,
+    {
+        if self.slope_erosion > 0.0 {
+            return self.get_eroded(point, dimensions, &sample);
+        }
+
+        let mut point = math::mul4(math::add4(point, self.domain_offset), self.frequency);
+        let mut result = 0.0;
+        let mut weight = 1.0;
+
+        for x in 0..self.octaves {
+            // Get the signal, shifting the octave away from the previous ones.
+            let octave_point = math::add4(point, math::mul4(self.octave_offset, x as f64));
+            let signal = sample(&self.sources[x], octave_point);
+
+            // Shape the signal and scale the amplitude appropriately for this
+            // frequency.
+            result += self.shape(signal, &mut weight) * self.amplitude(x);
+
+            // Increase the frequency for the next octave.
+            point = math::mul4(point, self.lacunarity);
+        }
+
+        self.normalize(result)
+    }
+
+    /// Sums the octaves like `get_octaves`, attenuating each octave by the
+    /// slope of the octaves before it.
+    fn get_eroded(
+        &self,
+        point: [f64; 4],
+        dimensions: usize,
+        sample: &dyn Fn(&Perlin, [f64; 4]) -> f64,
+    ) -> f64 {
+        const DELTA: f64 = 1e-4;
+
+        let mut point = math::mul4(math::add4(point, self.domain_offset), self.frequency);
+        let mut slope = [0.0; 4];
+        let mut scale = 1.0;
+        let mut result = 0.0;
+        let mut weight = 1.0;
+
+        for x in 0..self.octaves {
+            let source = &self.sources[x];
+            let octave_point = math::add4(point, math::mul4(self.octave_offset, x as f64));
+            let amplitude = self.amplitude(x);
+            let previous_weight = weight;
+            let signal = self.shape(sample(source, octave_point), &mut weight);
+
+            let steepness: f64 = slope[..dimensions].iter().map(|d| d * d).sum();
+            let erosion = 1.0 / (1.0 + self.slope_erosion * steepness);
+            result += signal * amplitude * erosion;
+
+            // Accumulate the gradient of this octave with respect to the
+            // input value, which grows with the frequency of the octave.
+            for (axis, slope) in slope[..dimensions].iter_mut().enumerate() {
+                let mut shifted = octave_point;
+                shifted[axis] += DELTA;
+                let shifted_signal = self.shape(sample(source, shifted), &mut { previous_weight });
+                let derivative = (shifted_signal - signal) / DELTA;
+
+                *slope += derivative * amplitude * scale;
+            }
+
+            point = math::mul4(point, self.lacunarity);
+            scale *= self.lacunarity;
+        }
+
+        self.normalize(result)
+    }
+}
+
+impl FractalHooks for Fractal {
+    // A fractional octave count only lasts until the next whole count is set.
+    fn octaves_set(self) -> Self {
+        Self {
+            partial_octave: 1.0,
+            ..self
+        }
+    }
+}
+
+impl Default for Fractal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NoiseFn<[f64; 2]> for Fractal {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.get_octaves([point[0], point[1], 0.0, 0.0], 2, |source, p| {
+            source.get([p[0], p[1]])
+        })
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Fractal")
+    }
+}
+
+impl NoiseFn<[f64; 3]> for Fractal {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_octaves([point[0], point[1], point[2], 0.0], 3, |source, p| {
+            source.get([p[0], p[1], p[2]])
+        })
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Fractal")
+    }
+}
+
+impl NoiseFn<[f64; 4]> for Fractal {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.get_octaves(point, 4, |source, p| source.get(p))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Fractal")
+    }
+}
+
+/// A single octave of a `Fractal`, as returned by `Fractal::octave`.
+#[derive(Clone, Copy, Debug)]
+pub struct FractalOctave<'a> {
+    fractal: &'a Fractal,
+    index: usize,
+}
+
+impl<'a> FractalOctave<'a> {
+    /// Scale of the input value of this octave.
+    pub fn frequency(&self) -> f64 {
+        self.fractal.frequency * self.fractal.lacunarity.powi(self.index as i32)
+    }
+
+    /// Returns the input value of this octave for `point`, of which only the
+    /// first `dimensions` axes are used.
+    fn octave_point(&self, point: [f64; 4]) -> [f64; 4] {
+        let fractal = self.fractal;
+        let point = math::mul4(math::add4(point, fractal.domain_offset), self.frequency());
+
+        math::add4(point, math::mul4(fractal.octave_offset, self.index as f64))
+    }
+
+    fn source(&self) -> &Perlin {
+        &self.fractal.sources[self.index]
+    }
+}
+
+impl<'a> NoiseFn<[f64; 2]> for FractalOctave<'a> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let p = self.octave_point([point[0], point[1], 0.0, 0.0]);
+
+        self.source().get([p[0], p[1]])
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("FractalOctave")
+    }
+}
+
+impl<'a> NoiseFn<[f64; 3]> for FractalOctave<'a> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let p = self.octave_point([point[0], point[1], point[2], 0.0]);
+
+        self.source().get([p[0], p[1], p[2]])
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("FractalOctave")
+    }
+}
+
+impl<'a> NoiseFn<[f64; 4]> for FractalOctave<'a> {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.source().get(self.octave_point(point))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("FractalOctave")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Billow, Fbm, RidgedMulti, Seedable};
+
+    const POINTS: [[f64; 3]; 3] = [[0.3, 0.7, 1.1], [12.5, -3.25, 0.4], [-100.1, 4.9, 7.7]];
+
+    #[test]
+    fn wrappers_preset_the_transform() {
+        let fractal = Fractal::new().set_seed(5).set_octaves(4);
+        let fbm = Fbm::new().set_seed(5).set_octaves(4);
+        let billow = Billow::new().set_seed(5).set_octaves(4);
+        let ridged = RidgedMulti::new().set_seed(5).set_octaves(4);
+
+        assert_eq!(fbm.octave_transform, OctaveTransform::Identity);
+        assert_eq!(billow.octave_transform, OctaveTransform::Abs);
+        assert_eq!(ridged.octave_transform, OctaveTransform::Ridge);
+
+        let abs = fractal.clone().set_octave_transform(OctaveTransform::Abs);
+        let ridge = fractal
+            .clone()
+            .set_octave_transform(OctaveTransform::Ridge)
+            .set_persistence(RidgedMulti::DEFAULT_PERSISTENCE);
+
+        for &point in &POINTS {
+            let point2 = [point[0], point[1]];
+            let point4 = [point[0], point[1], point[2], 0.5];
+
+            assert_eq!(fractal.get(point), fbm.get(point));
+            assert_eq!(fractal.get(point2), fbm.get(point2));
+            assert_eq!(abs.get(point), billow.get(point));
+            assert_eq!(abs.get(point4), billow.get(point4));
+            assert_eq!(ridge.get(point), ridged.get(point));
+            assert_eq!(ridge.get(point4), ridged.get(point4));
+        }
+    }
+
+    #[test]
+    fn fbm_features_apply_to_every_transform() {
+        for &transform in &[OctaveTransform::Abs, OctaveTransform::Ridge] {
+            let plain = Fractal::new().set_octave_transform(transform);
+            let featured = plain
+                .clone()
+                .set_octave_offset([0.3137, 0.7291, 0.1723, 0.5813])
+                .set_slope_erosion(1.5)
+                .set_octaves_f(4.5);
+
+            let mut difference = 0.0;
+            for i in 0..50 {
+                let point = [i as f64 * 0.173 - 3.0, i as f64 * 0.091, 0.4];
+                let value = featured.get(point);
+
+                assert!((-1.0..=1.0).contains(&value), "{:?}: {}", transform, value);
+                difference += (value - plain.get(point)).abs();
+            }
+            assert!(difference > 1.0);
+        }
+    }
+
+    #[test]
+    fn struct_literals_are_normalized() {
+        let literal = Fractal {
+            persistence: 0.8,
+            octave_transform: OctaveTransform::Ridge,
+            ..Fractal::new()
+        };
+        let set = Fractal::new()
+            .set_octave_transform(OctaveTransform::Ridge)
+            .set_persistence(0.8);
+
+        for &point in &POINTS {
+            assert_eq!(literal.get(point), set.get(point));
+        }
+    }
+}
@@ -1,6 +1,8 @@
 use crate::math;
 
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn, Perlin};
 
 /// Noise function that outputs hybrid Multifractal noise.
 ///
@@ -36,15 +38,17 @@ pub struct HybridMulti {
     /// persistence produces "rougher" noise.
     pub persistence: f64,
 
-    seed: u32,
-    sources: Vec<Perlin>,
+    pub(super) seed: u32,
+    pub(super) spectral_weights: Vec<f64>,
+    pub(super) custom_weights: bool,
+    pub(super) sources: Vec<Perlin>,
 }
 
 impl HybridMulti {
     pub const DEFAULT_SEED: u32 = 0;
     pub const DEFAULT_OCTAVES: usize = 6;
     pub const DEFAULT_FREQUENCY: f64 = 2.0;
-    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_LACUNARITY: f64 = core::f64::consts::PI * 2.0 / 3.0;
     pub const DEFAULT_PERSISTENCE: f64 = 0.25;
     pub const MAX_OCTAVES: usize = 32;
 
@@ -55,8 +59,17 @@ impl HybridMulti {
             frequency: Self::DEFAULT_FREQUENCY,
             lacunarity: Self::DEFAULT_LACUNARITY,
             persistence: Self::DEFAULT_PERSISTENCE,
+            spectral_weights: Vec::new(),
+            custom_weights: false,
             sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVES),
         }
+        .refresh_spectral_weights()
+    }
+
+    /// The weight of octave `x` unless custom weights are set. The first
+    /// octave is scaled by the persistence as well.
+    pub(super) fn default_spectral_weight(&self, x: usize) -> f64 {
+        self.persistence.powi(x.max(1) as i32)
     }
 }
 
@@ -66,60 +79,12 @@ impl Default for HybridMulti {
     }
 }
 
-impl MultiFractal for HybridMulti {
-    fn set_octaves(self, mut octaves: usize) -> Self {
-        if self.octaves == octaves {
-            return self;
-        }
-
-        octaves = math::clamp(octaves, 1, Self::MAX_OCTAVES);
-        Self {
-            octaves,
-            sources: super::build_sources(self.seed, octaves),
-            ..self
-        }
-    }
-
-    fn set_frequency(self, frequency: f64) -> Self {
-        Self { frequency, ..self }
-    }
-
-    fn set_lacunarity(self, lacunarity: f64) -> Self {
-        Self { lacunarity, ..self }
-    }
-
-    fn set_persistence(self, persistence: f64) -> Self {
-        Self {
-            persistence,
-            ..self
-        }
-    }
-}
-
-impl Seedable for HybridMulti {
-    fn set_seed(self, seed: u32) -> Self {
-        if self.seed == seed {
-            return self;
-        }
-
-        Self {
-            seed,
-            sources: super::build_sources(seed, self.octaves),
-            ..self
-        }
-    }
-
-    fn seed(&self) -> u32 {
-        self.seed
-    }
-}
-
 /// 2-dimensional `HybridMulti` noise
 impl NoiseFn<[f64; 2]> for HybridMulti {
     fn get(&self, mut point: [f64; 2]) -> f64 {
         // First unscaled octave of function; later octaves are scaled.
         point = math::mul2(point, self.frequency);
-        let mut result = self.sources[0].get(point) * self.persistence;
+        let mut result = self.sources[0].get(point) * self.spectral_weight(0);
         let mut weight = result;
 
         // Spectral construction inner loop, where the fractal is built.
@@ -134,7 +99,7 @@ impl NoiseFn<[f64; 2]> for HybridMulti {
             let mut signal = self.sources[x].get(point);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self.spectral_weight(x);
 
             // Add it in, weighted by previous octave's noise value.
             result += weight * signal;
@@ -146,6 +111,10 @@ impl NoiseFn<[f64; 2]> for HybridMulti {
         // Scale the result to the [-1,1] range
         result * 3.0
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("HybridMulti")
+    }
 }
 
 /// 3-dimensional `HybridMulti` noise
@@ -153,7 +122,7 @@ impl NoiseFn<[f64; 3]> for HybridMulti {
     fn get(&self, mut point: [f64; 3]) -> f64 {
         // First unscaled octave of function; later octaves are scaled.
         point = math::mul3(point, self.frequency);
-        let mut result = self.sources[0].get(point) * self.persistence;
+        let mut result = self.sources[0].get(point) * self.spectral_weight(0);
         let mut weight = result;
 
         // Spectral construction inner loop, where the fractal is built.
@@ -168,7 +137,7 @@ impl NoiseFn<[f64; 3]> for HybridMulti {
             let mut signal = self.sources[x].get(point);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self.spectral_weight(x);
 
             // Add it in, weighted by previous octave's noise value.
             result += weight * signal;
@@ -180,6 +149,10 @@ impl NoiseFn<[f64; 3]> for HybridMulti {
         // Scale the result to the [-1,1] range
         result * 3.0
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("HybridMulti")
+    }
 }
 
 /// 4-dimensional `HybridMulti` noise
@@ -187,7 +160,7 @@ impl NoiseFn<[f64; 4]> for HybridMulti {
     fn get(&self, mut point: [f64; 4]) -> f64 {
         // First unscaled octave of function; later octaves are scaled.
         point = math::mul4(point, self.frequency);
-        let mut result = self.sources[0].get(point) * self.persistence;
+        let mut result = self.sources[0].get(point) * self.spectral_weight(0);
         let mut weight = result;
 
         // Spectral construction inner loop, where the fractal is built.
@@ -202,7 +175,7 @@ impl NoiseFn<[f64; 4]> for HybridMulti {
             let mut signal = self.sources[x].get(point);
 
             // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
+            signal *= self.spectral_weight(x);
 
             // Add it in, weighted by previous octave's noise value.
             result += weight * signal;
@@ -214,4 +187,8 @@ impl NoiseFn<[f64; 4]> for HybridMulti {
         // Scale the result to the [-1,1] range
         result * 3.0
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("HybridMulti")
+    }
 }
@@ -1,5 +1,5 @@
-use crate::math::{self, scale_shift};
-use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
+use super::{Fractal, OctaveTransform};
+use crate::noise_fns::MultiFractal;
 
 /// Noise function that outputs ridged-multifractal noise.
 ///
@@ -9,271 +9,189 @@ use crate::noise_fns::{MultiFractal, NoiseFn, Perlin, Seedable};
 /// an absolute-value function. Modifying the octave values in this way
 /// produces ridge-like formations.
 ///
-/// The values output from this function will usually range from -1.0 to 1.0 with
-/// default values for the parameters, but there are no guarantees that all
-/// output values will exist within this range. If the parameters are modified
-/// from their defaults, then the output will need to be scaled to remain in
-/// the [-1,1] range.
+/// The output is normalized into the [-1,1] range from the smallest and
+/// largest values the octaves can accumulate with the current parameters.
 ///
 /// Ridged-multifractal noise is often used to generate craggy mountainous
 /// terrain or marble-like textures.
+///
+/// This is a `Fractal` with `OctaveTransform::Ridge` and a default
+/// persistence of 1.0, and dereferences to it for its fields and accessors.
 #[derive(Clone, Debug)]
 pub struct RidgedMulti {
-    /// Total number of frequency octaves to generate the noise with.
-    ///
-    /// The number of octaves control the _amount of detail_ in the noise
-    /// function. Adding more octaves increases the detail, with the drawback
-    /// of increasing the calculation time.
-    pub octaves: usize,
-
-    /// The number of cycles per unit length that the noise function outputs.
-    pub frequency: f64,
-
-    /// A multiplier that determines how quickly the frequency increases for
-    /// each successive octave in the noise function.
-    ///
-    /// The frequency of each successive octave is equal to the product of the
-    /// previous octave's frequency and the lacunarity value.
-    ///
-    /// A lacunarity of 2.0 results in the frequency doubling every octave. For
-    /// almost all cases, 2.0 is a good value to use.
-    pub lacunarity: f64,
-
-    /// A multiplier that determines how quickly the amplitudes diminish for
-    /// each successive octave in the noise function.
-    ///
-    /// The amplitude of each successive octave is equal to the product of the
-    /// previous octave's amplitude and the persistence value. Increasing the
-    /// persistence produces "rougher" noise.
-    pub persistence: f64,
-
-    /// The attenuation to apply to the weight on each octave. This reduces
-    /// the strength of each successive octave, making their respective
-    /// ridges smaller. The default attenuation is 2.0, making each octave
-    /// half the height of the previous.
-    pub attenuation: f64,
-
-    seed: u32,
-    sources: Vec<Perlin>,
+    pub(super) fractal: Fractal,
 }
 
 impl RidgedMulti {
-    pub const DEFAULT_SEED: u32 = 0;
-    pub const DEFAULT_OCTAVE_COUNT: usize = 6;
-    pub const DEFAULT_FREQUENCY: f64 = 1.0;
-    pub const DEFAULT_LACUNARITY: f64 = std::f64::consts::PI * 2.0 / 3.0;
+    pub const DEFAULT_SEED: u32 = Fractal::DEFAULT_SEED;
+    pub const DEFAULT_OCTAVE_COUNT: usize = Fractal::DEFAULT_OCTAVE_COUNT;
+    pub const DEFAULT_FREQUENCY: f64 = Fractal::DEFAULT_FREQUENCY;
+    pub const DEFAULT_LACUNARITY: f64 = Fractal::DEFAULT_LACUNARITY;
     pub const DEFAULT_PERSISTENCE: f64 = 1.0;
-    pub const DEFAULT_ATTENUATION: f64 = 2.0;
-    pub const MAX_OCTAVES: usize = 32;
+    pub const DEFAULT_ATTENUATION: f64 = Fractal::DEFAULT_ATTENUATION;
+    pub const DEFAULT_OFFSET: f64 = Fractal::DEFAULT_OFFSET;
+    pub const DEFAULT_DOMAIN_OFFSET: [f64; 4] = Fractal::DEFAULT_DOMAIN_OFFSET;
+    pub const MAX_OCTAVES: usize = Fractal::MAX_OCTAVES;
 
     pub fn new() -> Self {
         Self {
-            seed: Self::DEFAULT_SEED,
-            octaves: Self::DEFAULT_OCTAVE_COUNT,
-            frequency: Self::DEFAULT_FREQUENCY,
-            lacunarity: Self::DEFAULT_LACUNARITY,
-            persistence: Self::DEFAULT_PERSISTENCE,
-            attenuation: Self::DEFAULT_ATTENUATION,
-            sources: super::build_sources(Self::DEFAULT_SEED, Self::DEFAULT_OCTAVE_COUNT),
+            fractal: Fractal::new()
+                .set_octave_transform(OctaveTransform::Ridge)
+                .set_persistence(Self::DEFAULT_PERSISTENCE),
         }
     }
 
     pub fn set_attenuation(self, attenuation: f64) -> Self {
-        Self {
-            attenuation,
-            ..self
-        }
-    }
-}
-
-impl Default for RidgedMulti {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl MultiFractal for RidgedMulti {
-    fn set_octaves(self, mut octaves: usize) -> Self {
-        if self.octaves == octaves {
-            return self;
-        }
-
-        octaves = math::clamp(octaves, 1, Self::MAX_OCTAVES);
-        Self {
-            octaves,
-            sources: super::build_sources(self.seed, octaves),
-            ..self
-        }
+        self.map_fractal(|fractal| fractal.set_attenuation(attenuation))
     }
 
-    fn set_frequency(self, frequency: f64) -> Self {
-        Self { frequency, ..self }
+    pub fn set_offset(self, offset: f64) -> Self {
+        self.map_fractal(|fractal| fractal.set_offset(offset))
     }
 
-    fn set_lacunarity(self, lacunarity: f64) -> Self {
-        Self { lacunarity, ..self }
+    /// Sets the offset added to the input value before sampling.
+    pub fn set_domain_offset(self, domain_offset: [f64; 4]) -> Self {
+        self.map_fractal(|fractal| fractal.set_domain_offset(domain_offset))
     }
+}
 
-    fn set_persistence(self, persistence: f64) -> Self {
-        Self {
-            persistence,
-            ..self
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use crate::no_std::*;
+    use crate::noise_fns::NoiseFn;
+
+    #[test]
+    fn default_normalization_is_unchanged() {
+        for octaves in 1..=8 {
+            let ridged = RidgedMulti::new().set_octaves(octaves);
+            let expected = 2.0 - 0.5_f64.powi(octaves as i32 - 1);
+
+            assert_eq!(ridged.ridge_bounds().0, 0.0);
+            assert!((ridged.ridge_bounds().1 - expected).abs() < 1e-12);
         }
     }
-}
 
-impl Seedable for RidgedMulti {
-    fn set_seed(self, seed: u32) -> Self {
-        if self.seed == seed {
-            return self;
+    #[test]
+    fn offset_moves_ridges() {
+        let ridged = RidgedMulti::new().set_octaves(1).set_offset(0.5);
+        let source = &ridged.sources[0];
+
+        let mut shifted = 0.0;
+        let mut default = 0.0;
+        for i in 0..400 {
+            let point = [i as f64 * 0.173 - 3.0, i as f64 * 0.071];
+            let signal = source.get(point).abs();
+
+            // A single octave is (offset - |signal|)² scaled onto [-1,1], so
+            // the output bottoms out where the source is at +-0.5 instead of
+            // +-1.
+            let expected = (0.5 - signal).powi(2) / 0.25 * 2.0 - 1.0;
+            assert!((ridged.get(point) - expected).abs() < 1e-12);
+
+            shifted += ridged.get(point);
+            default += ridged.clone().set_offset(1.0).get(point);
         }
 
-        Self {
-            seed,
-            sources: super::build_sources(seed, self.octaves),
-            ..self
-        }
+        assert!((shifted - default).abs() > 1.0);
+        assert_eq!(
+            RidgedMulti::new().set_offset(1.0).get([0.3, 0.7]),
+            RidgedMulti::new().get([0.3, 0.7])
+        );
     }
 
-    fn seed(&self) -> u32 {
-        self.seed
+    #[test]
+    fn domain_offset_shifts_input() {
+        let mountains = RidgedMulti::new().set_domain_offset([0.0; 4]);
+        let canyons = RidgedMulti::new().set_domain_offset([137.5, -59.25, 11.0, 3.0]);
+
+        let mut difference = 0.0;
+        for i in 0..50 {
+            let point = [i as f64 * 0.173 - 3.0, i as f64 * 0.071, 0.4];
+
+            assert_eq!(mountains.get(point), RidgedMulti::new().get(point));
+            assert_eq!(
+                canyons.get(point),
+                mountains.get([point[0] + 137.5, point[1] - 59.25, point[2] + 11.0])
+            );
+            difference += (canyons.get(point) - mountains.get(point)).abs();
+        }
+        assert!(difference > 1.0);
     }
-}
-
-/// 2-dimensional `RidgedMulti` noise
-impl NoiseFn<[f64; 2]> for RidgedMulti {
-    fn get(&self, mut point: [f64; 2]) -> f64 {
-        let mut result = 0.0;
-        let mut weight = 1.0;
-
-        point = math::mul2(point, self.frequency);
-
-        for x in 0..self.octaves {
-            // Get the value.
-            let mut signal = self.sources[x].get(point);
-
-            // Make the ridges.
-            signal = signal.abs();
-            signal = 1.0 - signal;
-
-            // Square the signal to increase the sharpness of the ridges.
-            signal *= signal;
 
-            // Apply the weighting from the previous octave to the signal.
-            // Larger values have higher weights, producing sharp points along
-            // the ridges.
-            signal *= weight;
-
-            // Weight successive contributions by the previous signal.
-            weight = signal / self.attenuation;
-
-            // Clamp the weight to [0,1] to prevent the result from diverging.
-            weight = math::clamp(weight, 0.0, 1.0);
-
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
-
-            // Add the signal to the result.
-            result += signal;
-
-            // Increase the frequency.
-            point = math::mul2(point, self.lacunarity);
+    #[test]
+    fn output_stays_in_range() {
+        let mut attenuation = 1.0;
+
+        while attenuation <= 4.0 {
+            for octaves in 1..=8 {
+                let ridged = RidgedMulti::new()
+                    .set_octaves(octaves)
+                    .set_attenuation(attenuation);
+
+                for x in 0..20 {
+                    for y in 0..20 {
+                        let point = [x as f64 * 0.173, y as f64 * 0.291];
+                        let value = ridged.get(point);
+
+                        assert!(
+                            (-1.05..=1.05).contains(&value),
+                            "value {} out of range for attenuation {} and {} octaves",
+                            value,
+                            attenuation,
+                            octaves
+                        );
+
+                        let value = ridged.get([point[0], point[1], 0.5, 0.25]);
+                        assert!((-1.05..=1.05).contains(&value));
+                    }
+                }
+            }
+
+            attenuation += 0.5;
         }
-
-        // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - 0.5_f64.powi(self.octaves as i32 - 1);
-        scale_shift(result, 2.0 / scale)
     }
-}
-
-/// 3-dimensional `RidgedMulti` noise
-impl NoiseFn<[f64; 3]> for RidgedMulti {
-    fn get(&self, mut point: [f64; 3]) -> f64 {
-        let mut result = 0.0;
-        let mut weight = 1.0;
-
-        point = math::mul3(point, self.frequency);
-
-        for x in 0..self.octaves {
-            // Get the value.
-            let mut signal = self.sources[x].get(point);
-
-            // Make the ridges.
-            signal = signal.abs();
-            signal = 1.0 - signal;
-
-            // Square the signal to increase the sharpness of the ridges.
-            signal *= signal;
-
-            // Apply the weighting from the previous octave to the signal.
-            // Larger values have higher weights, producing sharp points along
-            // the ridges.
-            signal *= weight;
-
-            // Weight successive contributions by the previous signal.
-            weight = signal / self.attenuation;
-
-            // Clamp the weight to [0,1] to prevent the result from diverging.
-            weight = math::clamp(weight, 0.0, 1.0);
 
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
-
-            // Add the signal to the result.
-            result += signal;
-
-            // Increase the frequency.
-            point = math::mul3(point, self.lacunarity);
+    #[test]
+    fn field_writes_are_normalized() {
+        let mut written = RidgedMulti::new();
+        written.attenuation = 3.0;
+        written.offset = 0.5;
+        let mut rough = RidgedMulti::new();
+        rough.persistence = 0.5;
+
+        for i in 0..20 {
+            let point = [i as f64 * 0.173 - 3.0, i as f64 * 0.091];
+            let set = RidgedMulti::new().set_attenuation(3.0).set_offset(0.5);
+
+            assert_eq!(written.get(point), set.get(point));
+            assert_eq!(
+                rough.get(point),
+                RidgedMulti::new().set_persistence(0.5).get(point)
+            );
         }
-
-        // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - 0.5_f64.powi(self.octaves as i32 - 1);
-        scale_shift(result, 2.0 / scale)
     }
-}
-
-/// 4-dimensional `RidgedMulti` noise
-impl NoiseFn<[f64; 4]> for RidgedMulti {
-    fn get(&self, mut point: [f64; 4]) -> f64 {
-        let mut result = 0.0;
-        let mut weight = 1.0;
-
-        point = math::mul4(point, self.frequency);
-
-        for x in 0..self.octaves {
-            // Get the value.
-            let mut signal = self.sources[x].get(point);
-
-            // Make the ridges.
-            signal = signal.abs();
-            signal = 1.0 - signal;
 
-            // Square the signal to increase the sharpness of the ridges.
-            signal *= signal;
-
-            // Apply the weighting from the previous octave to the signal.
-            // Larger values have higher weights, producing sharp points along
-            // the ridges.
-            signal *= weight;
-
-            // Weight successive contributions by the previous signal.
-            weight = signal * self.attenuation;
-
-            // Clamp the weight to [0,1] to prevent the result from diverging.
-            weight = math::clamp(weight, 0.0, 1.0);
-
-            // Scale the amplitude appropriately for this frequency.
-            signal *= self.persistence.powi(x as i32);
-
-            // Add the signal to the result.
-            result += signal;
-
-            // Increase the frequency.
-            point = math::mul4(point, self.lacunarity);
+    #[test]
+    fn negative_weights_stay_in_range() {
+        let ridged = RidgedMulti::new()
+            .set_spectral_weights(vec![1.0, -0.8, 0.6, -0.4, 0.2, -0.1])
+            .set_attenuation(3.0);
+
+        assert!(ridged.ridge_bounds().0 < 0.0);
+
+        for x in 0..20 {
+            for y in 0..20 {
+                let point = [x as f64 * 0.173, y as f64 * 0.291, 0.5];
+                let value = ridged.get(point);
+
+                assert!(
+                    (-1.05..=1.05).contains(&value),
+                    "value {} out of range",
+                    value
+                );
+            }
         }
-
-        // Scale and shift the result into the [-1,1] range
-        let scale = 2.0 - 0.5_f64.powi(self.octaves as i32 - 1);
-        scale_shift(result, 2.0 / scale)
     }
 }
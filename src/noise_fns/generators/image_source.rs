@@ -0,0 +1,178 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math::{self, interpolate},
+    noise_fns::{NodeDesc, NoiseFn},
+};
+#[cfg(feature = "image")]
+use std::path::Path;
+
+/// Noise function that outputs values sampled from a supplied heightmap.
+///
+/// The heightmap is a grid of `width` × `height` values stored in row-major
+/// order. Input coordinates are measured in pixels, with the center of the
+/// pixel at `(x, y)` lying at the integer coordinate `[x, y]`. Between pixel
+/// centers the output value is bilinearly interpolated.
+///
+/// This noise function is useful for mixing authored heightmaps with
+/// procedural noise functions in the same noise graph.
+#[derive(Clone, Debug)]
+pub struct ImageSource {
+    /// Determines how coordinates outside of the heightmap are handled.
+    pub edge_mode: EdgeMode,
+
+    size: (usize, usize),
+    values: Vec<f64>,
+}
+
+/// Behavior applied to coordinates that fall outside of a finite grid.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EdgeMode {
+    /// Coordinates are clamped to the nearest edge of the grid.
+    Clamp,
+
+    /// Coordinates wrap around to the opposite edge of the grid, tiling the
+    /// grid infinitely.
+    Wrap,
+
+    /// Coordinates are reflected back into the grid at each edge, so the grid
+    /// repeats with every other copy mirrored and no seams appear.
+    Mirror,
+}
+
+impl ImageSource {
+    pub const DEFAULT_EDGE_MODE: EdgeMode = EdgeMode::Clamp;
+
+    /// Creates a new heightmap from a row-major vector of values.
+    ///
+    /// The values are used as-is, so they should already lie in the [-1,1]
+    /// range.
+    pub fn new(width: usize, height: usize, values: Vec<f64>) -> Self {
+        assert!(width > 0 && height > 0);
+        assert_eq!(values.len(), width * height);
+
+        Self {
+            edge_mode: Self::DEFAULT_EDGE_MODE,
+            size: (width, height),
+            values,
+        }
+    }
+
+    /// Creates a new heightmap from a row-major vector of 8-bit grayscale
+    /// pixels, mapping the range [0,255] onto [-1,1].
+    pub fn from_luma(width: usize, height: usize, pixels: &[u8]) -> Self {
+        let values = pixels
+            .iter()
+            .map(|&p| f64::from(p) / 255.0 * 2.0 - 1.0)
+            .collect();
+
+        Self::new(width, height, values)
+    }
+
+    /// Loads a heightmap from an image file, converting it to grayscale.
+    #[cfg(feature = "image")]
+    pub fn open<P: AsRef<Path>>(path: P) -> image::ImageResult<Self> {
+        let image = image::open(path)?.to_luma8();
+        let (width, height) = image.dimensions();
+
+        Ok(Self::from_luma(
+            width as usize,
+            height as usize,
+            image.as_raw(),
+        ))
+    }
+
+    pub fn set_edge_mode(self, edge_mode: EdgeMode) -> Self {
+        Self { edge_mode, ..self }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    fn pixel(&self, x: isize, y: isize) -> f64 {
+        let (width, height) = self.size;
+        let x = resolve_index(x, width, self.edge_mode);
+        let y = resolve_index(y, height, self.edge_mode);
+
+        self.values[x + y * width]
+    }
+}
+
+/// Maps `index` onto a valid index of a grid dimension `len` cells long.
+pub(crate) fn resolve_index(index: isize, len: usize, edge_mode: EdgeMode) -> usize {
+    match edge_mode {
+        EdgeMode::Clamp => math::clamp(index, 0, len as isize - 1) as usize,
+        EdgeMode::Wrap => {
+            let len = len as isize;
+            (((index % len) + len) % len) as usize
+        }
+        EdgeMode::Mirror => {
+            let period = 2 * len as isize;
+            let index = (((index % period) + period) % period) as usize;
+
+            if index < len {
+                index
+            } else {
+                2 * len - 1 - index
+            }
+        }
+    }
+}
+
+impl NoiseFn<[f64; 2]> for ImageSource {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let floored = math::map2(point, f64::floor);
+        let near = math::to_isize2(floored);
+        let frac = math::sub2(point, floored);
+
+        let v00 = self.pixel(near[0], near[1]);
+        let v10 = self.pixel(near[0] + 1, near[1]);
+        let v01 = self.pixel(near[0], near[1] + 1);
+        let v11 = self.pixel(near[0] + 1, near[1] + 1);
+
+        let v0 = interpolate::linear(v00, v10, frac[0]);
+        let v1 = interpolate::linear(v01, v11, frac[0]);
+
+        interpolate::linear(v0, v1, frac[1])
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("ImageSource")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bilinear_center_is_average() {
+        let source = ImageSource::new(2, 2, vec![-1.0, 0.0, 0.5, 1.0]);
+
+        assert!((source.get([0.5, 0.5]) - 0.125).abs() < 1e-12);
+    }
+
+    #[test]
+    fn pixel_centers_are_exact() {
+        let source = ImageSource::from_luma(2, 1, &[0, 255]);
+
+        assert_eq!(source.get([0.0, 0.0]), -1.0);
+        assert_eq!(source.get([1.0, 0.0]), 1.0);
+    }
+
+    #[test]
+    fn edge_modes() {
+        let source = ImageSource::new(2, 1, vec![-1.0, 1.0]);
+        assert_eq!(source.get([5.0, 0.0]), 1.0);
+
+        let source = source.set_edge_mode(EdgeMode::Wrap);
+        assert_eq!(source.get([2.0, 0.0]), -1.0);
+        assert_eq!(source.get([-1.0, 0.0]), 1.0);
+
+        let source = source.set_edge_mode(EdgeMode::Mirror);
+        assert_eq!(source.get([2.0, 0.0]), 1.0);
+        assert_eq!(source.get([-1.0, 0.0]), -1.0);
+        assert_eq!(source.get([4.0, 0.0]), -1.0);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::noise_fns::{NodeDesc, NoiseFn};
+
+/// Noise function that outputs a linear function of the input point,
+/// `a * x + b * y + c * z + d`.
+///
+/// The output is not limited to the [-1,1] range. This function is mostly
+/// useful for testing: it provides predictable, non-constant input for
+/// combiners, selectors and modifiers without depending on the exact output
+/// of a noise generator.
+#[derive(Clone, Copy, Debug)]
+pub struct Linear {
+    /// Coefficient of the x coordinate.
+    pub a: f64,
+
+    /// Coefficient of the y coordinate.
+    pub b: f64,
+
+    /// Coefficient of the z coordinate.
+    pub c: f64,
+
+    /// Constant offset.
+    pub d: f64,
+}
+
+impl Linear {
+    pub fn new(a: f64, b: f64, c: f64, d: f64) -> Self {
+        Self { a, b, c, d }
+    }
+}
+
+/// 2-dimensional points lie in the z = 0 plane, so `c` has no effect.
+impl NoiseFn<[f64; 2]> for Linear {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.a * point[0] + self.b * point[1] + self.d
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Linear")
+    }
+}
+
+impl NoiseFn<[f64; 3]> for Linear {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.a * point[0] + self.b * point[1] + self.c * point[2] + self.d
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Linear")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reproduces_linear_function() {
+        let linear = Linear::new(2.0, -0.5, 0.25, 1.0);
+
+        for i in 0..20 {
+            let (x, y, z) = (i as f64 * 0.37 - 3.0, i as f64 * -1.5, i as f64);
+
+            assert_eq!(linear.get([x, y]), 2.0 * x - 0.5 * y + 1.0);
+            assert_eq!(linear.get([x, y, z]), 2.0 * x - 0.5 * y + 0.25 * z + 1.0);
+        }
+
+        assert_eq!(Linear::new(1.0, 0.0, 0.0, 0.0).get([0.75, 9.0, 9.0]), 0.75);
+    }
+}
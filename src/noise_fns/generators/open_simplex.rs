@@ -2,12 +2,14 @@
 //! Instead, these functions use the `OpenSimplex` algorithm, as detailed here:
 //! <http://uniblock.tumblr.com/post/97868843242/noise>
 
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
 use crate::{
     gradient, math,
-    noise_fns::{NoiseFn, Seedable},
+    noise_fns::{NodeDesc, NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
-use std::ops::Add;
+use core::ops::Add;
 
 const STRETCH_CONSTANT_2D: f64 = -0.211_324_865_405_187; //(1/sqrt(2+1)-1)/2;
 const SQUISH_CONSTANT_2D: f64 = 0.366_025_403_784_439; //(sqrt(2+1)-1)/2;
@@ -16,26 +18,145 @@ const SQUISH_CONSTANT_3D: f64 = 1.0 / 3.0; //(Math.sqrt(3+1)-1)/3;
 const STRETCH_CONSTANT_4D: f64 = -0.138_196_601_125_011; //(Math.sqrt(4+1)-1)/4;
 const SQUISH_CONSTANT_4D: f64 = 0.309_016_994_374_947; //(Math.sqrt(4+1)-1)/4;
 
-const NORM_CONSTANT_2D: f64 = 1.0 / 14.0;
-const NORM_CONSTANT_3D: f64 = 1.0 / 14.0;
-const NORM_CONSTANT_4D: f64 = 1.0 / 6.869_909_007_095_662_5;
+// Squared radius of the contribution of each vertex, (N + 1) / N. Each point
+// only evaluates the vertices of the region it lies in, so the contributions
+// must fall to zero before reaching any point outside of the regions sharing
+// the vertex. Larger radii make the noise discontinuous along region borders.
+const RADIUS_SQUARED_2D: f64 = 3.0 / 2.0;
+const RADIUS_SQUARED_3D: f64 = 4.0 / 3.0;
+const RADIUS_SQUARED_4D: f64 = 5.0 / 4.0;
+
+const NORM_CONSTANT_2D: f64 = 1.0 / 1.5;
+const NORM_CONSTANT_3D: f64 = 1.0 / 0.8;
+const NORM_CONSTANT_4D: f64 = 1.0 / 0.6;
+
+// Lattice points contributing to the 3D noise in each region of the
+// rhombohedral super-cell, as pairs of the offset from the cell origin and the
+// offset squished back into input space, `offset + sum(offset) * SQUISH`.
+const LATTICE_3D: [&[([f64; 3], [f64; 3])]; 3] = [
+    // Tetrahedron at (0, 0, 0).
+    &[
+        LATTICE_3D_000,
+        LATTICE_3D_100,
+        LATTICE_3D_010,
+        LATTICE_3D_001,
+    ],
+    // Octahedron inbetween.
+    &[
+        LATTICE_3D_100,
+        LATTICE_3D_010,
+        LATTICE_3D_001,
+        LATTICE_3D_110,
+        LATTICE_3D_101,
+        LATTICE_3D_011,
+    ],
+    // Tetrahedron at (1, 1, 1).
+    &[
+        LATTICE_3D_110,
+        LATTICE_3D_101,
+        LATTICE_3D_011,
+        LATTICE_3D_111,
+    ],
+];
+
+const S1_3D: f64 = SQUISH_CONSTANT_3D;
+const S2_3D: f64 = 2.0 * SQUISH_CONSTANT_3D;
+const S3_3D: f64 = 3.0 * SQUISH_CONSTANT_3D;
+
+const LATTICE_3D_000: ([f64; 3], [f64; 3]) = ([0.0, 0.0, 0.0], [0.0, 0.0, 0.0]);
+const LATTICE_3D_100: ([f64; 3], [f64; 3]) = ([1.0, 0.0, 0.0], [1.0 + S1_3D, S1_3D, S1_3D]);
+const LATTICE_3D_010: ([f64; 3], [f64; 3]) = ([0.0, 1.0, 0.0], [S1_3D, 1.0 + S1_3D, S1_3D]);
+const LATTICE_3D_001: ([f64; 3], [f64; 3]) = ([0.0, 0.0, 1.0], [S1_3D, S1_3D, 1.0 + S1_3D]);
+const LATTICE_3D_110: ([f64; 3], [f64; 3]) = ([1.0, 1.0, 0.0], [1.0 + S2_3D, 1.0 + S2_3D, S2_3D]);
+const LATTICE_3D_101: ([f64; 3], [f64; 3]) = ([1.0, 0.0, 1.0], [1.0 + S2_3D, S2_3D, 1.0 + S2_3D]);
+const LATTICE_3D_011: ([f64; 3], [f64; 3]) = ([0.0, 1.0, 1.0], [S2_3D, 1.0 + S2_3D, 1.0 + S2_3D]);
+const LATTICE_3D_111: ([f64; 3], [f64; 3]) =
+    ([1.0, 1.0, 1.0], [1.0 + S3_3D, 1.0 + S3_3D, 1.0 + S3_3D]);
 
 /// Noise function that outputs 2/3/4-dimensional Open Simplex noise.
+///
+/// Unlike `Perlin` and `Worley`, Open Simplex noise has no `set_period`. Its
+/// lattice is skewed, so moving the input value along one axis moves it
+/// along every axis of the lattice, by multiples of the stretch constant.
+/// In 2D and 4D that constant is irrational, so no whole-number shift of the
+/// input lands back on the same lattice position, and wrapping the lattice
+/// lookups can't make the output repeat. Use
+/// `PlaneMapBuilder::set_is_seamless` to blend a map into a tile instead.
 #[derive(Clone, Copy, Debug)]
 pub struct OpenSimplex {
+    /// Number of gradient directions used by the 2D noise. Must be 8, 16 or
+    /// 32.
+    pub gradient_count: usize,
+
+    /// Whether the 3D noise rotates its input so that the main diagonal of
+    /// the lattice points along the z axis. See `set_domain_rotation`.
+    pub domain_rotation: bool,
+
     seed: u32,
     perm_table: PermutationTable,
 }
 
 impl OpenSimplex {
     const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_GRADIENT_COUNT: usize = 8;
+    pub const DEFAULT_DOMAIN_ROTATION: bool = false;
 
     pub fn new() -> Self {
         Self {
+            gradient_count: Self::DEFAULT_GRADIENT_COUNT,
+            domain_rotation: Self::DEFAULT_DOMAIN_ROTATION,
             seed: Self::DEFAULT_SEED,
             perm_table: PermutationTable::new(Self::DEFAULT_SEED),
         }
     }
+
+    /// Sets the number of gradient directions used by the 2D noise.
+    ///
+    /// More directions reduce the directional artifacts of the noise at a
+    /// small cost in performance. Supported counts are 8, 16 and 32.
+    pub fn set_gradient_count(self, gradient_count: usize) -> Self {
+        assert!(
+            [8, 16, 32].contains(&gradient_count),
+            "gradient count must be 8, 16 or 32"
+        );
+
+        Self {
+            gradient_count,
+            ..self
+        }
+    }
+
+    /// Sets whether the 3D noise rotates its input before evaluating it.
+    ///
+    /// The lattice of the 3D noise is aligned with the main diagonal, which
+    /// shows up as axis-aligned artifacts in slices of constant z, such as
+    /// textures and height maps sampled in the xy-plane. Enabling the rotation
+    /// turns the diagonal onto the z axis, as OpenSimplex2 does, so those
+    /// slices look more uniform in every direction. The rotation is
+    /// orthonormal, so the noise keeps its feature size and range, but the
+    /// output differs from the unrotated noise. The 2D and 4D noise are not
+    /// affected.
+    pub fn set_domain_rotation(self, domain_rotation: bool) -> Self {
+        Self {
+            domain_rotation,
+            ..self
+        }
+    }
+
+    /// Writes the 2D noise at the points `(xs[i], ys[i])` into `out[i]`.
+    ///
+    /// The output buffer is provided by the caller, so the same buffer can be
+    /// reused across many calls without allocating.
+    ///
+    /// Panics if the three slices don't have the same length.
+    pub fn fill2(&self, xs: &[f64], ys: &[f64], out: &mut [f64]) {
+        assert_eq!(xs.len(), ys.len(), "coordinate slices differ in length");
+        assert_eq!(xs.len(), out.len(), "output slice differs in length");
+
+        for ((value, &x), &y) in out.iter_mut().zip(xs).zip(ys) {
+            *value = self.get([x, y]);
+        }
+    }
 }
 
 impl Default for OpenSimplex {
@@ -56,6 +177,7 @@ impl Seedable for OpenSimplex {
         Self {
             seed,
             perm_table: PermutationTable::new(seed),
+            ..self
         }
     }
 
@@ -64,16 +186,38 @@ impl Seedable for OpenSimplex {
     }
 }
 
+/// Rotates `point` so that the main diagonal (1, 1, 1) points along the z
+/// axis, with the x and y axes staying symmetric to each other.
+fn rotate_diagonal_to_z(point: [f64; 3]) -> [f64; 3] {
+    const ORTHOGONALIZER: f64 = -0.211_324_865_405_187; // -1 / (3 + sqrt(3))
+    const ROOT_3_OVER_3: f64 = 0.577_350_269_189_626;
+
+    let xy = point[0] + point[1];
+    let s = xy * ORTHOGONALIZER;
+    let zz = point[2] * ROOT_3_OVER_3;
+
+    [
+        point[0] + s + zz,
+        point[1] + s + zz,
+        xy * -ROOT_3_OVER_3 + zz,
+    ]
+}
+
 /// 2-dimensional [`OpenSimplex` Noise](http://uniblock.tumblr.com/post/97868843242/noise)
 ///
 /// This is a slower but higher quality form of gradient noise than `Perlin` 2D.
 impl NoiseFn<[f64; 2]> for OpenSimplex {
     fn get(&self, point: [f64; 2]) -> f64 {
-        fn gradient(perm_table: &PermutationTable, vertex: [f64; 2], pos: [f64; 2]) -> f64 {
-            let attn = 2.0 - math::dot2(pos, pos);
+        fn gradient(
+            perm_table: &PermutationTable,
+            count: usize,
+            vertex: [f64; 2],
+            pos: [f64; 2],
+        ) -> f64 {
+            let attn = RADIUS_SQUARED_2D - math::dot2(pos, pos);
             if attn > 0.0 {
                 let index = perm_table.get2(math::to_isize2(vertex));
-                let vec = gradient::get2(index);
+                let vec = gradient::get2_n(index, count);
                 attn.powi(4) * math::dot2(pos, vec)
             } else {
                 0.0
@@ -118,12 +262,12 @@ impl NoiseFn<[f64; 2]> for OpenSimplex {
         // Contribution (1, 0)
         vertex = math::add2(stretched_floor, [1.0, 0.0]);
         dpos = math::sub2(pos0, [t1, t0]);
-        value += gradient(&self.perm_table, vertex, dpos);
+        value += gradient(&self.perm_table, self.gradient_count, vertex, dpos);
 
         // Contribution (0, 1)
         vertex = math::add2(stretched_floor, [0.0, 1.0]);
         dpos = math::sub2(pos0, [t0, t1]);
-        value += gradient(&self.perm_table, vertex, dpos);
+        value += gradient(&self.perm_table, self.gradient_count, vertex, dpos);
 
         // See the graph for an intuitive explanation; the sum of `x` and `y` is
         // only greater than `1` if we're on Region B.
@@ -139,19 +283,23 @@ impl NoiseFn<[f64; 2]> for OpenSimplex {
         }
 
         // Point (0, 0) or (1, 1)
-        value += gradient(&self.perm_table, vertex, dpos);
+        value += gradient(&self.perm_table, self.gradient_count, vertex, dpos);
 
         value * NORM_CONSTANT_2D
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("OpenSimplex")
+    }
 }
 
 /// 3-dimensional [`OpenSimplex` Noise](http://uniblock.tumblr.com/post/97868843242/noise)
 ///
 /// This is a slower but higher quality form of gradient noise than `Perlin` 3D.
 impl NoiseFn<[f64; 3]> for OpenSimplex {
-    fn get(&self, point: [f64; 3]) -> f64 {
+    fn get(&self, mut point: [f64; 3]) -> f64 {
         fn gradient(perm_table: &PermutationTable, vertex: [f64; 3], pos: [f64; 3]) -> f64 {
-            let attn = 2.0 - math::dot3(pos, pos);
+            let attn = RADIUS_SQUARED_3D - math::dot3(pos, pos);
             if attn > 0.0 {
                 let index = perm_table.get3(math::to_isize3(vertex));
                 let vec = gradient::get3(index);
@@ -161,6 +309,10 @@ impl NoiseFn<[f64; 3]> for OpenSimplex {
             }
         }
 
+        if self.domain_rotation {
+            point = rotate_diagonal_to_z(point);
+        }
+
         // Place input coordinates on simplectic h1.0ycomb.
         let stretch_offset = math::fold3(point, Add::add) * STRETCH_CONSTANT_3D;
         let stretched = math::map3(point, |v| v + stretch_offset);
@@ -183,100 +335,30 @@ impl NoiseFn<[f64; 3]> for OpenSimplex {
         // Positions relative to origin point.
         let pos0 = math::sub3(point, skewed_floor);
 
-        let mut value = 0.0;
-
-        let mut vertex;
-        let mut dpos;
-
-        if region_sum <= 1.0 {
-            // We're inside the tetrahedron (3-Simplex) at (0, 0, 0)
-            let t0 = SQUISH_CONSTANT_3D;
-            let t1 = SQUISH_CONSTANT_2D + 1.0;
-
-            // Contribution at (0, 0, 0)
-            vertex = math::add3(stretched_floor, [0.0, 0.0, 0.0]);
-            dpos = math::sub3(pos0, [0.0, 0.0, 0.0]);
-            value += gradient(&self.perm_table, vertex, dpos);
-
-            // Contribution at (1, 0, 0)
-            vertex = math::add3(stretched_floor, [1.0, 0.0, 0.0]);
-            dpos = math::sub3(pos0, [t1, t0, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
-
-            // Contribution at (0, 1, 0)
-            vertex = math::add3(stretched_floor, [0.0, 1.0, 0.0]);
-            dpos = math::sub3(pos0, [t0, t1, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
-
-            // Contribution at (0, 0, 1)
-            vertex = math::add3(stretched_floor, [0.0, 0.0, 1.0]);
-            dpos = math::sub3(pos0, [t0, t0, t1]);
-            value += gradient(&self.perm_table, vertex, dpos);
+        // The sum determines whether we're inside the tetrahedron (3-Simplex)
+        // at (0, 0, 0), the one at (1, 1, 1), or the octahedron (Rectified
+        // 3-Simplex) inbetween.
+        let region = if region_sum <= 1.0 {
+            0
         } else if region_sum >= 2.0 {
-            // We're inside the tetrahedron (3-Simplex) at (1, 1, 1)
-            let t0 = 2.0 * SQUISH_CONSTANT_3D;
-            let t1 = 1.0 + 2.0 * SQUISH_CONSTANT_3D;
-            let t2 = t1 + SQUISH_CONSTANT_3D;
-
-            // Contribution at (1, 1, 0)
-            vertex = math::add3(stretched_floor, [1.0, 1.0, 0.0]);
-            dpos = math::sub3(pos0, [t1, t1, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
-
-            // Contribution at (1, 0, 1)
-            vertex = math::add3(stretched_floor, [1.0, 0.0, 1.0]);
-            dpos = math::sub3(pos0, [t1, t0, t1]);
-            value += gradient(&self.perm_table, vertex, dpos);
-
-            // Contribution at (0, 1, 1)
-            vertex = math::add3(stretched_floor, [0.0, 1.0, 1.0]);
-            dpos = math::sub3(pos0, [t0, t1, t1]);
-            value += gradient(&self.perm_table, vertex, dpos);
-
-            // Contribution at (1, 1, 1)
-            vertex = math::add3(stretched_floor, [1.0, 1.0, 1.0]);
-            dpos = math::sub3(pos0, [t2, t2, t2]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            2
         } else {
-            // We're inside the octahedron (Rectified 3-Simplex) inbetween.
-            let t0 = SQUISH_CONSTANT_3D;
-            let t1 = 1.0 + SQUISH_CONSTANT_3D;
-            let t2 = 2.0 * SQUISH_CONSTANT_3D;
-            let t3 = 1.0 + 2.0 * SQUISH_CONSTANT_3D;
-
-            // Contribution at (1, 0, 0)
-            vertex = math::add3(stretched_floor, [1.0, 0.0, 0.0]);
-            dpos = math::sub3(pos0, [t1, t0, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
-
-            // Contribution at (0, 1, 0)
-            vertex = math::add3(stretched_floor, [0.0, 1.0, 0.0]);
-            dpos = math::sub3(pos0, [t0, t1, t0]);
-            value += gradient(&self.perm_table, vertex, dpos);
+            1
+        };
 
-            // Contribution at (0, 0, 1)
-            vertex = math::add3(stretched_floor, [0.0, 0.0, 1.0]);
-            dpos = math::sub3(pos0, [t0, t0, t1]);
-            value += gradient(&self.perm_table, vertex, dpos);
-
-            // Contribution at (1, 1, 0)
-            vertex = math::add3(stretched_floor, [1.0, 1.0, 0.0]);
-            dpos = math::sub3(pos0, [t3, t3, t2]);
-            value += gradient(&self.perm_table, vertex, dpos);
-
-            // Contribution at (1, 0, 1)
-            vertex = math::add3(stretched_floor, [1.0, 0.0, 1.0]);
-            dpos = math::sub3(pos0, [t3, t2, t3]);
-            value += gradient(&self.perm_table, vertex, dpos);
-
-            // Contribution at (0, 1, 1)
-            vertex = math::add3(stretched_floor, [0.0, 1.0, 1.0]);
-            dpos = math::sub3(pos0, [t2, t3, t3]);
+        let mut value = 0.0;
+        for &(offset, displacement) in LATTICE_3D[region] {
+            let vertex = math::add3(stretched_floor, offset);
+            let dpos = math::sub3(pos0, displacement);
             value += gradient(&self.perm_table, vertex, dpos);
         }
 
         value * NORM_CONSTANT_3D
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("OpenSimplex")
+    }
 }
 
 /// 4-dimensional [`OpenSimplex` Noise](http://uniblock.tumblr.com/post/97868843242/noise)
@@ -286,7 +368,7 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
     fn get(&self, point: [f64; 4]) -> f64 {
         #[inline(always)]
         fn gradient(perm_table: &PermutationTable, vertex: [f64; 4], pos: [f64; 4]) -> f64 {
-            let attn = 2.0 - math::dot4(pos, pos);
+            let attn = RADIUS_SQUARED_4D - math::dot4(pos, pos);
             if attn > 0.0 {
                 let index = perm_table.get4(math::to_isize4(vertex));
                 let vec = gradient::get4(index);
@@ -614,4 +696,148 @@ impl NoiseFn<[f64; 4]> for OpenSimplex {
 
         value * NORM_CONSTANT_4D
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("OpenSimplex")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Measures how strongly the gradients of the noise favor the directions of
+    // the 8-direction gradient set, as the magnitude of the mean of
+    // e^(8i * angle) over a large grid of samples. Perfectly isotropic noise
+    // gives 0.
+    fn directional_bias<F: Fn([f64; 2]) -> f64>(noise: F) -> f64 {
+        let delta = 1e-4;
+        let (mut re, mut im) = (0.0, 0.0);
+        let mut n = 0;
+
+        for y in 0..200 {
+            for x in 0..200 {
+                let point = [x as f64 * 0.173, y as f64 * 0.173];
+                let value = noise(point);
+                let dx = noise([point[0] + delta, point[1]]) - value;
+                let dy = noise([point[0], point[1] + delta]) - value;
+
+                let angle = dy.atan2(dx) * 8.0;
+                re += angle.cos();
+                im += angle.sin();
+                n += 1;
+            }
+        }
+
+        re.hypot(im) / n as f64
+    }
+
+    #[test]
+    fn default_gradient_count_is_unchanged() {
+        let noise = OpenSimplex::new();
+        let explicit = OpenSimplex::new().set_gradient_count(8);
+
+        for &point in &[[0.3, 0.7], [12.5, -3.25], [-100.1, 4.9]] {
+            assert_eq!(noise.get(point), explicit.get(point));
+        }
+    }
+
+    #[test]
+    fn more_gradients_reduce_directional_bias() {
+        let bias = |noise: OpenSimplex| directional_bias(|point| noise.get(point));
+        let bias_8 = bias(OpenSimplex::new());
+        let bias_16 = bias(OpenSimplex::new().set_gradient_count(16));
+        let bias_32 = bias(OpenSimplex::new().set_gradient_count(32));
+
+        assert!(bias_16 < bias_8 / 2.0);
+        assert!(bias_32 < bias_8 / 2.0);
+    }
+
+    #[test]
+    fn domain_rotation_reduces_directional_bias() {
+        let bias = |noise: OpenSimplex, z: f64| {
+            directional_bias(|point| noise.get([point[0], point[1], z]))
+        };
+        let rotated = OpenSimplex::new().set_domain_rotation(true);
+
+        for &z in &[0.37, 5.2] {
+            assert!(bias(rotated, z) < bias(OpenSimplex::new(), z) / 4.0);
+        }
+
+        let point = [0.3, 0.7, -1.2];
+        assert_ne!(rotated.get(point), OpenSimplex::new().get(point));
+        assert_eq!(rotated.get([0.3, 0.7]), OpenSimplex::new().get([0.3, 0.7]));
+    }
+
+    // Largest change in value between consecutive samples along a line that
+    // crosses many region borders.
+    fn max_step<F: Fn(f64) -> f64>(f: F) -> f64 {
+        let delta = 1e-5;
+        let mut previous = f(0.0);
+        let mut max = 0.0;
+
+        for i in 1..300_000 {
+            let value = f(i as f64 * delta);
+            max = f64::max(max, (value - previous).abs());
+            previous = value;
+        }
+
+        max
+    }
+
+    #[test]
+    fn output_is_continuous() {
+        let noise = OpenSimplex::new();
+
+        assert!(max_step(|t| noise.get([t, 0.37 * t + 0.11])) < 1e-3);
+        assert!(max_step(|t| noise.get([t, 0.37 * t + 0.11, 0.61 * t])) < 1e-3);
+        assert!(max_step(|t| noise.get([t, 0.37 * t, 0.61 * t, 0.83 * t + 0.2])) < 1e-3);
+    }
+
+    #[test]
+    fn matches_reference_3d() {
+        // Output of the original, fully unrolled implementation.
+        let reference = [
+            ([0.1, 0.2, 0.3], -0.0009878976618358617, 0.18023085587797355),
+            ([-1.75, 0.85, 0.4], -0.1402305348122365, 0.12413273021165755),
+            ([-1.04, 0.42, 0.69], 0.5516549804741562, -0.3040895329975253),
+            (
+                [0.42, -0.4, 1.29],
+                0.019581203436634165,
+                0.21899293114322774,
+            ),
+            ([2.6, -1.6, 2.2], 0.40159190989071236, -0.23689586812793467),
+            (
+                [12.5, 7.25, -3.125],
+                -0.5816651903146531,
+                -0.2784862910598317,
+            ),
+            (
+                [-40.3, 18.9, 5.7],
+                -0.02263690442861203,
+                -0.4376115457243284,
+            ),
+            ([100.01, -0.5, 33.3], -0.54129041015128, 0.41764505723687867),
+        ];
+        let seeded = OpenSimplex::new().set_seed(42);
+
+        for &(point, expected, expected_seeded) in &reference {
+            assert!((OpenSimplex::new().get(point) - expected).abs() < 1e-12);
+            assert!((seeded.get(point) - expected_seeded).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn fill2_matches_get() {
+        let noise = OpenSimplex::new();
+        let xs: Vec<_> = (0..50).map(|i| i as f64 * 0.37 - 3.0).collect();
+        let ys: Vec<_> = (0..50).map(|i| i as f64 * -0.53 + 1.0).collect();
+        let mut out = vec![0.0; 50];
+
+        noise.fill2(&xs, &ys, &mut out);
+
+        for i in 0..50 {
+            assert_eq!(out[i], noise.get([xs[i], ys[i]]));
+        }
+    }
 }
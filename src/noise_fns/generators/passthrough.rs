@@ -0,0 +1,45 @@
+use crate::noise_fns::{NodeDesc, NoiseFn};
+
+/// Noise function that outputs the first component of the input value.
+///
+/// The output is not limited to the [-1,1] range. Like `Linear`, this is
+/// mostly useful for testing noise graphs and map builders with a known,
+/// non-constant input.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Passthrough;
+
+impl Passthrough {
+    pub fn new() -> Self {
+        Passthrough
+    }
+}
+
+impl NoiseFn<[f64; 2]> for Passthrough {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        point[0]
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Passthrough")
+    }
+}
+
+impl NoiseFn<[f64; 3]> for Passthrough {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        point[0]
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Passthrough")
+    }
+}
+
+impl NoiseFn<[f64; 4]> for Passthrough {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        point[0]
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Passthrough")
+    }
+}
@@ -1,25 +1,128 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
 use crate::{
-    gradient, math,
-    noise_fns::{NoiseFn, Seedable},
-    permutationtable::PermutationTable,
+    gradient,
+    math::{self, interpolate},
+    noise_fns::{NodeDesc, NoiseFn, Seedable},
+    permutationtable::{HashMode, PermutationTable},
 };
 
 /// Noise function that outputs 2/3/4-dimensional Perlin noise.
 #[derive(Clone, Copy, Debug)]
 pub struct Perlin {
+    /// Algorithm used to combine the gradients at the corners of each cell.
+    pub variant: PerlinVariant,
+
     seed: u32,
     perm_table: PermutationTable,
 }
 
+/// Algorithms supported by `Perlin`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PerlinVariant {
+    /// Each corner contributes a radially attenuated gradient, and the
+    /// contributions are summed.
+    Surflet,
+
+    /// The gradients at the corners are blended with a quintic S-curve, as in
+    /// Ken Perlin's improved noise.
+    Interpolated,
+}
+
 impl Perlin {
     pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_VARIANT: PerlinVariant = PerlinVariant::Surflet;
 
     pub fn new() -> Self {
         Self {
+            variant: Self::DEFAULT_VARIANT,
             seed: Self::DEFAULT_SEED,
             perm_table: PermutationTable::new(Self::DEFAULT_SEED),
         }
     }
+
+    /// Sets the algorithm used for all dimensions.
+    pub fn set_variant(self, variant: PerlinVariant) -> Self {
+        Self { variant, ..self }
+    }
+
+    /// Sets the method used to hash the lattice points.
+    ///
+    /// The default `HashMode::Table` repeats every 256 units along each axis,
+    /// which can show up as tiling over very large areas. `HashMode::Integer`
+    /// removes the repetition. The two modes produce different noise for the
+    /// same seed.
+    pub fn set_hash(self, hash_mode: HashMode) -> Self {
+        Self {
+            perm_table: self.perm_table.set_hash_mode(hash_mode),
+            ..self
+        }
+    }
+
+    pub fn hash_mode(&self) -> HashMode {
+        self.perm_table.hash_mode()
+    }
+
+    /// Sets the number of lattice cells after which the noise repeats along
+    /// each axis, producing seamlessly tiling textures.
+    ///
+    /// The lattice points are looked up modulo the period, so the output at
+    /// `x` and at `x + period` is the same. A period of 0 leaves an axis
+    /// unbounded, as do axes without a period. A `PlaneMapBuilder` whose
+    /// bounds span a whole number of periods therefore builds a map that
+    /// tiles.
+    pub fn set_period(self, period: &[usize]) -> Self {
+        assert!(period.len() <= 4, "period supports at most 4 axes");
+
+        let mut cells = [0; 4];
+        cells[..period.len()].copy_from_slice(period);
+
+        Self {
+            perm_table: self.perm_table.set_period(cells),
+            ..self
+        }
+    }
+
+    pub fn period(&self) -> [usize; 4] {
+        self.perm_table.period()
+    }
+
+    fn xy_period(&self) -> Option<[f64; 2]> {
+        match self.period() {
+            [0, _, _, _] | [_, 0, _, _] => None,
+            [x, y, _, _] => Some([x as f64, y as f64]),
+        }
+    }
+
+    /// Returns the value at `point`, attenuated to reduce aliasing when the
+    /// noise is sampled coarsely.
+    ///
+    /// `filter_width` is the size of the area covered by one sample, usually
+    /// the spacing between neighboring samples, in the same units as `point`.
+    /// Features of the noise are about one unit wide, so the output fades
+    /// smoothly from the unfiltered value at widths of a quarter unit or less
+    /// toward zero at widths of one unit or more, where the features can no
+    /// longer be resolved. This is the noise analogue of mipmapping.
+    pub fn get_filtered(&self, point: [f64; 2], filter_width: f64) -> f64 {
+        let fade = math::clamp((filter_width - 0.25) / 0.75, 0.0, 1.0);
+
+        self.get(point) * (1.0 - interpolate::s_curve3(fade))
+    }
+
+    /// Writes the 2D noise at the points `(xs[i], ys[i])` into `out[i]`.
+    ///
+    /// The output buffer is provided by the caller, so the same buffer can be
+    /// reused across many calls without allocating.
+    ///
+    /// Panics if the three slices don't have the same length.
+    pub fn fill2(&self, xs: &[f64], ys: &[f64], out: &mut [f64]) {
+        assert_eq!(xs.len(), ys.len(), "coordinate slices differ in length");
+        assert_eq!(xs.len(), out.len(), "output slice differs in length");
+
+        for ((value, &x), &y) in out.iter_mut().zip(xs).zip(ys) {
+            *value = self.get([x, y]);
+        }
+    }
 }
 
 impl Default for Perlin {
@@ -39,7 +142,10 @@ impl Seedable for Perlin {
         // Otherwise, regenerate the permutation table based on the new seed.
         Self {
             seed,
-            perm_table: PermutationTable::new(seed),
+            perm_table: PermutationTable::new(seed)
+                .set_hash_mode(self.hash_mode())
+                .set_period(self.period()),
+            ..self
         }
     }
 
@@ -51,6 +157,10 @@ impl Seedable for Perlin {
 /// 2-dimensional perlin noise
 impl NoiseFn<[f64; 2]> for Perlin {
     fn get(&self, point: [f64; 2]) -> f64 {
+        if self.variant == PerlinVariant::Interpolated {
+            return interpolated2(&self.perm_table, point);
+        }
+
         const SCALE_FACTOR: f64 = 3.160_493_827_160_493_7;
 
         #[inline(always)]
@@ -93,11 +203,23 @@ impl NoiseFn<[f64; 2]> for Perlin {
         // Multiply by arbitrary value to scale to -1..1
         math::clamp((f00 + f10 + f01 + f11) * SCALE_FACTOR, -1.0, 1.0)
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Perlin")
+    }
+
+    fn is_periodic(&self) -> Option<[f64; 2]> {
+        self.xy_period()
+    }
 }
 
 /// 3-dimensional perlin noise
 impl NoiseFn<[f64; 3]> for Perlin {
     fn get(&self, point: [f64; 3]) -> f64 {
+        if self.variant == PerlinVariant::Interpolated {
+            return interpolated3(&self.perm_table, point);
+        }
+
         const SCALE_FACTOR: f64 = 3.889_855_325_553_107_4;
 
         #[inline(always)]
@@ -164,11 +286,23 @@ impl NoiseFn<[f64; 3]> for Perlin {
             1.0,
         )
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Perlin")
+    }
+
+    fn is_periodic(&self) -> Option<[f64; 2]> {
+        self.xy_period()
+    }
 }
 
 /// 4-dimensional perlin noise
 impl NoiseFn<[f64; 4]> for Perlin {
     fn get(&self, point: [f64; 4]) -> f64 {
+        if self.variant == PerlinVariant::Interpolated {
+            return interpolated4(&self.perm_table, point);
+        }
+
         const SCALE_FACTOR: f64 = 4.424_369_240_215_691;
 
         #[inline(always)]
@@ -396,4 +530,290 @@ impl NoiseFn<[f64; 4]> for Perlin {
             1.0,
         )
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Perlin")
+    }
+
+    fn is_periodic(&self) -> Option<[f64; 2]> {
+        self.xy_period()
+    }
+}
+
+/// 2-dimensional interpolated Perlin noise.
+fn interpolated2(perm_table: &PermutationTable, point: [f64; 2]) -> f64 {
+    // Scales the largest possible output, sqrt(N) / 2, to 1.
+    const SCALE_FACTOR: f64 = core::f64::consts::SQRT_2;
+
+    let floored = math::map2(point, f64::floor);
+    let near_corner = math::to_isize2(floored);
+    let near_distance = math::sub2(point, floored);
+
+    let corner = |offset: [isize; 2]| {
+        let distance = math::sub2(near_distance, math::to_f64_2(offset));
+        let gradient = gradient::get2(perm_table.get2(math::add2(near_corner, offset)));
+        math::dot2(distance, gradient)
+    };
+
+    let u = math::map2(near_distance, interpolate::s_curve5);
+    let lerp = interpolate::linear;
+
+    let value = lerp(
+        lerp(corner([0, 0]), corner([1, 0]), u[0]),
+        lerp(corner([0, 1]), corner([1, 1]), u[0]),
+        u[1],
+    );
+
+    math::clamp(value * SCALE_FACTOR, -1.0, 1.0)
+}
+
+/// 3-dimensional interpolated Perlin noise.
+fn interpolated3(perm_table: &PermutationTable, point: [f64; 3]) -> f64 {
+    const SCALE_FACTOR: f64 = 1.154_700_538_379_251_5; // 2 / sqrt(3)
+
+    let floored = math::map3(point, f64::floor);
+    let near_corner = math::to_isize3(floored);
+    let near_distance = math::sub3(point, floored);
+
+    let corner = |offset: [isize; 3]| {
+        let distance = math::sub3(near_distance, math::to_f64_3(offset));
+        let gradient = gradient::get3(perm_table.get3(math::add3(near_corner, offset)));
+        math::dot3(distance, gradient)
+    };
+
+    let u = math::map3(near_distance, interpolate::s_curve5);
+    let lerp = interpolate::linear;
+
+    let face = |z| {
+        lerp(
+            lerp(corner([0, 0, z]), corner([1, 0, z]), u[0]),
+            lerp(corner([0, 1, z]), corner([1, 1, z]), u[0]),
+            u[1],
+        )
+    };
+    let value = lerp(face(0), face(1), u[2]);
+
+    math::clamp(value * SCALE_FACTOR, -1.0, 1.0)
+}
+
+/// 4-dimensional interpolated Perlin noise.
+fn interpolated4(perm_table: &PermutationTable, point: [f64; 4]) -> f64 {
+    let floored = math::map4(point, f64::floor);
+    let near_corner = math::to_isize4(floored);
+    let near_distance = math::sub4(point, floored);
+
+    let corner = |offset: [isize; 4]| {
+        let distance = math::sub4(near_distance, math::to_f64_4(offset));
+        let gradient = gradient::get4(perm_table.get4(math::add4(near_corner, offset)));
+        math::dot4(distance, gradient)
+    };
+
+    let u = math::map4(near_distance, interpolate::s_curve5);
+    let lerp = interpolate::linear;
+
+    let face = |z, w| {
+        lerp(
+            lerp(corner([0, 0, z, w]), corner([1, 0, z, w]), u[0]),
+            lerp(corner([0, 1, z, w]), corner([1, 1, z, w]), u[0]),
+            u[1],
+        )
+    };
+    let cube = |w| lerp(face(0, w), face(1, w), u[2]);
+    let value = lerp(cube(0), cube(1), u[3]);
+
+    // The largest possible output, sqrt(N) / 2, is already 1.
+    math::clamp(value, -1.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::ScalePoint;
+
+    // Changing these values changes the output for existing seeds, which
+    // requires bumping `ALGORITHM_VERSION`.
+    #[test]
+    fn output_is_stable() {
+        let perlin = Perlin::new().set_seed(42);
+
+        assert_eq!(perlin.get([0.3, 1.7]), -0.332_021_282_833_555_75);
+        assert_eq!(perlin.get([0.3, 1.7, -2.2]), -0.139_313_657_328_902_47);
+        assert_eq!(perlin.get([0.3, 1.7, -2.2, 5.9]), 0.148_906_705_996_573_02);
+    }
+
+    #[test]
+    fn interpolated_output_is_stable() {
+        let perlin = Perlin::new()
+            .set_seed(42)
+            .set_variant(PerlinVariant::Interpolated);
+
+        assert_eq!(perlin.get([0.3, 1.7]), -0.144_695_424_033_947_1);
+        assert_eq!(perlin.get([0.3, 1.7, -2.2]), -0.071_351_380_188_582_6);
+        assert_eq!(perlin.get([0.3, 1.7, -2.2, 5.9]), 0.156_303_098_518_624_56);
+    }
+
+    #[test]
+    fn variant_applies_to_all_dimensions() {
+        let surflet = Perlin::new();
+        let interpolated = surflet.set_variant(PerlinVariant::Interpolated);
+
+        for i in 0..20 {
+            let (x, y, z) = (i as f64 * 0.37 + 0.1, i as f64 * -0.53, 0.71);
+
+            assert_ne!(surflet.get([x, y]), interpolated.get([x, y]));
+            assert_ne!(surflet.get([x, y, z]), interpolated.get([x, y, z]));
+            assert_ne!(surflet.get([x, y, z, z]), interpolated.get([x, y, z, z]));
+        }
+    }
+
+    #[test]
+    fn integer_hash_does_not_repeat() {
+        let table = Perlin::new().set_seed(3);
+        let integer = table.set_hash(HashMode::Integer);
+        assert_eq!(integer.set_seed(4).hash_mode(), HashMode::Integer);
+
+        let mut repeats = 0;
+        for i in 0..200 {
+            let point = [i as f64 * 0.37 + 0.1, i as f64 * -0.53];
+            let shifted = [point[0] + 256.0, point[1]];
+
+            assert!((table.get(shifted) - table.get(point)).abs() < 1e-9);
+            if (integer.get(shifted) - integer.get(point)).abs() < 1e-9 {
+                repeats += 1;
+            }
+
+            let value = integer.get([point[0], point[1], 0.71]);
+            assert!((-1.0..=1.0).contains(&value));
+        }
+        assert!(repeats < 10);
+    }
+
+    #[test]
+    fn reports_period() {
+        let periodic = Perlin::new().set_period(&[4, 3]);
+
+        assert_eq!(
+            NoiseFn::<[f64; 2]>::is_periodic(&periodic),
+            Some([4.0, 3.0])
+        );
+        assert_eq!(NoiseFn::<[f64; 3]>::is_periodic(&Perlin::new()), None);
+        assert_eq!(
+            NoiseFn::<[f64; 2]>::is_periodic(&Perlin::new().set_period(&[4])),
+            None
+        );
+
+        let scaled = ScalePoint::new(&periodic)
+            .set_x_scale(0.5)
+            .set_y_scale(-2.0);
+        assert_eq!(NoiseFn::<[f64; 3]>::is_periodic(&scaled), Some([8.0, 1.5]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn period_tiles_seamlessly() {
+        use crate::utils::{NoiseMapBuilder, PlaneMapBuilder};
+
+        for &variant in &[PerlinVariant::Surflet, PerlinVariant::Interpolated] {
+            let perlin = Perlin::new()
+                .set_variant(variant)
+                .set_period(&[4, 3, 5, 2])
+                .set_seed(9);
+            assert_eq!(perlin.period(), [4, 3, 5, 2]);
+
+            // Maps over neighboring periods are identical.
+            let map = |x: f64, y: f64| {
+                PlaneMapBuilder::new(&perlin)
+                    .set_size(16, 12)
+                    .set_x_bounds(x, x + 4.0)
+                    .set_y_bounds(y, y + 3.0)
+                    .build()
+            };
+            let tile = map(0.0, 0.0);
+            assert!(tile.approx_eq(&map(4.0, 0.0), 1e-9));
+            assert!(tile.approx_eq(&map(-8.0, 3.0), 1e-9));
+
+            for i in 0..50 {
+                let point = [i as f64 * 0.37 - 4.0, i as f64 * 0.23, 0.61, 1.3];
+                let shifted = [
+                    point[0] + 4.0,
+                    point[1] - 3.0,
+                    point[2] + 5.0,
+                    point[3] + 2.0,
+                ];
+                let (point2, shifted2) = ([point[0], point[1]], [shifted[0], shifted[1]]);
+
+                assert!((perlin.get(point2) - perlin.get(shifted2)).abs() < 1e-9);
+                assert!((perlin.get(point) - perlin.get(shifted)).abs() < 1e-9);
+                let (point3, shifted3) = (
+                    [point[0], point[1], point[2]],
+                    [shifted[0], shifted[1], shifted[2]],
+                );
+                assert!((perlin.get(point3) - perlin.get(shifted3)).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn filtering_reduces_variance() {
+        let perlin = Perlin::new();
+        let points: Vec<_> = (0..500)
+            .map(|i| [i as f64 * 0.37 + 0.1, i as f64 * -0.53])
+            .collect();
+
+        let sum_of_squares = |filter_width| {
+            points
+                .iter()
+                .map(|&point| perlin.get_filtered(point, filter_width).powi(2))
+                .sum::<f64>()
+        };
+
+        for &point in &points {
+            assert_eq!(perlin.get_filtered(point, 0.0), perlin.get(point));
+        }
+        assert!(sum_of_squares(0.5) < sum_of_squares(0.0));
+        assert!(sum_of_squares(0.8) < sum_of_squares(0.5));
+        assert_eq!(sum_of_squares(1.0), 0.0);
+    }
+
+    #[test]
+    fn fill2_matches_get() {
+        let noise = Perlin::new();
+        let xs: Vec<_> = (0..50).map(|i| i as f64 * 0.37 - 3.0).collect();
+        let ys: Vec<_> = (0..50).map(|i| i as f64 * -0.53 + 1.0).collect();
+        let mut out = vec![0.0; 50];
+
+        noise.fill2(&xs, &ys, &mut out);
+
+        for i in 0..50 {
+            assert_eq!(out[i], noise.get([xs[i], ys[i]]));
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_tests {
+    use super::*;
+    use crate::noise_fns::{Fbm, MultiFractal};
+
+    // `libm` may round differently from the platform's math library, so the
+    // values only have to match the pinned ones in `tests` closely.
+    #[test]
+    fn get_without_std() {
+        let perlin = Perlin::new().set_seed(42);
+
+        assert!((perlin.get([0.3, 1.7]) + 0.332_021_282_833_555_75).abs() < 1e-12);
+        assert!((perlin.get([0.3, 1.7, -2.2]) + 0.139_313_657_328_902_47).abs() < 1e-12);
+        assert!((perlin.get([0.3, 1.7, -2.2, 5.9]) - 0.148_906_705_996_573_02).abs() < 1e-12);
+    }
+
+    #[test]
+    fn fractal_without_std() {
+        let fbm = Fbm::new().set_octaves(4);
+
+        for i in 0..100 {
+            let value = fbm.get([i as f64 * 0.37 - 3.0, i as f64 * -0.53, 0.71]);
+
+            assert!((-1.0..=1.0).contains(&value));
+        }
+    }
 }
@@ -0,0 +1,101 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
+
+/// Noise function that outputs the distance to the nearest of a set of
+/// points.
+///
+/// The distance is divided by `falloff`, so the output is 0.0 at the points
+/// and 1.0 at a distance of `falloff` from the nearest one. With `signed`
+/// enabled, 1.0 is subtracted from that, which turns the output into a signed
+/// distance to discs of radius `falloff` around the points: negative inside a
+/// disc, zero on its edge and positive outside. This is useful for placing
+/// scattered features, such as villages or craters, at known positions.
+///
+/// The output is not limited to the [-1,1] range.
+#[derive(Clone, Debug)]
+pub struct PointCloud {
+    /// Points the distance is measured to.
+    pub points: Vec<[f64; 2]>,
+
+    /// Distance that maps to an output of 1.0. Default is 1.0.
+    pub falloff: f64,
+
+    /// Whether to output the signed distance to discs around the points
+    /// instead of the distance to the points. Default is false.
+    pub signed: bool,
+}
+
+impl PointCloud {
+    pub const DEFAULT_FALLOFF: f64 = 1.0;
+    pub const DEFAULT_SIGNED: bool = false;
+
+    /// Panics if `points` is empty.
+    pub fn new(points: Vec<[f64; 2]>) -> Self {
+        assert!(!points.is_empty(), "at least one point is required");
+
+        Self {
+            points,
+            falloff: Self::DEFAULT_FALLOFF,
+            signed: Self::DEFAULT_SIGNED,
+        }
+    }
+
+    /// Panics if `falloff` is not positive.
+    pub fn set_falloff(self, falloff: f64) -> Self {
+        assert!(falloff > 0.0, "falloff must be positive");
+
+        Self { falloff, ..self }
+    }
+
+    pub fn set_signed(self, signed: bool) -> Self {
+        Self { signed, ..self }
+    }
+}
+
+impl NoiseFn<[f64; 2]> for PointCloud {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let distance_squared = self
+            .points
+            .iter()
+            .map(|p| (p[0] - point[0]).powi(2) + (p[1] - point[1]).powi(2))
+            .fold(core::f64::INFINITY, f64::min);
+        let distance = distance_squared.sqrt() / self.falloff;
+
+        if self.signed {
+            distance - 1.0
+        } else {
+            distance
+        }
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("PointCloud")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distance_grows_away_from_points() {
+        let cloud = PointCloud::new(vec![[0.0, 0.0], [4.0, 1.0]]).set_falloff(2.0);
+
+        assert_eq!(cloud.get([0.0, 0.0]), 0.0);
+        assert_eq!(cloud.get([4.0, 1.0]), 0.0);
+        assert_eq!(cloud.get([4.0, 3.0]), 1.0);
+
+        let mut previous = 0.0;
+        for i in 1..20 {
+            let value = cloud.get([-0.1 * i as f64, 0.0]);
+            assert!(value > previous);
+            previous = value;
+        }
+
+        let signed = cloud.set_signed(true);
+        assert_eq!(signed.get([0.0, 0.0]), -1.0);
+        assert_eq!(signed.get([0.0, 2.0]), 0.0);
+        assert!(signed.get([0.0, 3.0]) > 0.0);
+    }
+}
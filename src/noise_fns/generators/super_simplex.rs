@@ -1,7 +1,9 @@
-use crate::noise_fns::{NoiseFn, Seedable};
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn, Seedable};
 use crate::permutationtable::PermutationTable;
 use crate::{gradient, math};
-use std::ops::Add;
+use core::ops::Add;
 
 const TO_REAL_CONSTANT_2D: f64 = -0.211_324_865_405_187; // (1 / sqrt(2 + 1) - 1) / 2
 const TO_SIMPLEX_CONSTANT_2D: f64 = 0.366_025_403_784_439; // (sqrt(2 + 1) - 1) / 2
@@ -170,6 +172,10 @@ impl NoiseFn<[f64; 2]> for SuperSimplex {
 
         value * NORM_CONSTANT_2D
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("SuperSimplex")
+    }
 }
 
 /// 3-dimensional Super Simplex noise
@@ -247,4 +253,8 @@ impl NoiseFn<[f64; 3]> for SuperSimplex {
 
         value * NORM_CONSTANT_3D
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("SuperSimplex")
+    }
 }
@@ -1,6 +1,8 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
 use crate::{
     math::{self, interpolate},
-    noise_fns::{NoiseFn, Seedable},
+    noise_fns::{NodeDesc, NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
 
@@ -71,6 +73,10 @@ impl NoiseFn<[f64; 2]> for Value {
 
         d * 2.0 - 1.0
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Value")
+    }
 }
 
 /// 3-dimensional value noise
@@ -128,6 +134,10 @@ impl NoiseFn<[f64; 3]> for Value {
 
         d * 2.0 - 1.0
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Value")
+    }
 }
 
 /// 4-dimensional value noise
@@ -250,4 +260,8 @@ impl NoiseFn<[f64; 4]> for Value {
 
         d * 2.0 - 1.0
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Value")
+    }
 }
@@ -0,0 +1,118 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{NodeDesc, NoiseFn},
+};
+use core::f64::consts::PI;
+
+/// A single plane wave of a `Waves` noise function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Wave {
+    /// Direction the wave travels in. Only the orientation matters, as the
+    /// direction is normalized.
+    pub direction: [f64; 2],
+
+    /// Distance between two successive crests of the wave.
+    pub wavelength: f64,
+
+    /// Height of the crests of the wave.
+    pub amplitude: f64,
+
+    /// Offset of the wave along its direction, in radians.
+    pub phase: f64,
+}
+
+/// Noise function that outputs the sum of a set of sine plane waves.
+///
+/// Overlapping waves interfere with each other, which produces the patterns
+/// seen in water caustics, moiré and standing waves. The sum is divided by
+/// the total amplitude of the waves, so the output lies in the [-1,1] range.
+/// Without any waves, the output is 0.0.
+#[derive(Clone, Debug, Default)]
+pub struct Waves {
+    /// The waves that are summed.
+    pub waves: Vec<Wave>,
+}
+
+impl Waves {
+    pub fn new() -> Self {
+        Self { waves: Vec::new() }
+    }
+
+    /// Adds a plane wave travelling in `direction`.
+    pub fn add_wave(
+        mut self,
+        direction: [f64; 2],
+        wavelength: f64,
+        amplitude: f64,
+        phase: f64,
+    ) -> Self {
+        assert!(wavelength > 0.0);
+        assert!(direction[0] != 0.0 || direction[1] != 0.0);
+
+        self.waves.push(Wave {
+            direction,
+            wavelength,
+            amplitude,
+            phase,
+        });
+        self
+    }
+}
+
+impl NoiseFn<[f64; 2]> for Waves {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let total: f64 = self.waves.iter().map(|wave| wave.amplitude.abs()).sum();
+
+        if total == 0.0 {
+            return 0.0;
+        }
+
+        let sum: f64 = self
+            .waves
+            .iter()
+            .map(|wave| {
+                let direction = wave.direction;
+                let length = math::dot2(direction, direction).sqrt();
+                let distance = math::dot2(direction, point) / length;
+
+                wave.amplitude * (2.0 * PI * distance / wave.wavelength + wave.phase).sin()
+            })
+            .sum();
+
+        sum / total
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Waves")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orthogonal_waves_interfere() {
+        let waves =
+            Waves::new()
+                .add_wave([1.0, 0.0], 4.0, 1.0, 0.0)
+                .add_wave([0.0, 2.0], 4.0, 1.0, 0.0);
+
+        // The crests and troughs of the two waves line up in a checkerboard
+        // of peaks and pits, with nodal lines in between.
+        assert!((waves.get([1.0, 1.0]) - 1.0).abs() < 1e-12);
+        assert!((waves.get([3.0, 3.0]) + 1.0).abs() < 1e-12);
+        assert!(waves.get([3.0, 1.0]).abs() < 1e-12);
+        assert!(waves.get([1.0, 3.0]).abs() < 1e-12);
+        assert!((waves.get([5.0, 5.0]) - 1.0).abs() < 1e-12);
+
+        for i in 0..400 {
+            let value = waves.get([(i % 20) as f64 * 0.37, (i / 20) as f64 * 0.53]);
+            assert!((-1.0..=1.0).contains(&value));
+        }
+
+        assert_eq!(Waves::new().get([1.0, 2.0]), 0.0);
+    }
+}
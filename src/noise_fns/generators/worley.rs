@@ -1,10 +1,21 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
 use crate::{
     math,
-    noise_fns::{NoiseFn, Seedable},
+    noise_fns::{NodeDesc, NoiseFn, Seedable},
     permutationtable::PermutationTable,
 };
 
 /// Noise function that outputs Worley noise.
+///
+/// For a return rank of 0, the closest seed point is found by default with a
+/// quick search of the few neighboring cells most likely to hold it. This
+/// search is approximate: it never reports a seed point closer than the
+/// closest one, but it occasionally settles for one slightly farther away,
+/// which shows up as small steps in the range output along some cell edges.
+/// A search radius above 1, a return rank above 0 or a stretch switch to an
+/// exhaustive search of the surrounding cells, which always finds the seed
+/// point of the requested rank within the searched cells, at a higher cost.
 #[derive(Clone, Copy, Debug)]
 pub struct Worley {
     /// Specifies the range function to use when calculating the boundaries of
@@ -26,6 +37,38 @@ pub struct Worley {
     /// the displacement value.
     pub displacement: f64,
 
+    /// Rank of the seed point used for the output value, where 0 is the
+    /// closest seed point (F1), 1 is the second closest (F2), and so on.
+    pub return_rank: usize,
+
+    /// How far the seed point of each cell can be displaced from the cell's
+    /// lattice point, as a fraction of the maximum displacement.
+    ///
+    /// A jitter of 0.0 places the seed points on a regular grid, while the
+    /// default of 1.0 displaces them fully at random.
+    pub jitter: f64,
+
+    /// Scale of each axis in the distance metric. Stretching an axis by a
+    /// factor greater than 1.0 elongates the cells along that axis.
+    ///
+    /// Only the first N components are used for N-dimensional input.
+    pub stretch: [f64; 4],
+
+    /// Number of cells after which the pattern repeats along each axis, or 0
+    /// for no repetition.
+    ///
+    /// Only the first N components are used for N-dimensional input.
+    pub period: [usize; 4],
+
+    /// Width of the smooth minimum that blends the distances to the nearby
+    /// seed points. See `set_smoothing`.
+    pub smoothing: f64,
+
+    /// Number of cells around the input point searched for the closest seed
+    /// point. The default radius of 1 uses an approximate search, see
+    /// `set_search_radius`.
+    pub search_radius: usize,
+
     seed: u32,
     perm_table: PermutationTable,
 }
@@ -35,6 +78,12 @@ impl Worley {
     pub const DEFAULT_RANGEFUNCTION: RangeFunction = RangeFunction::Euclidean;
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
     pub const DEFAULT_DISPLACEMENT: f64 = 1.0;
+    pub const DEFAULT_RETURN_RANK: usize = 0;
+    pub const DEFAULT_JITTER: f64 = 1.0;
+    pub const DEFAULT_STRETCH: [f64; 4] = [1.0; 4];
+    pub const DEFAULT_PERIOD: [usize; 4] = [0; 4];
+    pub const DEFAULT_SMOOTHING: f64 = 0.0;
+    pub const DEFAULT_SEARCH_RADIUS: usize = 1;
 
     pub fn new() -> Self {
         Self {
@@ -44,6 +93,12 @@ impl Worley {
             enable_range: false,
             frequency: Self::DEFAULT_FREQUENCY,
             displacement: Self::DEFAULT_DISPLACEMENT,
+            return_rank: Self::DEFAULT_RETURN_RANK,
+            jitter: Self::DEFAULT_JITTER,
+            stretch: Self::DEFAULT_STRETCH,
+            period: Self::DEFAULT_PERIOD,
+            smoothing: Self::DEFAULT_SMOOTHING,
+            search_radius: Self::DEFAULT_SEARCH_RADIUS,
         }
     }
 
@@ -75,6 +130,195 @@ impl Worley {
             ..self
         }
     }
+
+    /// Sets the rank of the seed point used for the output value.
+    ///
+    /// A rank of 0 uses the closest seed point (F1), 1 the second closest
+    /// (F2), and so on. Ranks above 0 search the two rings of cells
+    /// surrounding the input point, which is exact for the first few ranks.
+    pub fn set_return_rank(self, return_rank: usize) -> Self {
+        Self {
+            return_rank,
+            ..self
+        }
+    }
+
+    /// Sets how far the seed points can be displaced from the lattice points.
+    ///
+    /// The displacement of each seed point is derived from the hash of its
+    /// cell and scaled by `jitter`, so 0.0 produces a regular grid of seed
+    /// points and 1.0 fully random ones.
+    pub fn set_jitter(self, jitter: f64) -> Self {
+        Self { jitter, ..self }
+    }
+
+    /// Sets the scale of each axis in the distance metric, producing elongated
+    /// cells for textures such as bark or scales.
+    ///
+    /// Differences along each axis are divided by the corresponding factor
+    /// before the range function is applied, so a stretch of `[2.0, 1.0]`
+    /// makes the cells about twice as long along x as along y. Axes without a
+    /// factor keep a stretch of 1.0. Stretched cells are found with a wider
+    /// search of the neighboring cells, which is slower than the default.
+    pub fn set_stretch(self, stretch: &[f64]) -> Self {
+        assert!(stretch.len() <= 4, "stretch supports at most 4 axes");
+        assert!(
+            stretch.iter().all(|&factor| factor > 0.0),
+            "stretch factors must be positive"
+        );
+
+        let mut factors = Self::DEFAULT_STRETCH;
+        factors[..stretch.len()].copy_from_slice(stretch);
+
+        Self {
+            stretch: factors,
+            ..self
+        }
+    }
+
+    /// Sets the number of cells after which the pattern repeats along each
+    /// axis, producing seamlessly tiling textures.
+    ///
+    /// The cells are looked up modulo the period, so the output at `x` and at
+    /// `x + period / frequency` is the same. A period of 0 leaves an axis
+    /// unbounded, as do axes without a period.
+    pub fn set_period(self, period: &[usize]) -> Self {
+        assert!(period.len() <= 4, "period supports at most 4 axes");
+
+        let mut cells = Self::DEFAULT_PERIOD;
+        cells[..period.len()].copy_from_slice(period);
+
+        Self {
+            period: cells,
+            ..self
+        }
+    }
+
+    /// Sets the width of the smooth minimum that blends the distances to the
+    /// nearby seed points, producing soft, metaball-like blobs.
+    ///
+    /// Instead of the distance to the closest seed point, the range becomes
+    /// `-k * ln(sum(exp(-d / k)))` over the distances `d` to the seed points
+    /// in the surrounding cells, which is continuous and has a continuous
+    /// derivative across the cell boundaries. The blended range is slightly
+    /// smaller than the closest distance, so the output can dip below -1.0.
+    /// Larger widths round the cells off more. A width of 0.0, the default,
+    /// produces the standard hard-edged cells. Smoothing only applies to a
+    /// return rank of 0, and is slower than the default as it searches more
+    /// cells.
+    pub fn set_smoothing(self, smoothing: f64) -> Self {
+        assert!(smoothing >= 0.0);
+
+        Self { smoothing, ..self }
+    }
+
+    /// Sets how many cells around the input point are searched for the
+    /// closest seed point.
+    ///
+    /// With a jitter of at most 1.0, each seed point stays within half a cell
+    /// of its lattice point, so the closest one lies within the default radius
+    /// of 1. The default only checks the few cells most likely to hold it,
+    /// though, and can settle for a slightly farther seed point, as described
+    /// on `Worley`. Larger jitters move seed points further away, so the
+    /// closest one can be missed entirely, which shows up as discontinuities
+    /// in the output. A radius of 2 covers jitters of up to 2.0, and so on.
+    /// Radii above 1 search every cell within the radius exhaustively, which
+    /// is exact but costs about `(2 * radius + 3)^N` distance calculations for
+    /// N dimensions, rather than the handful of the default.
+    pub fn set_search_radius(self, search_radius: usize) -> Self {
+        assert!(search_radius > 0, "search radius must be positive");
+
+        Self {
+            search_radius,
+            ..self
+        }
+    }
+
+    /// Number of rings of cells searched by the exhaustive search. Two rings
+    /// are exact for the first few ranks at the default search radius.
+    fn rings(&self) -> usize {
+        self.search_radius + 1
+    }
+
+    fn is_smoothed(&self) -> bool {
+        self.smoothing > 0.0 && self.return_rank == 0
+    }
+
+    fn is_stretched(&self, dimensions: usize) -> bool {
+        self.stretch[..dimensions]
+            .iter()
+            .any(|&factor| (factor - 1.0).abs() > core::f64::EPSILON)
+    }
+}
+
+impl Worley {
+    fn wrap(&self, axis: usize, x: isize) -> isize {
+        let period = self.period[axis] as isize;
+
+        if period == 0 {
+            x
+        } else {
+            ((x % period) + period) % period
+        }
+    }
+
+    fn hash2(&self, cell: [isize; 2]) -> usize {
+        self.perm_table
+            .get2([self.wrap(0, cell[0]), self.wrap(1, cell[1])])
+    }
+
+    fn hash3(&self, cell: [isize; 3]) -> usize {
+        self.perm_table.get3([
+            self.wrap(0, cell[0]),
+            self.wrap(1, cell[1]),
+            self.wrap(2, cell[2]),
+        ])
+    }
+
+    fn hash4(&self, cell: [isize; 4]) -> usize {
+        self.perm_table.get4([
+            self.wrap(0, cell[0]),
+            self.wrap(1, cell[1]),
+            self.wrap(2, cell[2]),
+            self.wrap(3, cell[3]),
+        ])
+    }
+
+    fn make_sample(&self, range: f64, cell_hash: usize) -> WorleySample {
+        WorleySample {
+            distance: range * 2.0 - 1.0,
+            value: (self.displacement * cell_hash as f64 / 255.0) * 2.0 - 1.0,
+            cell_id: cell_hash,
+        }
+    }
+}
+
+/// Output of a single Worley evaluation, holding both of the values that the
+/// noise function can output.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct WorleySample {
+    /// Distance to the selected seed point, as output when the range is
+    /// enabled.
+    pub distance: f64,
+
+    /// Random value of the cell of the selected seed point, as output when the
+    /// range is disabled. This value is constant within each cell, so it can
+    /// serve as a cell identifier.
+    pub value: f64,
+
+    /// Hash of the cell of the selected seed point, between 0 and 255. Cells
+    /// sharing a hash share their value.
+    pub cell_id: usize,
+}
+
+impl WorleySample {
+    fn output(self, enable_range: bool) -> f64 {
+        if enable_range {
+            self.distance
+        } else {
+            self.value
+        }
+    }
 }
 
 impl Default for Worley {
@@ -113,6 +357,13 @@ pub enum RangeFunction {
 
     /// Same as Euclidean, but without the square root calculations. Distance
     /// results will be smaller, however, but hash patterns will be the same.
+    ///
+    /// Squaring preserves the order of the distances, so the cells are
+    /// identical to the Euclidean ones and this is the faster choice when
+    /// only the cell values are used. The distances follow a different
+    /// distribution, though: they stay flat around the seed points and rise
+    /// steeply toward the cell boundaries, so the range output isn't a drop-in
+    /// replacement for the Euclidean one.
     EuclideanSquared,
 
     /// Measured by only moving in straight lines along the axes. Diagonal
@@ -170,7 +421,7 @@ fn range_chebyshev(p1: &[f64], p2: &[f64]) -> f64 {
         .zip(p2.iter())
         .map(|(a, b)| *a - *b)
         .map(|a| a.abs())
-        .fold(std::f64::MIN, |a, b| a.max(b))
+        .fold(core::f64::MIN, |a, b| a.max(b))
 }
 
 fn range_quadratic(p1: &[f64], p2: &[f64]) -> f64 {
@@ -187,14 +438,269 @@ fn range_quadratic(p1: &[f64], p2: &[f64]) -> f64 {
     result
 }
 
-impl NoiseFn<[f64; 2]> for Worley {
-    fn get(&self, point: [f64; 2]) -> f64 {
-        fn get_point(perm_table: &PermutationTable, whole: [isize; 2]) -> [f64; 2] {
-            math::add2(get_vec2(perm_table.get2(whole)), math::to_f64_2(whole))
+/// Calls `visit` with the range from `point` to the seed point of each cell
+/// in the `rings` rings of cells around the cell nearest to `point`, along
+/// with the cell.
+///
+/// Differences along each axis are divided by the matching `stretch` factor
+/// before measuring the range. The search is widened along the axes that are
+/// stretched relative to the least stretched one, as their cells can hold
+/// closer seed points further away.
+fn visit_cells(
+    rings: usize,
+    range_function: RangeFunction,
+    point: &[f64],
+    stretch: &[f64],
+    get_point: &dyn Fn(&[isize]) -> Vec<f64>,
+    visit: &mut dyn FnMut(f64, Vec<isize>),
+) {
+    let min_stretch = stretch.iter().cloned().fold(core::f64::INFINITY, f64::min);
+    let radii: Vec<isize> = stretch
+        .iter()
+        .map(|factor| rings as isize * (factor / min_stretch).ceil() as isize)
+        .collect();
+    let scale = |p: &[f64]| -> Vec<f64> { p.iter().zip(stretch).map(|(x, s)| x / s).collect() };
+
+    let center: Vec<isize> = point.iter().map(|x| math::to_isize(x.round())).collect();
+    let scaled_point = scale(point);
+    let cell_count = radii.iter().map(|r| (2 * r + 1) as usize).product();
+
+    for index in 0..cell_count {
+        let mut rest = index;
+        let cell: Vec<isize> = center
+            .iter()
+            .zip(&radii)
+            .map(|(c, radius)| {
+                let width = (2 * radius + 1) as usize;
+                let offset = (rest % width) as isize - radius;
+                rest /= width;
+                c + offset
+            })
+            .collect();
+
+        let range = calculate_range(range_function, &scaled_point, &scale(&get_point(&cell)));
+
+        visit(range, cell);
+    }
+}
+
+/// Finds the seed point with the given rank by distance from `point`. Returns
+/// the distance to that seed point and the cell it belongs to. See
+/// `visit_cells` for the cells that are searched.
+fn nth_closest(
+    rank: usize,
+    rings: usize,
+    range_function: RangeFunction,
+    point: &[f64],
+    stretch: &[f64],
+    get_point: &dyn Fn(&[isize]) -> Vec<f64>,
+) -> (f64, Vec<isize>) {
+    let mut closest: Vec<(f64, Vec<isize>)> = Vec::with_capacity(rank + 2);
+
+    visit_cells(
+        rings,
+        range_function,
+        point,
+        stretch,
+        get_point,
+        &mut |range, cell| {
+            if closest.len() <= rank || range < closest[rank].0 {
+                let position = closest
+                    .iter()
+                    .position(|(r, _)| range < *r)
+                    .unwrap_or(closest.len());
+                closest.insert(position, (range, cell));
+                closest.truncate(rank + 1);
+            }
+        },
+    );
+
+    closest.swap_remove(rank)
+}
+
+/// Blends the ranges to the nearby seed points with an exponential smooth
+/// minimum of width `smoothing`. Returns the blended range and the cell of
+/// the closest seed point. See `visit_cells` for the cells that are searched.
+fn smooth_closest(
+    smoothing: f64,
+    rings: usize,
+    range_function: RangeFunction,
+    point: &[f64],
+    stretch: &[f64],
+    get_point: &dyn Fn(&[isize]) -> Vec<f64>,
+) -> (f64, Vec<isize>) {
+    let mut ranges = Vec::new();
+    let mut closest = (core::f64::INFINITY, Vec::new());
+
+    visit_cells(
+        rings,
+        range_function,
+        point,
+        stretch,
+        get_point,
+        &mut |range, cell| {
+            ranges.push(range);
+            if range < closest.0 {
+                closest = (range, cell);
+            }
+        },
+    );
+
+    // Subtracting the minimum keeps the exponentials from underflowing.
+    let min = closest.0;
+    let sum: f64 = ranges
+        .iter()
+        .map(|range| (-(range - min) / smoothing).exp())
+        .sum();
+
+    (min - smoothing * sum.ln(), closest.1)
+}
+
+impl Worley {
+    /// Returns the distance from a 2-dimensional input value to the nearest
+    /// cell boundary, in units of the seed point spacing.
+    ///
+    /// The boundary is the line halfway between the closest and second
+    /// closest seed points, measured with the Euclidean metric regardless of
+    /// the range function. With a stretch applied, the distance is measured in
+    /// the stretched metric.
+    pub fn edge_distance2(&self, point: [f64; 2]) -> f64 {
+        self.nearest_edge2(point).0
+    }
+
+    /// Returns the offset from a 2-dimensional input value to the nearest
+    /// point on the boundary of its cell, in input coordinates.
+    ///
+    /// Adding the offset to the input value moves it onto the boundary
+    /// between the closest and second closest seed points.
+    pub fn edge_vector2(&self, point: [f64; 2]) -> [f64; 2] {
+        let (distance, direction) = self.nearest_edge2(point);
+
+        [
+            direction[0] * distance * self.stretch[0] / self.frequency,
+            direction[1] * distance * self.stretch[1] / self.frequency,
+        ]
+    }
+
+    // Distance to the boundary of the cell, and the unit direction toward it,
+    // in the frequency scaled and stretched space.
+    fn nearest_edge2(&self, point: [f64; 2]) -> (f64, [f64; 2]) {
+        let stretch = [self.stretch[0], self.stretch[1]];
+        let unstretch = |p: [f64; 2]| [p[0] / stretch[0], p[1] / stretch[1]];
+
+        let point = math::mul2(point, self.frequency);
+        let get_point = |cell: &[isize]| {
+            let whole = [cell[0], cell[1]];
+            let offset = math::mul2(get_vec2(self.hash2(whole)), self.jitter);
+            math::add2(offset, math::to_f64_2(whole)).to_vec()
+        };
+
+        let closest = |rank| {
+            let (_, cell) = nth_closest(
+                rank,
+                self.rings(),
+                RangeFunction::Euclidean,
+                &point,
+                &stretch,
+                &get_point,
+            );
+            let seed_point = get_point(&cell);
+            unstretch([seed_point[0], seed_point[1]])
+        };
+        let near = closest(0);
+        let far = closest(1);
+        let point = unstretch(point);
+
+        let near_squared = math::dot2(math::sub2(point, near), math::sub2(point, near));
+        let far_squared = math::dot2(math::sub2(point, far), math::sub2(point, far));
+        let separation = math::sub2(far, near);
+        let length = math::dot2(separation, separation).sqrt();
+
+        (
+            (far_squared - near_squared) / (2.0 * length),
+            math::mul2(separation, 1.0 / length),
+        )
+    }
+
+    /// Returns the position of a 2-dimensional input value relative to the
+    /// seed point of its cell, in units of the seed point spacing.
+    ///
+    /// The seed point is at (0, 0), and the coordinates change continuously
+    /// within a cell and jump at its boundaries, so they can serve as texture
+    /// coordinates for mapping an image into each cell. With the default
+    /// jitter, the coordinates lie roughly within [-1,1]; scale them by 0.5
+    /// and add 0.5 to map them onto a unit texture. The cell is the one
+    /// selected by the return rank, but the coordinates are not stretched.
+    pub fn local_coords2(&self, point: [f64; 2]) -> [f64; 2] {
+        let point = math::mul2(point, self.frequency);
+        let get_point = |cell: &[isize]| {
+            let whole = [cell[0], cell[1]];
+            let offset = math::mul2(get_vec2(self.hash2(whole)), self.jitter);
+            math::add2(offset, math::to_f64_2(whole)).to_vec()
+        };
+
+        let (_, cell) = nth_closest(
+            self.return_rank,
+            self.rings(),
+            self.range_function,
+            &point,
+            &self.stretch[..2],
+            &get_point,
+        );
+        let seed_point = get_point(&cell);
+
+        [point[0] - seed_point[0], point[1] - seed_point[1]]
+    }
+
+    /// Returns 1.0 if a 2-dimensional input value lies within `width` of a
+    /// cell boundary and -1.0 otherwise, tracing thin lines along the cell
+    /// edges. See `edge_distance2` for the units of `width`.
+    pub fn edge_mask2(&self, point: [f64; 2], width: f64) -> f64 {
+        if self.edge_distance2(point) < width {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+impl Worley {
+    /// Calculates both the distance to the closest seed point and the random
+    /// value of its cell for a 2-dimensional input value.
+    pub fn sample2(&self, point: [f64; 2]) -> WorleySample {
+        fn get_point(worley: &Worley, whole: [isize; 2]) -> [f64; 2] {
+            let offset = math::mul2(get_vec2(worley.hash2(whole)), worley.jitter);
+            math::add2(offset, math::to_f64_2(whole))
         }
 
         let point = &math::mul2(point, self.frequency);
 
+        if self.is_smoothed() {
+            let (range, cell) = smooth_closest(
+                self.smoothing,
+                self.rings(),
+                self.range_function,
+                point,
+                &self.stretch[..2],
+                &|cell| get_point(self, [cell[0], cell[1]]).to_vec(),
+            );
+
+            return self.make_sample(range, self.hash2([cell[0], cell[1]]));
+        }
+
+        if self.return_rank > 0 || self.search_radius > 1 || self.is_stretched(2) {
+            let (range, cell) = nth_closest(
+                self.return_rank,
+                self.rings(),
+                self.range_function,
+                point,
+                &self.stretch[..2],
+                &|cell| get_point(self, [cell[0], cell[1]]).to_vec(),
+            );
+
+            return self.make_sample(range, self.hash2([cell[0], cell[1]]));
+        }
+
         let cell = math::map2(*point, f64::floor);
         let whole = math::to_isize2(cell);
         let frac = math::sub2(*point, cell);
@@ -206,7 +712,7 @@ impl NoiseFn<[f64; 2]> for Worley {
         let far = [whole[0] + (!x_half as isize), whole[1] + (!y_half as isize)];
 
         let mut seed_cell = near;
-        let seed_point = get_point(&self.perm_table, near);
+        let seed_point = get_point(self, near);
         let mut range = calculate_range(self.range_function, point, &seed_point);
 
         let x_range = (0.5 - frac[0]) * (0.5 - frac[0]); // x-distance squared to center line
@@ -215,7 +721,7 @@ impl NoiseFn<[f64; 2]> for Worley {
         macro_rules! test_point(
             [$x:expr, $y:expr] => {
                 {
-                    let cur_point = get_point(&self.perm_table, [$x, $y]);
+                    let cur_point = get_point(self, [$x, $y]);
                     let cur_range = calculate_range(self.range_function, point, &cur_point);
                     if cur_range < range {
                         range = cur_range;
@@ -237,20 +743,24 @@ impl NoiseFn<[f64; 2]> for Worley {
             test_point![far[0], far[1]];
         }
 
-        let value = if self.enable_range {
-            range
-        } else {
-            self.displacement * self.perm_table.get2(seed_cell) as f64 / 255.0
-        };
+        self.make_sample(range, self.hash2(seed_cell))
+    }
+}
+
+impl NoiseFn<[f64; 2]> for Worley {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.sample2(point).output(self.enable_range)
+    }
 
-        value * 2.0 - 1.0
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Worley")
     }
 }
 
 #[rustfmt::skip]
 fn get_vec2(index: usize) -> [f64; 2] {
     let length = ((index & 0xF8) >> 3) as f64 * 0.5 / 31.0;
-    let diag = length * std::f64::consts::FRAC_1_SQRT_2;
+    let diag = length * core::f64::consts::FRAC_1_SQRT_2;
 
     match index & 0x07 {
         0 => [   diag,    diag],
@@ -265,14 +775,43 @@ fn get_vec2(index: usize) -> [f64; 2] {
     }
 }
 
-impl NoiseFn<[f64; 3]> for Worley {
-    fn get(&self, point: [f64; 3]) -> f64 {
-        fn get_point(perm_table: &PermutationTable, whole: [isize; 3]) -> [f64; 3] {
-            math::add3(get_vec3(perm_table.get3(whole)), math::to_f64_3(whole))
+impl Worley {
+    /// Calculates both the distance to the closest seed point and the random
+    /// value of its cell for a 3-dimensional input value.
+    pub fn sample3(&self, point: [f64; 3]) -> WorleySample {
+        fn get_point(worley: &Worley, whole: [isize; 3]) -> [f64; 3] {
+            let offset = math::mul3(get_vec3(worley.hash3(whole)), worley.jitter);
+            math::add3(offset, math::to_f64_3(whole))
         }
 
         let point = &math::mul3(point, self.frequency);
 
+        if self.is_smoothed() {
+            let (range, cell) = smooth_closest(
+                self.smoothing,
+                self.rings(),
+                self.range_function,
+                point,
+                &self.stretch[..3],
+                &|cell| get_point(self, [cell[0], cell[1], cell[2]]).to_vec(),
+            );
+
+            return self.make_sample(range, self.hash3([cell[0], cell[1], cell[2]]));
+        }
+
+        if self.return_rank > 0 || self.search_radius > 1 || self.is_stretched(3) {
+            let (range, cell) = nth_closest(
+                self.return_rank,
+                self.rings(),
+                self.range_function,
+                point,
+                &self.stretch[..3],
+                &|cell| get_point(self, [cell[0], cell[1], cell[2]]).to_vec(),
+            );
+
+            return self.make_sample(range, self.hash3([cell[0], cell[1], cell[2]]));
+        }
+
         let cell = math::map3(*point, f64::floor);
         let whole = math::to_isize3(cell);
         let frac = math::sub3(*point, cell);
@@ -293,7 +832,7 @@ impl NoiseFn<[f64; 3]> for Worley {
         ];
 
         let mut seed_cell = near;
-        let seed_point = get_point(&self.perm_table, near);
+        let seed_point = get_point(self, near);
         let mut range = calculate_range(self.range_function, point, &seed_point);
 
         let x_range = (0.5 - frac[0]) * (0.5 - frac[0]); // x-distance squared to center line
@@ -303,7 +842,7 @@ impl NoiseFn<[f64; 3]> for Worley {
         macro_rules! test_point(
             [$x:expr, $y:expr, $z:expr] => {
                 {
-                    let cur_point = get_point(&self.perm_table, [$x, $y, $z]);
+                    let cur_point = get_point(self, [$x, $y, $z]);
                     let cur_range = calculate_range(self.range_function, point, &cur_point);
                     if cur_range < range {
                         range = cur_range;
@@ -337,20 +876,24 @@ impl NoiseFn<[f64; 3]> for Worley {
             test_point![far[0], far[1], far[2]];
         }
 
-        let value = if self.enable_range {
-            range
-        } else {
-            self.displacement * self.perm_table.get3(seed_cell) as f64 / 255.0
-        };
+        self.make_sample(range, self.hash3(seed_cell))
+    }
+}
+
+impl NoiseFn<[f64; 3]> for Worley {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.sample3(point).output(self.enable_range)
+    }
 
-        value * 2.0 - 1.0
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Worley")
     }
 }
 
 #[rustfmt::skip]
 fn get_vec3(index: usize) -> [f64; 3] {
     let length = ((index & 0xE0) >> 5) as f64 * 0.5 / 7.0;
-    let diag = length * std::f64::consts::FRAC_1_SQRT_2;
+    let diag = length * core::f64::consts::FRAC_1_SQRT_2;
 
     match index % 18 {
         0  => [   diag,    diag,     0.0],
@@ -375,15 +918,44 @@ fn get_vec3(index: usize) -> [f64; 3] {
     }
 }
 
-#[allow(clippy::cognitive_complexity)]
-impl NoiseFn<[f64; 4]> for Worley {
-    fn get(&self, point: [f64; 4]) -> f64 {
-        fn get_point(perm_table: &PermutationTable, whole: [isize; 4]) -> [f64; 4] {
-            math::add4(get_vec4(perm_table.get4(whole)), math::to_f64_4(whole))
+impl Worley {
+    /// Calculates both the distance to the closest seed point and the random
+    /// value of its cell for a 4-dimensional input value.
+    #[allow(clippy::cognitive_complexity)]
+    pub fn sample4(&self, point: [f64; 4]) -> WorleySample {
+        fn get_point(worley: &Worley, whole: [isize; 4]) -> [f64; 4] {
+            let offset = math::mul4(get_vec4(worley.hash4(whole)), worley.jitter);
+            math::add4(offset, math::to_f64_4(whole))
         }
 
         let point = &math::mul4(point, self.frequency);
 
+        if self.is_smoothed() {
+            let (range, cell) = smooth_closest(
+                self.smoothing,
+                self.rings(),
+                self.range_function,
+                point,
+                &self.stretch,
+                &|cell| get_point(self, [cell[0], cell[1], cell[2], cell[3]]).to_vec(),
+            );
+
+            return self.make_sample(range, self.hash4([cell[0], cell[1], cell[2], cell[3]]));
+        }
+
+        if self.return_rank > 0 || self.search_radius > 1 || self.is_stretched(4) {
+            let (range, cell) = nth_closest(
+                self.return_rank,
+                self.rings(),
+                self.range_function,
+                point,
+                &self.stretch,
+                &|cell| get_point(self, [cell[0], cell[1], cell[2], cell[3]]).to_vec(),
+            );
+
+            return self.make_sample(range, self.hash4([cell[0], cell[1], cell[2], cell[3]]));
+        }
+
         let cell = math::map4(*point, f64::floor);
         let whole = math::to_isize4(cell);
         let frac = math::sub4(*point, cell);
@@ -407,7 +979,7 @@ impl NoiseFn<[f64; 4]> for Worley {
         ];
 
         let mut seed_cell = near;
-        let seed_point = get_point(&self.perm_table, near);
+        let seed_point = get_point(self, near);
         let mut range = calculate_range(self.range_function, point, &seed_point);
 
         let x_range = (0.5 - frac[0]) * (0.5 - frac[0]); // x-distance squared to center line
@@ -418,7 +990,7 @@ impl NoiseFn<[f64; 4]> for Worley {
         macro_rules! test_point(
             [$x:expr, $y:expr, $z:expr, $w:expr] => {
                 {
-                    let cur_point = get_point(&self.perm_table, [$x, $y, $z, $w]);
+                    let cur_point = get_point(self, [$x, $y, $z, $w]);
                     let cur_range = calculate_range(self.range_function, point, &cur_point);
                     if cur_range < range {
                         range = cur_range;
@@ -477,13 +1049,17 @@ impl NoiseFn<[f64; 4]> for Worley {
             test_point![far[0], far[1], far[2], far[3]];
         }
 
-        let value = if self.enable_range {
-            range
-        } else {
-            self.displacement * self.perm_table.get4(seed_cell) as f64 / 255.0
-        };
+        self.make_sample(range, self.hash4(seed_cell))
+    }
+}
+
+impl NoiseFn<[f64; 4]> for Worley {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.sample4(point).output(self.enable_range)
+    }
 
-        value * 2.0 - 1.0
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Worley")
     }
 }
 
@@ -528,3 +1104,348 @@ fn get_vec4(index: usize) -> [f64; 4] {
         _ => panic!("Attempt to access 4D gradient {} of 32", index % 32),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rank_zero_is_closest() {
+        let worley = Worley::new().enable_range(true);
+        let exact = worley.set_search_radius(2);
+        let get_point = |cell: &[isize]| {
+            math::add2(
+                get_vec2(worley.perm_table.get2([cell[0], cell[1]])),
+                math::to_f64_2([cell[0], cell[1]]),
+            )
+            .to_vec()
+        };
+
+        for i in 0..200 {
+            let point = [i as f64 * 0.37 - 30.0, i as f64 * 0.91 - 70.0];
+            let (range, _) = nth_closest(
+                0,
+                2,
+                RangeFunction::Euclidean,
+                &point,
+                &[1.0, 1.0],
+                &get_point,
+            );
+
+            assert!(((exact.get(point) + 1.0) / 2.0 - range).abs() < 1e-12);
+
+            // The default search is approximate, but never reports a seed
+            // point closer than the closest one.
+            assert!((worley.get(point) + 1.0) / 2.0 - range > -1e-12);
+        }
+    }
+
+    #[test]
+    fn rank_one_is_second_closest() {
+        let worley = Worley::new().enable_range(true).set_return_rank(1);
+
+        for i in 0..200 {
+            let point = [i as f64 * 0.37 - 30.0, i as f64 * 0.91 - 70.0];
+
+            // Brute force search over a wide block of cells.
+            let mut ranges = Vec::new();
+            for y in -4..=4 {
+                for x in -4..=4 {
+                    let cell = [point[0].floor() as isize + x, point[1].floor() as isize + y];
+                    let seed_point =
+                        math::add2(get_vec2(worley.perm_table.get2(cell)), math::to_f64_2(cell));
+                    ranges.push(range_euclidean(&point, &seed_point));
+                }
+            }
+            ranges.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            assert!((worley.get(point) - (ranges[1] * 2.0 - 1.0)).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn search_radius_covers_large_jitter() {
+        // The range output changes by at most twice the distance between two
+        // points, unless the closest seed point is missed.
+        let max_slope = |worley: &Worley| {
+            let step = 1e-3;
+            let mut previous = worley.get([-20.0, 0.37]);
+            let mut max: f64 = 0.0;
+            for i in 1..40_000 {
+                let value = worley.get([i as f64 * step - 20.0, 0.37 + i as f64 * step * 0.3]);
+                max = max.max((value - previous).abs() / step);
+                previous = value;
+            }
+            max
+        };
+
+        let near = Worley::new().enable_range(true).set_jitter(1.9);
+        let wide = near.set_search_radius(2);
+
+        assert!(max_slope(&near) > 3.0);
+        assert!(max_slope(&wide) < 2.2);
+        assert!(max_slope(&Worley::new().enable_range(true)) < 2.2);
+    }
+
+    #[test]
+    fn jitter() {
+        // Distance from each point to the nearest lattice point.
+        let grid_range = |point: [f64; 2]| {
+            let nearest = math::map2(point, f64::round);
+            range_euclidean(&point, &nearest)
+        };
+        let points: Vec<_> = (0..100)
+            .map(|i| [i as f64 * 0.37 - 30.0, i as f64 * 0.91 - 70.0])
+            .collect();
+
+        let regular = Worley::new().enable_range(true).set_jitter(0.0);
+        for &point in &points {
+            assert!((regular.get(point) - (grid_range(point) * 2.0 - 1.0)).abs() < 1e-12);
+        }
+
+        let irregular = Worley::new().enable_range(true).set_jitter(1.0);
+        assert!(points
+            .iter()
+            .any(|&point| (irregular.get(point) - (grid_range(point) * 2.0 - 1.0)).abs() > 1e-3));
+    }
+
+    #[test]
+    fn sample_matches_get() {
+        let distance = Worley::new().enable_range(true);
+        let value = Worley::new();
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.37 - 30.0, i as f64 * 0.91 - 70.0];
+            let sample = value.sample2(point);
+
+            assert_eq!(sample.distance, distance.get(point));
+            assert_eq!(sample.value, value.get(point));
+
+            let point = [point[0], point[1], 0.3];
+            let sample = value.sample3(point);
+
+            assert_eq!(sample.distance, distance.get(point));
+            assert_eq!(sample.value, value.get(point));
+        }
+    }
+
+    #[test]
+    fn sample_value_is_constant_within_cells() {
+        let worley = Worley::new().set_jitter(0.0);
+
+        // With no jitter the seed points lie on the lattice, so every point
+        // within a quarter unit of a lattice point belongs to its cell.
+        for &[x, y] in &[[0.0, 0.0], [3.0, -2.0], [-7.0, 5.0]] {
+            let center = worley.sample2([x, y]).value;
+
+            for &[dx, dy] in &[[0.2, 0.1], [-0.1, 0.2], [0.15, -0.2]] {
+                assert_eq!(worley.sample2([x + dx, y + dy]).value, center);
+            }
+        }
+    }
+
+    #[test]
+    fn edge_mask() {
+        // With no jitter the seed points lie on the integer lattice, so the
+        // cell boundaries are the lines halfway between them.
+        let regular = Worley::new().set_jitter(0.0);
+        assert!(regular.edge_distance2([0.5, 0.2]).abs() < 1e-12);
+        assert!((regular.edge_distance2([0.1, 0.1]) - 0.4).abs() < 1e-12);
+        assert_eq!(regular.edge_mask2([0.5, 0.2], 0.05), 1.0);
+        assert_eq!(regular.edge_mask2([0.2, 1.48], 0.05), 1.0);
+        assert_eq!(regular.edge_mask2([0.1, 0.1], 0.05), -1.0);
+
+        let worley = Worley::new();
+        let mut set = 0;
+        for y in 0..100 {
+            for x in 0..100 {
+                let point = [x as f64 * 0.05, y as f64 * 0.05];
+                if worley.edge_mask2(point, 0.02) > 0.0 {
+                    set += 1;
+                }
+            }
+        }
+        assert!(set > 0);
+        assert!(set < 1000);
+
+        // Moving by the edge vector lands on the boundary.
+        let worley = Worley::new().set_frequency(2.0);
+        for i in 0..50 {
+            let point = [i as f64 * 0.37 - 3.0, i as f64 * 0.91];
+            let edge = math::add2(point, worley.edge_vector2(point));
+            assert!(worley.edge_distance2(edge).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn edge_distance_covers_large_jitter() {
+        for &(jitter, search_radius) in &[(1.9, 2), (3.9, 4)] {
+            let worley = Worley::new()
+                .set_jitter(jitter)
+                .set_search_radius(search_radius);
+
+            for i in 0..900 {
+                let point = [(i % 30) as f64 * 0.137 - 2.0, (i / 30) as f64 * 0.191 - 9.0];
+
+                // Collect the seed points of every cell far enough around the
+                // input value that the two closest can't be missed.
+                let whole = math::to_isize2(math::map2(point, f64::floor));
+                let mut seed_points = Vec::new();
+                for y in -7..=7 {
+                    for x in -7..=7 {
+                        let cell = [whole[0] + x, whole[1] + y];
+                        let offset = math::mul2(get_vec2(worley.hash2(cell)), jitter);
+                        seed_points.push(math::add2(offset, math::to_f64_2(cell)));
+                    }
+                }
+                let range = |seed_point: &[f64; 2]| range_euclidean(&point, seed_point);
+                seed_points.sort_by(|a, b| range(a).partial_cmp(&range(b)).unwrap());
+
+                let (near, far) = (seed_points[0], seed_points[1]);
+                let separation = math::sub2(far, near);
+                let expected = (range(&far).powi(2) - range(&near).powi(2))
+                    / (2.0 * math::dot2(separation, separation).sqrt());
+
+                assert!((worley.edge_distance2(point) - expected).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn period_tiles_seamlessly() {
+        let worley = Worley::new()
+            .set_frequency(2.0)
+            .set_period(&[5, 3, 4])
+            .enable_range(true);
+        let size = [5.0 / 2.0, 3.0 / 2.0, 4.0 / 2.0];
+
+        for i in 0..200 {
+            let point = [i as f64 * 0.113 - 4.0, i as f64 * 0.071 + 1.0, 0.37];
+            let value = worley.get(point);
+
+            for &(dx, dy, dz) in &[(1.0, 0.0, 0.0), (0.0, -1.0, 0.0), (2.0, 3.0, 1.0)] {
+                let shifted = [
+                    point[0] + dx * size[0],
+                    point[1] + dy * size[1],
+                    point[2] + dz * size[2],
+                ];
+                assert!((worley.get(shifted) - value).abs() < 1e-9);
+            }
+
+            let point2 = [point[0], point[1]];
+            let shifted2 = [point[0] - size[0], point[1] + size[1]];
+            assert!((worley.get(shifted2) - worley.get(point2)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn smoothing_rounds_cell_boundaries() {
+        let hard = Worley::new().enable_range(true);
+        let smooth = hard.set_smoothing(0.1);
+        let cell_id = |x: f64| hard.sample2([x, 0.71]).cell_id;
+
+        // Bisect for the first cell boundary along the line.
+        let (mut low, mut high) = (0.0, 0.01);
+        while cell_id(high) == cell_id(low) {
+            high += 0.01;
+        }
+        for _ in 0..60 {
+            let middle = (low + high) / 2.0;
+            if cell_id(middle) == cell_id(0.0) {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+
+        // Change in slope across the boundary.
+        let kink = |worley: &Worley| {
+            let (step, offset) = (1e-6, 1e-3);
+            let slope = |x: f64| (worley.get([x + step, 0.71]) - worley.get([x, 0.71])) / step;
+            (slope(low + offset) - slope(low - offset - step)).abs()
+        };
+
+        assert!(kink(&hard) > 0.1);
+        assert!(kink(&smooth) < kink(&hard) * 0.1);
+
+        let unsmoothed = hard.set_smoothing(0.0);
+        for i in 0..50 {
+            let point = [i as f64 * 0.37 - 3.0, i as f64 * 0.91];
+            assert_eq!(unsmoothed.get(point), hard.get(point));
+            assert!(smooth.get(point) <= hard.get(point));
+        }
+    }
+
+    #[test]
+    fn stretch_elongates_cells() {
+        // Number of cell changes along lines in the direction of `step`.
+        let crossings = |worley: &Worley, step: [f64; 2]| {
+            let mut count = 0;
+            for line in 0..20 {
+                let start = [line as f64 * 1.7, line as f64 * -2.3];
+                let mut previous = worley.get(start);
+                for i in 1..400 {
+                    let value = worley.get(math::add2(start, math::mul2(step, i as f64)));
+                    if value != previous {
+                        count += 1;
+                    }
+                    previous = value;
+                }
+            }
+            count
+        };
+
+        let worley = Worley::new();
+        let isotropic = (
+            crossings(&worley, [0.05, 0.0]),
+            crossings(&worley, [0.0, 0.05]),
+        );
+        assert!(isotropic.0 * 2 > isotropic.1 && isotropic.1 * 2 > isotropic.0);
+
+        let stretched = worley.set_stretch(&[2.0]);
+        let (along_x, along_y) = (
+            crossings(&stretched, [0.05, 0.0]),
+            crossings(&stretched, [0.0, 0.05]),
+        );
+        assert!(along_x * 3 < along_y * 2);
+
+        // Stretching every axis equally keeps the shape of the cells but
+        // scales the distances.
+        let uniform = Worley::new().enable_range(true).set_stretch(&[2.0, 2.0]);
+        let distance = Worley::new().enable_range(true);
+        for i in 0..50 {
+            let point = [i as f64 * 0.37 - 3.0, i as f64 * 0.91];
+            let expected = (distance.get(point) + 1.0) / 2.0 / 2.0;
+            assert!(((uniform.get(point) + 1.0) / 2.0 - expected).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn local_coords_reset_at_cell_boundaries() {
+        let worley = Worley::new().set_frequency(2.0);
+        let step = 0.005;
+
+        let mut resets = 0;
+        let mut previous: Option<([f64; 2], usize)> = None;
+        for i in 0..1000 {
+            let point = [i as f64 * step - 2.0, 0.31];
+            let local = worley.local_coords2(point);
+            let cell = worley.sample2(point).cell_id;
+
+            if let Some((previous_local, previous_cell)) = previous {
+                let dx = local[0] - previous_local[0];
+                let dy = local[1] - previous_local[1];
+                let continuous = (dx - step * 2.0).abs() < 1e-9 && dy.abs() < 1e-9;
+
+                // Within a cell the coordinates move with the input value.
+                assert_eq!(continuous, cell == previous_cell, "at {:?}", point);
+                if !continuous {
+                    resets += 1;
+                }
+            }
+            previous = Some((local, cell));
+        }
+
+        assert!(resets > 3);
+    }
+}
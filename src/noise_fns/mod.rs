@@ -1,17 +1,26 @@
 pub use self::cache::*;
 pub use self::combiners::*;
+pub use self::fluent::*;
 pub use self::generators::*;
 pub use self::modifiers::*;
+#[cfg(feature = "std")]
+pub use self::profiled::*;
 pub use self::selectors::*;
 pub use self::transformers::*;
 
 mod cache;
 mod combiners;
+mod fluent;
 mod generators;
 mod modifiers;
+#[cfg(feature = "std")]
+mod profiled;
 mod selectors;
 mod transformers;
 
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+
 /// Base trait for noise functions.
 ///
 /// A noise function is a object that calculates and outputs a value given a
@@ -27,6 +36,26 @@ mod transformers;
 /// * Combining the output values from two noise functions in various ways.
 pub trait NoiseFn<T> {
     fn get(&self, point: T) -> f64;
+
+    /// Describes the structure of the noise function as a tree, for debugging
+    /// deeply composed graphs. See `utils::graph_to_dot` for rendering it.
+    ///
+    /// Functions that build on other functions override this to include
+    /// their sources as children. Functions that don't override it show up as
+    /// a single node named `NoiseFn`.
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("NoiseFn")
+    }
+
+    /// Returns the distances after which the output repeats along the x and
+    /// y axes of the input, or `None` if it does not repeat along both.
+    ///
+    /// Functions that transform the input of their source adjust its period
+    /// accordingly. Functions that don't override this report `None`, even
+    /// if their output happens to repeat.
+    fn is_periodic(&self) -> Option<[f64; 2]> {
+        None
+    }
 }
 
 impl<'a, T, M: NoiseFn<T>> NoiseFn<T> for &'a M {
@@ -34,6 +63,77 @@ impl<'a, T, M: NoiseFn<T>> NoiseFn<T> for &'a M {
     fn get(&self, point: T) -> f64 {
         M::get(*self, point)
     }
+
+    fn describe(&self) -> NodeDesc {
+        M::describe(*self)
+    }
+
+    fn is_periodic(&self) -> Option<[f64; 2]> {
+        M::is_periodic(*self)
+    }
+}
+
+/// Description of a node in a graph of noise functions, as returned by
+/// `NoiseFn::describe`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeDesc {
+    /// Name of the noise function.
+    pub name: String,
+
+    /// Descriptions of the source functions, in order.
+    pub children: Vec<NodeDesc>,
+}
+
+impl NodeDesc {
+    pub fn new<S: Into<String>>(name: S) -> Self {
+        Self {
+            name: name.into(),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn set_children(self, children: Vec<NodeDesc>) -> Self {
+        Self { children, ..self }
+    }
+
+    /// Returns the number of nodes in the tree rooted at this node.
+    pub fn node_count(&self) -> usize {
+        1 + self
+            .children
+            .iter()
+            .map(NodeDesc::node_count)
+            .sum::<usize>()
+    }
+}
+
+/// Extension of `NoiseFn` for points whose dimension is only known at runtime.
+///
+/// This is implemented for every noise function that supports 2, 3 and 4
+/// dimensional input, and is mainly useful for scripting or FFI layers that
+/// pass coordinates around as slices.
+pub trait NoiseFnDyn {
+    /// Calculates the output value for `point`, dispatching to the 2, 3 or 4
+    /// dimensional implementation based on the length of `point`.
+    ///
+    /// Panics if `point` does not have 2, 3 or 4 components.
+    fn get_dyn(&self, point: &[f64]) -> f64;
+}
+
+impl<M> NoiseFnDyn for M
+where
+    M: NoiseFn<[f64; 2]> + NoiseFn<[f64; 3]> + NoiseFn<[f64; 4]> + ?Sized,
+{
+    fn get_dyn(&self, point: &[f64]) -> f64 {
+        match *point {
+            [x, y] => self.get([x, y]),
+            [x, y, z] => self.get([x, y, z]),
+            [x, y, z, w] => self.get([x, y, z, w]),
+            _ => panic!(
+                "noise functions support 2, 3 or 4 dimensional points, got {} dimensions",
+                point.len()
+            ),
+        }
+    }
 }
 
 /// Trait for functions that require a seed before generating their values
@@ -43,4 +143,117 @@ pub trait Seedable {
 
     /// Getter to retrieve the seed from the function
     fn seed(&self) -> u32;
+
+    /// Returns the function reseeded with its current seed plus `offset`,
+    /// wrapping around on overflow.
+    ///
+    /// This allows generating variations of a preset function without
+    /// knowing the seed it was built with.
+    fn with_seed_offset(self, offset: u32) -> Self
+    where
+        Self: Sized,
+    {
+        let seed = self.seed().wrapping_add(offset);
+        self.set_seed(seed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math;
+
+    #[test]
+    fn get_dyn_dispatches_on_length() {
+        let perlin = Perlin::new();
+
+        assert_eq!(perlin.get_dyn(&[1.0, 2.0]), perlin.get([1.0, 2.0]));
+        assert_eq!(
+            perlin.get_dyn(&[0.1, 0.2, 0.3]),
+            perlin.get([0.1, 0.2, 0.3])
+        );
+        assert_eq!(
+            perlin.get_dyn(&[0.1, 0.2, 0.3, 0.4]),
+            perlin.get([0.1, 0.2, 0.3, 0.4])
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn get_dyn_rejects_unsupported_length() {
+        Perlin::new().get_dyn(&[1.0]);
+    }
+
+    // FNV-1a hash of the bits of the output values on a fixed 64x64 grid.
+    fn output_hash(source: &dyn NoiseFn<[f64; 2]>) -> u64 {
+        let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+
+        for y in 0..64 {
+            for x in 0..64 {
+                let value = source.get([x as f64 * 0.173 - 5.0, y as f64 * 0.173 + 2.0]);
+
+                for byte in &value.to_bits().to_le_bytes() {
+                    hash = (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3);
+                }
+            }
+        }
+
+        hash
+    }
+
+    #[test]
+    fn seed_offset_varies_preset() {
+        let preset = || Fbm::new().set_seed(core::u32::MAX - 1).set_octaves(3);
+
+        let first = preset().with_seed_offset(1);
+        let second = preset().with_seed_offset(2);
+        assert_eq!(first.seed(), core::u32::MAX);
+        assert_eq!(second.seed(), 0);
+
+        assert_ne!(output_hash(&first), output_hash(&second));
+        assert_ne!(output_hash(&first), output_hash(&preset()));
+        assert_eq!(
+            output_hash(&preset().with_seed_offset(0)),
+            output_hash(&preset())
+        );
+    }
+
+    #[test]
+    fn output_hashes_are_stable() {
+        // These hashes pin the exact output bits. If they change on some
+        // platform, outputs differ from the reference platform; if they
+        // change everywhere, `ALGORITHM_VERSION` needs to be incremented.
+        let perlin = Perlin::new();
+        let scale_bias = ScaleBias::new(&perlin).set_scale(0.3).set_bias(0.1);
+
+        assert_eq!(output_hash(&perlin), 0x4df2_4ec8_4c79_9a2f);
+        assert_eq!(output_hash(&OpenSimplex::new()), 0x36fa_7628_1926_fdd8);
+        assert_eq!(output_hash(&scale_bias), 0xc6fd_028b_186d_8c01);
+        assert_eq!(output_hash(&Billow::new()), 0x4698_00b0_125b_8b75);
+    }
+
+    #[test]
+    fn extreme_coordinates_saturate() {
+        let huge = core::isize::MAX as f64;
+        let sources: Vec<Box<dyn NoiseFn<[f64; 3]>>> = vec![
+            Box::new(Perlin::new()),
+            Box::new(OpenSimplex::new()),
+            Box::new(SuperSimplex::new()),
+            Box::new(Value::new()),
+            Box::new(Worley::new()),
+            Box::new(Worley::new().set_return_rank(1)),
+        ];
+
+        for source in &sources {
+            for &point in &[[huge, 0.5, -huge], [-huge, huge, 0.3], [0.25, 0.5, huge]] {
+                let value = source.get(point);
+                assert!(value.is_finite());
+
+                // Lattice coordinates saturate, so all coordinates beyond the
+                // limit sample the same lattice point.
+                let doubled = math::map3(point, |x| if x.abs() >= huge { x * 2.0 } else { x });
+                assert_eq!(value, source.get(doubled));
+            }
+        }
+    }
 }
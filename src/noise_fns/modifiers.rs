@@ -1,15 +1,29 @@
 pub use self::abs::*;
+pub use self::bias_gain::*;
 pub use self::clamp::*;
 pub use self::curve::*;
+pub use self::dynamic_clamp::*;
 pub use self::exponent::*;
+pub use self::identity::*;
 pub use self::negate::*;
+pub use self::palette::*;
+pub use self::plateau::*;
+pub use self::remap_range::*;
+pub use self::sanitize::*;
 pub use self::scale_bias::*;
 pub use self::terrace::*;
 
 mod abs;
+mod bias_gain;
 mod clamp;
 mod curve;
+mod dynamic_clamp;
 mod exponent;
+mod identity;
 mod negate;
+mod palette;
+mod plateau;
+mod remap_range;
+mod sanitize;
 mod scale_bias;
 mod terrace;
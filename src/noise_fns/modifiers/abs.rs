@@ -1,4 +1,6 @@
-use crate::noise_fns::NoiseFn;
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
 
 /// Noise function that outputs the absolute value of the output value from the
 /// source function.
@@ -17,4 +19,8 @@ impl<'a, T> NoiseFn<T> for Abs<'a, T> {
     fn get(&self, point: T) -> f64 {
         (self.source.get(point)).abs()
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Abs").set_children(vec![self.source.describe()])
+    }
 }
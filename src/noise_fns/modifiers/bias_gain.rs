@@ -0,0 +1,86 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math::{self, scale_shift},
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that reshapes the output value from the source function
+/// with Perlin's bias and gain functions.
+///
+/// The output value from the source function is first normalized to the
+/// [0,1] range. The bias function is then applied, pushing values towards 0
+/// or 1, followed by the gain function, which steepens or flattens the curve
+/// around the middle of the range. Finally, the value is rescaled back to the
+/// [-1,1] range.
+pub struct BiasGain<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Value that the middle of the range is mapped to by the bias function.
+    /// Default is 0.5, which leaves the value unchanged.
+    pub bias: f64,
+
+    /// Steepness of the gain function around the middle of the range. Values
+    /// above 0.5 increase contrast, values below 0.5 decrease it. Default is
+    /// 0.5, which leaves the value unchanged.
+    pub gain: f64,
+}
+
+impl<'a, T> BiasGain<'a, T> {
+    pub const DEFAULT_BIAS: f64 = 0.5;
+    pub const DEFAULT_GAIN: f64 = 0.5;
+
+    pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            bias: Self::DEFAULT_BIAS,
+            gain: Self::DEFAULT_GAIN,
+        }
+    }
+
+    pub fn set_bias(self, bias: f64) -> Self {
+        Self { bias, ..self }
+    }
+
+    pub fn set_gain(self, gain: f64) -> Self {
+        Self { gain, ..self }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for BiasGain<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        let value = math::clamp((self.source.get(point) + 1.0) / 2.0, 0.0, 1.0);
+        let value = math::gain(self.gain, math::bias(self.bias, value));
+
+        scale_shift(value, 2.0)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("BiasGain").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    #[test]
+    fn defaults_are_identity() {
+        for &v in &[-1.0, -0.3, 0.0, 0.6, 1.0] {
+            let source = Constant::new(v);
+            let bias_gain = BiasGain::new(&source);
+
+            assert!((bias_gain.get([0.0, 0.0]) - v).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn bias_moves_midpoint() {
+        let source = Constant::new(0.0);
+        let bias_gain = BiasGain::new(&source).set_bias(0.75);
+
+        assert!((bias_gain.get([0.0, 0.0]) - 0.5).abs() < 1e-12);
+    }
+}
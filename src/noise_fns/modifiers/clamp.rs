@@ -1,4 +1,9 @@
-use crate::{math, noise_fns::NoiseFn};
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{NodeDesc, NoiseFn},
+};
 
 /// Noise function that clamps the output value from the source function to a
 /// range of values.
@@ -46,4 +51,8 @@ impl<'a, T> NoiseFn<T> for Clamp<'a, T> {
 
         math::clamp(value, self.bounds.0, self.bounds.1)
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Clamp").set_children(vec![self.source.describe()])
+    }
 }
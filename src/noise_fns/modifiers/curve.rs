@@ -1,6 +1,8 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
 use crate::{
     math::{self, interpolate},
-    noise_fns::NoiseFn,
+    noise_fns::{NodeDesc, NoiseFn},
 };
 
 /// Noise function that maps the output value from the source function onto an
@@ -43,7 +45,7 @@ impl<'a, T> Curve<'a, T> {
         if !self
             .control_points
             .iter()
-            .any(|x| (x.input - input_value).abs() < std::f64::EPSILON)
+            .any(|x| (x.input - input_value).abs() < core::f64::EPSILON)
         {
             // it doesn't, so find the correct position to insert the new
             // control point.
@@ -83,6 +85,7 @@ impl<'a, T> NoiseFn<T> for Curve<'a, T> {
             .position(|x| x.input > source_value)
             .unwrap_or_else(|| self.control_points.len());
 
+        #[cfg(feature = "std")]
         if index_pos < 2 {
             println!(
                 "index_pos in curve was less than 2! source value was {}",
@@ -122,4 +125,8 @@ impl<'a, T> NoiseFn<T> for Curve<'a, T> {
             alpha,
         )
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Curve").set_children(vec![self.source.describe()])
+    }
 }
@@ -0,0 +1,82 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that clamps the output value from the source function to a
+/// range given by two other functions.
+///
+/// Unlike `Clamp`, the bounds vary across space, which allows for spatially
+/// varying contrast, such as flattening the peaks of a terrain only in some
+/// regions. Where the lower bound is greater than the upper bound, the bounds
+/// are swapped.
+pub struct DynamicClamp<'a, T> {
+    /// Outputs the value to clamp.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Outputs the lower bound of the clamping range.
+    pub lower: &'a dyn NoiseFn<T>,
+
+    /// Outputs the upper bound of the clamping range.
+    pub upper: &'a dyn NoiseFn<T>,
+}
+
+impl<'a, T> DynamicClamp<'a, T> {
+    pub fn new(
+        source: &'a dyn NoiseFn<T>,
+        lower: &'a dyn NoiseFn<T>,
+        upper: &'a dyn NoiseFn<T>,
+    ) -> Self {
+        Self {
+            source,
+            lower,
+            upper,
+        }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for DynamicClamp<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        let lower = self.lower.get(point);
+        let upper = self.upper.get(point);
+
+        math::clamp(self.source.get(point), lower.min(upper), lower.max(upper))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("DynamicClamp").set_children(vec![
+            self.source.describe(),
+            self.lower.describe(),
+            self.upper.describe(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Clamp, Constant, Perlin};
+
+    #[test]
+    fn constant_bounds_match_clamp() {
+        let perlin = Perlin::new();
+        let lower = Constant::new(-0.2);
+        let upper = Constant::new(0.3);
+
+        let clamp = Clamp::new(&perlin).set_bounds(-0.2, 0.3);
+        let dynamic = DynamicClamp::new(&perlin, &lower, &upper);
+        let swapped = DynamicClamp::new(&perlin, &upper, &lower);
+
+        for i in 0..100 {
+            let point = [i as f64 * 0.173 - 3.0, i as f64 * 0.071];
+
+            assert_eq!(dynamic.get(point), clamp.get(point));
+            assert_eq!(swapped.get(point), clamp.get(point));
+        }
+    }
+}
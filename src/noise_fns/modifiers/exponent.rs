@@ -1,4 +1,9 @@
-use crate::{math::scale_shift, noise_fns::NoiseFn};
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math::scale_shift,
+    noise_fns::{NodeDesc, NoiseFn},
+};
 
 /// Noise function that maps the output value from the source function onto an
 /// exponential curve.
@@ -37,4 +42,8 @@ impl<'a, T> NoiseFn<T> for Exponent<'a, T> {
         value = value.powf(self.exponent);
         scale_shift(value, 2.0)
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Exponent").set_children(vec![self.source.describe()])
+    }
 }
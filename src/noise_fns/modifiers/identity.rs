@@ -0,0 +1,46 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
+
+/// Noise function that outputs the output value from the source function
+/// unchanged.
+///
+/// This is useful as a placeholder while building a noise graph, or to swap
+/// nodes of a graph in and out without restructuring it.
+pub struct Identity<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+}
+
+impl<'a, T> Identity<'a, T> {
+    pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
+        Self { source }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Identity<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        self.source.get(point)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Identity").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Perlin;
+
+    #[test]
+    fn matches_source() {
+        let perlin = Perlin::new();
+        let identity = Identity::new(&perlin);
+
+        for i in 0..20 {
+            let point = [i as f64 * 0.37 - 3.0, i as f64 * 0.91, 0.4];
+            assert_eq!(identity.get(point), perlin.get(point));
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::noise_fns::NoiseFn;
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
 
 /// Noise function that negates the output value from the source function.
 pub struct Negate<'a, T> {
@@ -16,4 +18,8 @@ impl<'a, T> NoiseFn<T> for Negate<'a, T> {
     fn get(&self, point: T) -> f64 {
         -self.source.get(point)
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Negate").set_children(vec![self.source.describe()])
+    }
 }
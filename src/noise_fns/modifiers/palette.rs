@@ -0,0 +1,99 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
+
+/// Noise function that maps ranges of the output value from the source
+/// function onto discrete values.
+///
+/// The mapping is defined by a number of _bands_, each with a _threshold_ and
+/// an _output value_. The output is the output value of the band with the
+/// highest threshold that does not exceed the source value. Unlike `Curve`,
+/// the output is not interpolated, which makes this noise function suitable
+/// for assigning biomes or palette indices.
+///
+/// To add bands, use the `add_band` method. Source values below the lowest
+/// threshold map to the output value of the lowest band. If there are no
+/// bands, the get() method panics.
+pub struct Palette<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Bands as (threshold, output value) pairs, sorted by threshold.
+    bands: Vec<(f64, f64)>,
+}
+
+impl<'a, T> Palette<'a, T> {
+    pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            bands: Vec::new(),
+        }
+    }
+
+    /// Adds a band starting at `threshold`. A band with the same threshold as
+    /// an existing one replaces it.
+    pub fn add_band(mut self, threshold: f64, output_value: f64) -> Self {
+        match self
+            .bands
+            .iter()
+            .position(|&(existing, _)| existing >= threshold)
+        {
+            Some(index) if self.bands[index].0 == threshold => {
+                self.bands[index].1 = output_value;
+            }
+            Some(index) => self.bands.insert(index, (threshold, output_value)),
+            None => self.bands.push((threshold, output_value)),
+        }
+
+        self
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Palette<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        assert!(!self.bands.is_empty());
+
+        let value = self.source.get(point);
+
+        self.bands
+            .iter()
+            .rev()
+            .find(|&&(threshold, _)| threshold <= value)
+            .unwrap_or(&self.bands[0])
+            .1
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Palette").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    #[test]
+    fn bands() {
+        let expected = [
+            (-1.5, 10.0),
+            (-1.0, 10.0),
+            (-0.2, 10.0),
+            (0.0, 20.0),
+            (0.3, 20.0),
+            (0.5, 30.0),
+            (1.0, 30.0),
+        ];
+
+        for &(input, output) in &expected {
+            let source = Constant::new(input);
+            let palette = Palette::new(&source)
+                .add_band(0.5, 30.0)
+                .add_band(-1.0, 10.0)
+                .add_band(0.0, 25.0)
+                .add_band(0.0, 20.0);
+
+            assert_eq!(palette.get([0.0, 0.0]), output, "input {}", input);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
+
+/// Noise function that flattens the output value from the source function
+/// above a level, producing mesas and plateaus.
+///
+/// Values at or below `level` are left unchanged. Values above it are
+/// compressed toward it by `level + excess / (1.0 + hardness * excess)`,
+/// where `excess` is the distance above the level. The result rises smoothly
+/// out of the valleys and levels off into a gently varying plateau, unlike
+/// `Clamp`, which cuts the terrain off flat. A hardness of 0.0 leaves the
+/// values unchanged and larger hardness values flatten the plateau further;
+/// no output exceeds `level + 1.0 / hardness`.
+pub struct Plateau<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Level above which the values are compressed. Default is 0.5.
+    pub level: f64,
+
+    /// Strength of the compression. Default is 4.0.
+    pub hardness: f64,
+}
+
+impl<'a, T> Plateau<'a, T> {
+    pub const DEFAULT_LEVEL: f64 = 0.5;
+    pub const DEFAULT_HARDNESS: f64 = 4.0;
+
+    pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            level: Self::DEFAULT_LEVEL,
+            hardness: Self::DEFAULT_HARDNESS,
+        }
+    }
+
+    pub fn set_level(self, level: f64) -> Self {
+        Self { level, ..self }
+    }
+
+    pub fn set_hardness(self, hardness: f64) -> Self {
+        assert!(hardness >= 0.0, "hardness must not be negative");
+
+        Self { hardness, ..self }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Plateau<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        let value = self.source.get(point);
+
+        if value <= self.level {
+            return value;
+        }
+
+        let excess = value - self.level;
+        self.level + excess / (1.0 + self.hardness * excess)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Plateau").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Linear;
+
+    #[test]
+    fn compresses_above_level() {
+        let ramp = Linear::new(1.0, 0.0, 0.0, 0.0);
+        let plateau = Plateau::new(&ramp).set_level(0.2).set_hardness(10.0);
+        let plateau3 = Plateau::new(&ramp).set_level(0.2).set_hardness(10.0);
+
+        for &x in &[-1.0, -0.3, 0.0, 0.2] {
+            assert_eq!(plateau.get([x, 0.0]), x);
+            assert_eq!(plateau3.get([x, 0.0, 0.0]), x);
+        }
+
+        // Far above the level the excess shrinks to a small fraction.
+        let high = plateau.get([5.0, 0.0]);
+        assert!(high > 0.2 && high < 0.3);
+        assert!(plateau.get([1.0, 0.0]) < high);
+
+        // The plateau still varies, unlike a clamp.
+        assert!(high - plateau.get([2.0, 0.0]) > 1e-3);
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math::{self, interpolate},
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that linearly maps the output value from the source
+/// function from one range onto another.
+///
+/// A value at the start of `in_range` maps to the start of `out_range`, and a
+/// value at the end of `in_range` maps to the end of `out_range`. Values
+/// outside of `in_range` are clamped to it first, so the output always lies
+/// within `out_range`. Either range may be reversed to flip the output.
+///
+/// This is the same remapping as `ScaleBias`, but specified by the ranges
+/// instead of the scale and bias they imply.
+pub struct RemapRange<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Range of the values from the source function. Default is (-1.0, 1.0).
+    pub in_range: (f64, f64),
+
+    /// Range the values are mapped onto. Default is (0.0, 1.0).
+    pub out_range: (f64, f64),
+}
+
+impl<'a, T> RemapRange<'a, T> {
+    pub const DEFAULT_IN_RANGE: (f64, f64) = (-1.0, 1.0);
+    pub const DEFAULT_OUT_RANGE: (f64, f64) = (0.0, 1.0);
+
+    pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            in_range: Self::DEFAULT_IN_RANGE,
+            out_range: Self::DEFAULT_OUT_RANGE,
+        }
+    }
+
+    pub fn set_in_range(self, start: f64, end: f64) -> Self {
+        assert!(start != end, "input range must not be empty");
+
+        Self {
+            in_range: (start, end),
+            ..self
+        }
+    }
+
+    pub fn set_out_range(self, start: f64, end: f64) -> Self {
+        Self {
+            out_range: (start, end),
+            ..self
+        }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for RemapRange<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        let (in_start, in_end) = self.in_range;
+        let (out_start, out_end) = self.out_range;
+
+        let alpha = (self.source.get(point) - in_start) / (in_end - in_start);
+
+        interpolate::linear(out_start, out_end, math::clamp(alpha, 0.0, 1.0))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("RemapRange").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Linear;
+
+    #[test]
+    fn maps_onto_output_range() {
+        let ramp = Linear::new(1.0, 0.0, 0.0, 0.0);
+        let remap = RemapRange::new(&ramp).set_out_range(0.0, 255.0);
+
+        assert_eq!(remap.get([-1.0, 0.0]), 0.0);
+        assert_eq!(remap.get([0.0, 0.0]), 127.5);
+        assert_eq!(remap.get([1.0, 0.0]), 255.0);
+        assert_eq!(remap.get([-3.0, 0.0]), 0.0);
+        assert_eq!(remap.get([2.0, 0.0]), 255.0);
+
+        let flipped = remap.set_in_range(1.0, -1.0);
+        assert_eq!(flipped.get([1.0, 0.0]), 0.0);
+        assert_eq!(flipped.get([-0.5, 0.0]), 191.25);
+    }
+}
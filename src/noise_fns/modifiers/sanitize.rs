@@ -0,0 +1,76 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
+
+/// Noise function that replaces non-finite output values from the source
+/// function with a fallback value.
+///
+/// A single operation producing NaN or an infinity, such as a `Power` with a
+/// negative base, can propagate through a long chain of functions and ruin a
+/// whole map. Placing this function after such operations keeps the damage
+/// local. Finite values pass through unchanged.
+pub struct Sanitize<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Value output in place of NaN and infinite values. Default is 0.0.
+    pub fallback: f64,
+}
+
+impl<'a, T> Sanitize<'a, T> {
+    pub const DEFAULT_FALLBACK: f64 = 0.0;
+
+    pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            fallback: Self::DEFAULT_FALLBACK,
+        }
+    }
+
+    pub fn set_fallback(self, fallback: f64) -> Self {
+        Self { fallback, ..self }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Sanitize<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        let value = self.source.get(point);
+
+        if value.is_finite() {
+            value
+        } else {
+            self.fallback
+        }
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Sanitize").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Constant, Perlin};
+
+    #[test]
+    fn replaces_non_finite_values() {
+        let nan = Constant::new(core::f64::NAN);
+        let infinity = Constant::new(core::f64::NEG_INFINITY);
+        let perlin = Perlin::new();
+
+        assert_eq!(Sanitize::new(&nan).get([0.5, 1.5]), 0.0);
+        assert_eq!(
+            Sanitize::new(&infinity)
+                .set_fallback(-1.0)
+                .get([0.5, 1.5, 2.5]),
+            -1.0
+        );
+
+        let sanitized = Sanitize::new(&perlin).set_fallback(0.75);
+        for i in 0..50 {
+            let point = [i as f64 * 0.37 - 3.0, i as f64 * 0.23];
+            assert_eq!(sanitized.get(point), perlin.get(point));
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::noise_fns::NoiseFn;
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
 
 /// Noise function that applies a scaling factor and a bias to the output value
 /// from the source function.
@@ -16,6 +18,10 @@ pub struct ScaleBias<'a, T> {
     /// Bias to apply to the scaled output value from the source function.
     /// The default value is 0.0.
     pub bias: f64,
+
+    /// Whether the scale and bias are applied in the [0,1] range instead of
+    /// the [-1,1] range. The default value is false.
+    pub normalized_space: bool,
 }
 
 impl<'a, T> ScaleBias<'a, T> {
@@ -24,6 +30,7 @@ impl<'a, T> ScaleBias<'a, T> {
             source,
             scale: 1.0,
             bias: 0.0,
+            normalized_space: false,
         }
     }
 
@@ -34,16 +41,60 @@ impl<'a, T> ScaleBias<'a, T> {
     pub fn set_bias(self, bias: f64) -> Self {
         Self { bias, ..self }
     }
+
+    /// Sets whether the scale and bias are applied in the [0,1] range.
+    ///
+    /// When enabled, the output value from the source function is mapped from
+    /// [-1,1] onto [0,1], scaled and biased, then mapped back onto [-1,1], the
+    /// same way `Exponent` normalizes its input. This makes it easier to
+    /// think in terms of positions on a color ramp: a bias of 0.25 shifts the
+    /// output a quarter of the way along the ramp, and a scale of 0.5
+    /// squeezes the output into the lower half of it.
+    pub fn set_normalized_space(self, normalized_space: bool) -> Self {
+        Self {
+            normalized_space,
+            ..self
+        }
+    }
 }
 
 impl<'a, T> NoiseFn<T> for ScaleBias<'a, T> {
-    #[cfg(not(target_os = "emscripten"))]
     fn get(&self, point: T) -> f64 {
-        (self.source.get(point)).mul_add(self.scale, self.bias)
+        let value = self.source.get(point);
+
+        if self.normalized_space {
+            let normalized = value * 0.5 + 0.5;
+            normalized.mul_add(self.scale, self.bias) * 2.0 - 1.0
+        } else {
+            value.mul_add(self.scale, self.bias)
+        }
     }
 
-    #[cfg(target_os = "emscripten")]
-    fn get(&self, point: T) -> f64 {
-        (self.source.get(point) * self.scale) + self.bias
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("ScaleBias").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    #[test]
+    fn normalized_space() {
+        let source = Constant::new(0.5);
+        let raw = ScaleBias::new(&source).set_scale(0.5).set_bias(0.25);
+        let normalized = ScaleBias::new(&source)
+            .set_scale(0.5)
+            .set_bias(0.25)
+            .set_normalized_space(true);
+
+        assert_eq!(raw.get([0.0, 0.0]), 0.5);
+        // 0.5 is 0.75 in [0,1], which scales and biases to 0.625, or 0.25 in
+        // [-1,1].
+        assert_eq!(normalized.get([0.0, 0.0]), 0.25);
+
+        let identity = ScaleBias::new(&source).set_normalized_space(true);
+        assert_eq!(identity.get([0.0, 0.0]), 0.5);
     }
 }
@@ -1,6 +1,8 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
 use crate::{
     math::{clamp, interpolate},
-    noise_fns::NoiseFn,
+    noise_fns::{NodeDesc, NoiseFn},
 };
 
 /// Noise function that maps the output value from the source function onto a
@@ -59,7 +61,7 @@ impl<'a, T> Terrace<'a, T> {
         if !self
             .control_points
             .iter()
-            .any(|&x| (x - control_point).abs() < std::f64::EPSILON)
+            .any(|&x| (x - control_point).abs() < core::f64::EPSILON)
         {
             // it doesn't, so find the correct position to insert the new
             // control point.
@@ -123,7 +125,7 @@ impl<'a, T> NoiseFn<T> for Terrace<'a, T> {
 
         if self.invert_terraces {
             alpha = 1.0 - alpha;
-            std::mem::swap(&mut input0, &mut input1);
+            core::mem::swap(&mut input0, &mut input1);
         }
 
         // Squaring the alpha produces the terrace effect.
@@ -132,6 +134,10 @@ impl<'a, T> NoiseFn<T> for Terrace<'a, T> {
         // Now perform the cubic interpolation and return.
         interpolate::linear(input0, input1, alpha)
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Terrace").set_children(vec![self.source.describe()])
+    }
 }
 
 fn clamp_index(index: isize, min: usize, max: usize) -> usize {
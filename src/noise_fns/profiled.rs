@@ -0,0 +1,134 @@
+use crate::noise_fns::{NodeDesc, NoiseFn};
+use std::{
+    cell::RefCell,
+    collections::BTreeMap,
+    time::{Duration, Instant},
+};
+
+/// Accumulated timing of all `Profiled` noise functions sharing a label.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfileEntry {
+    pub label: String,
+
+    /// Number of calls to `get`.
+    pub calls: u64,
+
+    /// Total time spent in `get`, including the time spent in the sources of
+    /// the wrapped function.
+    pub total: Duration,
+}
+
+/// Collects the timings recorded by `Profiled` noise functions.
+///
+/// A single profiler is usually shared by every `Profiled` function in a
+/// noise graph, so the report shows which nodes dominate the runtime.
+#[derive(Debug, Default)]
+pub struct Profiler {
+    entries: RefCell<BTreeMap<String, (u64, Duration)>>,
+}
+
+impl Profiler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the accumulated timings, one entry per label, sorted by label.
+    pub fn profiling_report(&self) -> Vec<ProfileEntry> {
+        self.entries
+            .borrow()
+            .iter()
+            .map(|(label, &(calls, total))| ProfileEntry {
+                label: label.clone(),
+                calls,
+                total,
+            })
+            .collect()
+    }
+
+    /// Discards all recorded timings.
+    pub fn reset(&self) {
+        self.entries.borrow_mut().clear();
+    }
+
+    fn record(&self, label: &str, elapsed: Duration) {
+        let mut entries = self.entries.borrow_mut();
+
+        if let Some(entry) = entries.get_mut(label) {
+            entry.0 += 1;
+            entry.1 += elapsed;
+        } else {
+            entries.insert(label.to_owned(), (1, elapsed));
+        }
+    }
+}
+
+/// Noise function that measures the time spent in the source function.
+///
+/// Every call to `get` is timed and added to the entry for `label` in the
+/// shared `Profiler`. Timing each call has a small overhead of its own, so
+/// this is meant for temporarily instrumenting a noise graph while optimizing
+/// it.
+pub struct Profiled<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Name under which the timings are recorded.
+    pub label: &'a str,
+
+    profiler: &'a Profiler,
+}
+
+impl<'a, T> Profiled<'a, T> {
+    pub fn new(source: &'a dyn NoiseFn<T>, label: &'a str, profiler: &'a Profiler) -> Self {
+        Self {
+            source,
+            label,
+            profiler,
+        }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Profiled<'a, T> {
+    fn get(&self, point: T) -> f64 {
+        let start = Instant::now();
+        let value = self.source.get(point);
+        self.profiler.record(self.label, start.elapsed());
+
+        value
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Profiled").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Abs, Perlin};
+
+    #[test]
+    fn records_calls_per_label() {
+        let profiler = Profiler::new();
+        let perlin = Perlin::new();
+        let source = Profiled::new(&perlin, "perlin", &profiler);
+        let abs = Abs::new(&source);
+        let output = Profiled::new(&abs, "abs", &profiler);
+
+        for i in 0..10 {
+            let point = [i as f64 * 0.37, 1.5];
+            assert_eq!(output.get(point), perlin.get(point).abs());
+        }
+
+        let report = profiler.profiling_report();
+        assert_eq!(report.len(), 2);
+        assert_eq!((report[0].label.as_str(), report[0].calls), ("abs", 10));
+        assert_eq!((report[1].label.as_str(), report[1].calls), ("perlin", 10));
+
+        // The outer node includes the time spent in its source.
+        assert!(report[0].total >= report[1].total);
+
+        profiler.reset();
+        assert!(profiler.profiling_report().is_empty());
+    }
+}
@@ -1,5 +1,15 @@
 pub use self::blend::*;
+pub use self::mask::*;
+pub use self::multi_select::*;
+pub use self::region_fill::*;
 pub use self::select::*;
+pub use self::slope_select::*;
+pub use self::weighted_blend::*;
 
 mod blend;
+mod mask;
+mod multi_select;
+mod region_fill;
 mod select;
+mod slope_select;
+mod weighted_blend;
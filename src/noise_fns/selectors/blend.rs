@@ -1,4 +1,9 @@
-use crate::{math::interpolate, noise_fns::NoiseFn};
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math::interpolate,
+    noise_fns::{NodeDesc, NoiseFn},
+};
 
 /// Noise function that outputs a weighted blend of the output values from two
 /// source functions given the output value supplied by a control function.
@@ -44,4 +49,12 @@ where
 
         interpolate::linear(lower, upper, control)
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Blend").set_children(vec![
+            self.source1.describe(),
+            self.source2.describe(),
+            self.control.describe(),
+        ])
+    }
 }
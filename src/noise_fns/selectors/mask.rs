@@ -0,0 +1,123 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math::{self, interpolate},
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that outputs the value from a source function where a
+/// control function exceeds a threshold, and a constant background value
+/// elsewhere.
+///
+/// This is a simpler alternative to `Select` for the common case of cutting
+/// a source function out along a threshold.
+pub struct Mask<'a, T> {
+    /// Outputs a value.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Determines where the source function is shown. If the output value
+    /// from the control function is above the threshold, this noise function
+    /// outputs the value from `source`. Otherwise, it outputs the background
+    /// value.
+    pub control: &'a dyn NoiseFn<T>,
+
+    /// Threshold the control value is compared against. Default is 0.0.
+    pub threshold: f64,
+
+    /// Value output where the control value is at or below the threshold.
+    /// Default is 0.0.
+    pub background: f64,
+
+    /// Edge falloff value. Within `falloff` of the threshold, the output
+    /// blends smoothly between the background and the source. Default is 0.0.
+    pub falloff: f64,
+}
+
+impl<'a, T> Mask<'a, T> {
+    pub const DEFAULT_THRESHOLD: f64 = 0.0;
+    pub const DEFAULT_BACKGROUND: f64 = 0.0;
+    pub const DEFAULT_FALLOFF: f64 = 0.0;
+
+    pub fn new(source: &'a dyn NoiseFn<T>, control: &'a dyn NoiseFn<T>) -> Self {
+        Mask {
+            source,
+            control,
+            threshold: Self::DEFAULT_THRESHOLD,
+            background: Self::DEFAULT_BACKGROUND,
+            falloff: Self::DEFAULT_FALLOFF,
+        }
+    }
+
+    pub fn set_threshold(self, threshold: f64) -> Self {
+        Mask { threshold, ..self }
+    }
+
+    pub fn set_background(self, background: f64) -> Self {
+        Mask { background, ..self }
+    }
+
+    pub fn set_falloff(self, falloff: f64) -> Self {
+        Mask { falloff, ..self }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for Mask<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        let control_value = self.control.get(point);
+
+        if self.falloff > 0.0 {
+            let lower = self.threshold - self.falloff;
+            let alpha = math::clamp((control_value - lower) / (2.0 * self.falloff), 0.0, 1.0);
+
+            if alpha == 0.0 {
+                self.background
+            } else if alpha == 1.0 {
+                self.source.get(point)
+            } else {
+                let alpha = interpolate::s_curve3(alpha);
+
+                interpolate::linear(self.background, self.source.get(point), alpha)
+            }
+        } else if control_value > self.threshold {
+            self.source.get(point)
+        } else {
+            self.background
+        }
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Mask").set_children(vec![self.source.describe(), self.control.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Linear, Perlin};
+
+    #[test]
+    fn shows_source_above_threshold() {
+        let perlin = Perlin::new();
+        let control = Linear::new(1.0, 0.0, 0.0, 0.0);
+        let mask = Mask::new(&perlin, &control)
+            .set_threshold(0.5)
+            .set_background(-1.0);
+
+        for i in 0..20 {
+            let y = i as f64 * 0.37;
+
+            assert_eq!(mask.get([1.5, y]), perlin.get([1.5, y]));
+            assert_eq!(mask.get([-0.5, y]), -1.0);
+            assert_eq!(mask.get([0.5, y]), -1.0);
+        }
+
+        let soft = mask.set_falloff(0.25);
+        let edge = soft.get([0.5, 0.3]);
+        assert!((edge - (perlin.get([0.5, 0.3]) - 1.0) / 2.0).abs() < 1e-12);
+        assert_eq!(soft.get([0.2, 0.3]), -1.0);
+        assert_eq!(soft.get([0.8, 0.3]), perlin.get([0.8, 0.3]));
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math::interpolate,
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that outputs the value selected from one of several source
+/// functions chosen by the output value from a control function.
+///
+/// The thresholds split the range of the control value into bands, one per
+/// source function. If the control value is below the first threshold, the
+/// value from the first source is output; if it lies between the first and
+/// the second threshold, the value from the second source is output; and so
+/// on.
+pub struct MultiSelect<'a, T> {
+    /// Outputs the values to select from, in order of increasing control
+    /// value.
+    pub sources: Vec<&'a dyn NoiseFn<T>>,
+
+    /// Determines the source to select.
+    pub control: &'a dyn NoiseFn<T>,
+
+    /// Boundaries between the bands of successive sources, in increasing
+    /// order. There is one threshold fewer than there are sources.
+    pub thresholds: Vec<f64>,
+
+    /// Edge falloff value applied on either side of each threshold. Default is
+    /// 0.0.
+    pub falloff: f64,
+}
+
+impl<'a, T> MultiSelect<'a, T> {
+    /// Creates a new `MultiSelect` from `sources` and the `thresholds`
+    /// separating them.
+    ///
+    /// Panics if there is not exactly one threshold fewer than there are
+    /// sources, or if the thresholds are not in increasing order.
+    pub fn new(
+        sources: Vec<&'a dyn NoiseFn<T>>,
+        control: &'a dyn NoiseFn<T>,
+        thresholds: Vec<f64>,
+    ) -> Self {
+        assert!(!sources.is_empty(), "at least one source is required");
+        assert_eq!(
+            thresholds.len() + 1,
+            sources.len(),
+            "there must be one threshold fewer than there are sources"
+        );
+        assert!(
+            thresholds.windows(2).all(|pair| pair[0] <= pair[1]),
+            "thresholds must be in increasing order"
+        );
+
+        MultiSelect {
+            sources,
+            control,
+            thresholds,
+            falloff: 0.0,
+        }
+    }
+
+    pub fn set_falloff(self, falloff: f64) -> Self {
+        MultiSelect { falloff, ..self }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for MultiSelect<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        let control_value = self.control.get(point);
+
+        if self.falloff > 0.0 {
+            let boundary = self
+                .thresholds
+                .iter()
+                .position(|&threshold| (control_value - threshold).abs() < self.falloff);
+
+            if let Some(index) = boundary {
+                let lower_curve = self.thresholds[index] - self.falloff;
+                let alpha =
+                    interpolate::s_curve3((control_value - lower_curve) / (2.0 * self.falloff));
+
+                return interpolate::linear(
+                    self.sources[index].get(point),
+                    self.sources[index + 1].get(point),
+                    alpha,
+                );
+            }
+        }
+
+        let band = self
+            .thresholds
+            .iter()
+            .take_while(|&&threshold| control_value >= threshold)
+            .count();
+
+        self.sources[band].get(point)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        let mut children: Vec<_> = self
+            .sources
+            .iter()
+            .map(|source| source.describe())
+            .collect();
+        children.push(self.control.describe());
+
+        NodeDesc::new("MultiSelect").set_children(children)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    #[test]
+    fn bands_route_to_sources() {
+        let sources = [Constant::new(1.0), Constant::new(2.0), Constant::new(3.0)];
+
+        for &(control_value, expected) in &[(-0.9, 1.0), (-0.5, 2.0), (0.2, 2.0), (0.5, 3.0)] {
+            let control = Constant::new(control_value);
+
+            let select_2d = MultiSelect::<[f64; 2]>::new(
+                vec![&sources[0], &sources[1], &sources[2]],
+                &control,
+                vec![-0.5, 0.5],
+            );
+            let select_3d = MultiSelect::<[f64; 3]>::new(
+                vec![&sources[0], &sources[1], &sources[2]],
+                &control,
+                vec![-0.5, 0.5],
+            );
+
+            assert_eq!(select_2d.get([0.0, 0.0]), expected);
+            assert_eq!(select_3d.get([0.0, 0.0, 0.0]), expected);
+        }
+    }
+
+    #[test]
+    fn falloff_blends_at_thresholds() {
+        let sources = [Constant::new(1.0), Constant::new(2.0), Constant::new(3.0)];
+        let control = Constant::new(0.5);
+        let select = MultiSelect::<[f64; 2]>::new(
+            vec![&sources[0], &sources[1], &sources[2]],
+            &control,
+            vec![-0.5, 0.5],
+        )
+        .set_falloff(0.1);
+
+        assert!((select.get([0.0, 0.0]) - 2.5).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn, Worley};
+
+/// Noise function that fills each cell of a Worley diagram with a value
+/// picked from a palette.
+///
+/// Each cell outputs `palette[cell_id % palette.len()]`, where `cell_id` is
+/// the hash of the cell that the Worley function selects for the input value.
+/// The output is constant within each cell and changes only across cell
+/// boundaries, which produces flat-shaded regions such as biome maps. The
+/// range function, frequency, jitter, stretch and return rank of the Worley
+/// function shape the regions.
+pub struct RegionFill<'a> {
+    /// Worley function that divides the input space into cells.
+    pub control: &'a Worley,
+
+    /// Values the cells are filled with.
+    pub palette: Vec<f64>,
+}
+
+impl<'a> RegionFill<'a> {
+    pub fn new(control: &'a Worley, palette: Vec<f64>) -> Self {
+        assert!(!palette.is_empty(), "palette must not be empty");
+
+        Self { control, palette }
+    }
+
+    fn fill(&self, cell_id: usize) -> f64 {
+        palette_entry(&self.palette, cell_id)
+    }
+}
+
+pub(crate) fn palette_entry(palette: &[f64], cell_id: usize) -> f64 {
+    palette[cell_id % palette.len()]
+}
+
+impl<'a> NoiseFn<[f64; 2]> for RegionFill<'a> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.fill(self.control.sample2(point).cell_id)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("RegionFill").set_children(vec![NoiseFn::<[f64; 2]>::describe(self.control)])
+    }
+}
+
+impl<'a> NoiseFn<[f64; 3]> for RegionFill<'a> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.fill(self.control.sample3(point).cell_id)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("RegionFill").set_children(vec![NoiseFn::<[f64; 3]>::describe(self.control)])
+    }
+}
+
+impl<'a> NoiseFn<[f64; 4]> for RegionFill<'a> {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.fill(self.control.sample4(point).cell_id)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("RegionFill").set_children(vec![NoiseFn::<[f64; 4]>::describe(self.control)])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_cells_from_palette() {
+        let worley = Worley::new().set_frequency(2.0);
+        let palette = vec![-1.0, -0.25, 0.5, 0.75];
+        let regions = RegionFill::new(&worley, palette.clone());
+
+        let mut previous: Option<(f64, f64)> = None;
+        for i in 0..2000 {
+            let point = [i as f64 * 0.003 - 2.0, 0.71];
+            let value = regions.get(point);
+            let cell = worley.get(point);
+
+            assert!(palette.contains(&value));
+            if let Some((previous_value, previous_cell)) = previous {
+                // The fill only changes where the Worley cell changes.
+                if cell == previous_cell {
+                    assert_eq!(value, previous_value);
+                }
+            }
+            previous = Some((value, cell));
+        }
+
+        let uses: Vec<bool> = palette
+            .iter()
+            .map(|&entry| {
+                (0..400)
+                    .any(|i| regions.get([(i % 20) as f64 * 0.7, (i / 20) as f64 * 0.7]) == entry)
+            })
+            .collect();
+        assert!(uses.iter().filter(|&&used| used).count() > 1);
+    }
+}
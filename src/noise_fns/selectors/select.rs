@@ -1,4 +1,17 @@
-use crate::{math::interpolate, noise_fns::NoiseFn};
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math::interpolate,
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Source function chosen by a `Select`, as returned by
+/// `Select::get_with_selection`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Selection {
+    Source1,
+    Source2,
+}
 
 /// Noise function that outputs the value selected from one of two source
 /// functions chosen by the output value from a control function.
@@ -20,6 +33,10 @@ pub struct Select<'a, T> {
 
     /// Edge falloff value. Default is 0.0.
     pub falloff: f64,
+
+    /// Width of the dead band around each bound used by
+    /// `get_with_selection`. Default is 0.0.
+    pub hysteresis: f64,
 }
 
 impl<'a, T> Select<'a, T> {
@@ -34,6 +51,7 @@ impl<'a, T> Select<'a, T> {
             control,
             bounds: (0.0, 1.0),
             falloff: 0.0,
+            hysteresis: 0.0,
         }
     }
 
@@ -47,6 +65,55 @@ impl<'a, T> Select<'a, T> {
     pub fn set_falloff(self, falloff: f64) -> Self {
         Select { falloff, ..self }
     }
+
+    pub fn set_hysteresis(self, hysteresis: f64) -> Self {
+        Select { hysteresis, ..self }
+    }
+}
+
+impl<'a, T> Select<'a, T>
+where
+    T: Copy,
+{
+    /// Outputs the value at `point` like `get`, taking into account which
+    /// source was selected at the same point before, and returns the source
+    /// selected this time along with the value.
+    ///
+    /// Noise functions don't keep state between calls, so the caller keeps
+    /// the selection of each point, for example of each cell of a map, and
+    /// passes it back in for the next frame of an animation. Once `source2`
+    /// is selected, it stays selected until the control value moves beyond
+    /// `hysteresis` outside the bounds; once `source1` is selected, the
+    /// control value has to move `hysteresis` inside the bounds to select
+    /// `source2`. This keeps a control value hovering around a bound from
+    /// flickering between the sources. With no previous selection, the
+    /// bounds are used as they are.
+    pub fn get_with_selection(&self, point: T, previous: Option<Selection>) -> (f64, Selection) {
+        let control_value = self.control.get(point);
+        let (lower, upper) = self.bounds;
+
+        let bounds = match previous {
+            Some(Selection::Source1) => (lower + self.hysteresis, upper - self.hysteresis),
+            Some(Selection::Source2) => (lower - self.hysteresis, upper + self.hysteresis),
+            None => self.bounds,
+        };
+        let selection = if control_value < bounds.0 || control_value > bounds.1 {
+            Selection::Source1
+        } else {
+            Selection::Source2
+        };
+
+        let value = select(
+            self.source1,
+            self.source2,
+            control_value,
+            bounds,
+            self.falloff,
+            point,
+        );
+
+        (value, selection)
+    }
 }
 
 impl<'a, T> NoiseFn<T> for Select<'a, T>
@@ -55,36 +122,110 @@ where
 {
     fn get(&self, point: T) -> f64 {
         let control_value = self.control.get(point);
-        let (lower, upper) = self.bounds;
 
-        if self.falloff > 0.0 {
-            match () {
-                _ if control_value < (lower - self.falloff) => self.source1.get(point),
-                _ if control_value < (lower + self.falloff) => {
-                    let lower_curve = lower - self.falloff;
-                    let upper_curve = lower + self.falloff;
-                    let alpha = interpolate::s_curve3(
-                        (control_value - lower_curve) / (upper_curve - lower_curve),
-                    );
-
-                    interpolate::linear(self.source1.get(point), self.source2.get(point), alpha)
-                }
-                _ if control_value < (upper - self.falloff) => self.source2.get(point),
-                _ if control_value < (upper + self.falloff) => {
-                    let lower_curve = upper - self.falloff;
-                    let upper_curve = upper + self.falloff;
-                    let alpha = interpolate::s_curve3(
-                        (control_value - lower_curve) / (upper_curve - lower_curve),
-                    );
-
-                    interpolate::linear(self.source2.get(point), self.source1.get(point), alpha)
-                }
-                _ => self.source1.get(point),
+        select(
+            self.source1,
+            self.source2,
+            control_value,
+            self.bounds,
+            self.falloff,
+            point,
+        )
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Select").set_children(vec![
+            self.source1.describe(),
+            self.source2.describe(),
+            self.control.describe(),
+        ])
+    }
+}
+
+/// Outputs the value from `source2` if `control_value` lies within `bounds`, or
+/// the value from `source1` otherwise, blending the two across a band of
+/// `falloff` on either side of each bound.
+pub(crate) fn select<T>(
+    source1: &dyn NoiseFn<T>,
+    source2: &dyn NoiseFn<T>,
+    control_value: f64,
+    bounds: (f64, f64),
+    falloff: f64,
+    point: T,
+) -> f64
+where
+    T: Copy,
+{
+    let (lower, upper) = bounds;
+
+    if falloff > 0.0 {
+        match () {
+            _ if control_value < (lower - falloff) => source1.get(point),
+            _ if control_value < (lower + falloff) => {
+                let lower_curve = lower - falloff;
+                let upper_curve = lower + falloff;
+                let alpha = interpolate::s_curve3(
+                    (control_value - lower_curve) / (upper_curve - lower_curve),
+                );
+
+                interpolate::linear(source1.get(point), source2.get(point), alpha)
             }
-        } else if control_value < lower || control_value > upper {
-            self.source1.get(point)
-        } else {
-            self.source2.get(point)
+            _ if control_value < (upper - falloff) => source2.get(point),
+            _ if control_value < (upper + falloff) => {
+                let lower_curve = upper - falloff;
+                let upper_curve = upper + falloff;
+                let alpha = interpolate::s_curve3(
+                    (control_value - lower_curve) / (upper_curve - lower_curve),
+                );
+
+                interpolate::linear(source2.get(point), source1.get(point), alpha)
+            }
+            _ => source1.get(point),
         }
+    } else if control_value < lower || control_value > upper {
+        source1.get(point)
+    } else {
+        source2.get(point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    #[test]
+    fn hysteresis_keeps_selection() {
+        let source1 = Constant::new(-1.0);
+        let source2 = Constant::new(1.0);
+        let frames = [0.98, 1.03, 0.97, 1.04, 0.99, 1.02];
+
+        let run = |hysteresis| {
+            let mut selection = None;
+            frames
+                .iter()
+                .map(|&control| {
+                    let control = Constant::new(control);
+                    let select = Select::new(&source1, &source2, &control)
+                        .set_bounds(0.0, 1.0)
+                        .set_hysteresis(hysteresis);
+                    let (value, selected) = select.get_with_selection([0.5, 0.5], selection);
+
+                    selection = Some(selected);
+                    value
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(run(0.0), vec![1.0, -1.0, 1.0, -1.0, 1.0, -1.0]);
+        assert_eq!(run(0.1), vec![1.0; 6]);
+
+        // Moving past the dead band still switches the source.
+        let control = Constant::new(1.2);
+        let select = Select::new(&source1, &source2, &control).set_hysteresis(0.1);
+        assert_eq!(
+            select.get_with_selection([0.5, 0.5], Some(Selection::Source2)),
+            (-1.0, Selection::Source1)
+        );
     }
 }
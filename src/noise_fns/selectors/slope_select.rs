@@ -0,0 +1,127 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{selectors::select::select, NodeDesc, NoiseFn};
+
+/// Noise function that outputs the value selected from one of two source
+/// functions chosen by the local slope of a height function.
+///
+/// The slope is the magnitude of the gradient of the height function,
+/// estimated with central differences spaced `step` apart. A slope of 1.0
+/// corresponds to a 45 degree incline. This is useful for placing features
+/// based on terrain steepness rather than elevation, such as cliffs on steep
+/// slopes and grass on flat ground.
+pub struct SlopeSelect<'a> {
+    /// Outputs a value.
+    pub source1: &'a dyn NoiseFn<[f64; 2]>,
+
+    /// Outputs a value.
+    pub source2: &'a dyn NoiseFn<[f64; 2]>,
+
+    /// Height function whose slope determines the value to select. If the
+    /// slope is within the _selection range_, this noise function outputs the
+    /// value from `source2`. Otherwise, this noise function outputs the value
+    /// from `source1`.
+    pub height: &'a dyn NoiseFn<[f64; 2]>,
+
+    /// Bounds of the selection range. Default is 1.0 to `core::f64::MAX`.
+    pub bounds: (f64, f64),
+
+    /// Edge falloff value. Default is 0.0.
+    pub falloff: f64,
+
+    /// Distance between the samples used to estimate the slope. Default is
+    /// 0.01.
+    pub step: f64,
+}
+
+impl<'a> SlopeSelect<'a> {
+    pub const DEFAULT_STEP: f64 = 0.01;
+
+    pub fn new(
+        source1: &'a dyn NoiseFn<[f64; 2]>,
+        source2: &'a dyn NoiseFn<[f64; 2]>,
+        height: &'a dyn NoiseFn<[f64; 2]>,
+    ) -> Self {
+        SlopeSelect {
+            source1,
+            source2,
+            height,
+            bounds: (1.0, core::f64::MAX),
+            falloff: 0.0,
+            step: Self::DEFAULT_STEP,
+        }
+    }
+
+    pub fn set_slope_bounds(self, lower_bound: f64, upper_bound: f64) -> Self {
+        SlopeSelect {
+            bounds: (lower_bound, upper_bound),
+            ..self
+        }
+    }
+
+    pub fn set_falloff(self, falloff: f64) -> Self {
+        SlopeSelect { falloff, ..self }
+    }
+
+    pub fn set_step(self, step: f64) -> Self {
+        SlopeSelect { step, ..self }
+    }
+
+    /// Estimates the slope of the height function at the given point.
+    pub fn slope(&self, point: [f64; 2]) -> f64 {
+        let [x, y] = point;
+        let step = self.step;
+
+        let dx = (self.height.get([x + step, y]) - self.height.get([x - step, y])) / (2.0 * step);
+        let dy = (self.height.get([x, y + step]) - self.height.get([x, y - step])) / (2.0 * step);
+
+        dx.hypot(dy)
+    }
+}
+
+impl<'a> NoiseFn<[f64; 2]> for SlopeSelect<'a> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        select(
+            self.source1,
+            self.source2,
+            self.slope(point),
+            self.bounds,
+            self.falloff,
+            point,
+        )
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("SlopeSelect").set_children(vec![
+            self.source1.describe(),
+            self.source2.describe(),
+            self.height.describe(),
+        ])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Constant;
+
+    // Flat for negative x, rising with a slope of 4 for positive x.
+    struct Ramp;
+
+    impl NoiseFn<[f64; 2]> for Ramp {
+        fn get(&self, point: [f64; 2]) -> f64 {
+            point[0].max(0.0) * 4.0
+        }
+    }
+
+    #[test]
+    fn selects_by_slope() {
+        let grass = Constant::new(-1.0);
+        let cliff = Constant::new(1.0);
+        let select = SlopeSelect::new(&grass, &cliff, &Ramp).set_slope_bounds(2.0, core::f64::MAX);
+
+        assert_eq!(select.get([-1.0, 0.0]), -1.0);
+        assert_eq!(select.get([1.0, 0.0]), 1.0);
+        assert!((select.slope([1.0, 0.5]) - 4.0).abs() < 1e-9);
+    }
+}
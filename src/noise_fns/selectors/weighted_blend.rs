@@ -0,0 +1,102 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math::{self, interpolate},
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that blends between several source functions, chosen by
+/// the output value from a control function.
+///
+/// The sources are spread evenly over the [-1,1] range of the control value:
+/// a control value of -1.0 outputs the value from the first source, 1.0 the
+/// value from the last, and values in between linearly interpolate between
+/// the two sources on either side. Control values outside [-1,1] are clamped.
+/// This generalizes `Blend` to any number of sources, which is useful for
+/// smooth transitions through a sequence of terrain types.
+///
+/// Only the two sources nearest to the control value are evaluated.
+pub struct WeightedBlend<'a, T> {
+    /// Outputs the values to blend, in order of increasing control value.
+    pub sources: Vec<&'a dyn NoiseFn<T>>,
+
+    /// Determines the position along the list of sources.
+    pub control: &'a dyn NoiseFn<T>,
+}
+
+impl<'a, T> WeightedBlend<'a, T> {
+    /// Panics if `sources` is empty.
+    pub fn new(sources: Vec<&'a dyn NoiseFn<T>>, control: &'a dyn NoiseFn<T>) -> Self {
+        assert!(!sources.is_empty(), "at least one source is required");
+
+        WeightedBlend { sources, control }
+    }
+}
+
+impl<'a, T> NoiseFn<T> for WeightedBlend<'a, T>
+where
+    T: Copy,
+{
+    fn get(&self, point: T) -> f64 {
+        let last = self.sources.len() - 1;
+        if last == 0 {
+            return self.sources[0].get(point);
+        }
+
+        let control = math::clamp(self.control.get(point), -1.0, 1.0);
+        let position = (control + 1.0) * 0.5 * last as f64;
+        let lower = (position.floor() as usize).min(last - 1);
+        let alpha = position - lower as f64;
+
+        if alpha == 0.0 {
+            return self.sources[lower].get(point);
+        }
+
+        interpolate::linear(
+            self.sources[lower].get(point),
+            self.sources[lower + 1].get(point),
+            alpha,
+        )
+    }
+
+    fn describe(&self) -> NodeDesc {
+        let mut children: Vec<_> = self
+            .sources
+            .iter()
+            .map(|source| source.describe())
+            .collect();
+        children.push(self.control.describe());
+
+        NodeDesc::new("WeightedBlend").set_children(children)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Constant, Linear};
+
+    #[test]
+    fn blends_across_all_sources() {
+        let first = Constant::new(-1.0);
+        let second = Constant::new(1.0);
+        let third = Constant::new(0.0);
+        let ramp = Linear::new(1.0, 0.0, 0.0, 0.0);
+        let blend = WeightedBlend::new(vec![&first, &second, &third], &ramp);
+
+        assert_eq!(blend.get([-1.0, 0.0]), -1.0);
+        assert_eq!(blend.get([-0.5, 0.0]), 0.0);
+        assert_eq!(blend.get([0.0, 0.0]), 1.0);
+        assert_eq!(blend.get([0.5, 0.0]), 0.5);
+        assert_eq!(blend.get([1.0, 0.0]), 0.0);
+        assert_eq!(blend.get([3.0, 0.0]), 0.0);
+
+        // The output changes continuously along the whole ramp.
+        let mut previous = blend.get([-1.0, 0.0]);
+        for i in 1..=200 {
+            let value = blend.get([i as f64 * 0.01 - 1.0, 0.0]);
+            assert!((value - previous).abs() <= 0.04 + 1e-12);
+            previous = value;
+        }
+    }
+}
@@ -1,11 +1,27 @@
+pub use self::crackle::*;
 pub use self::displace::*;
+pub use self::domain_warp::*;
+pub use self::project::*;
+pub use self::rebase_origin::*;
 pub use self::rotate_point::*;
 pub use self::scale_point::*;
+pub use self::spherical::*;
+pub use self::supersample::*;
+pub use self::tiled_domain::*;
 pub use self::translate_point::*;
+pub use self::triplanar::*;
 pub use self::turbulence::*;
 
+mod crackle;
 mod displace;
+mod domain_warp;
+mod project;
+mod rebase_origin;
 mod rotate_point;
 mod scale_point;
+mod spherical;
+mod supersample;
+mod tiled_domain;
 mod translate_point;
+mod triplanar;
 mod turbulence;
@@ -0,0 +1,143 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{NodeDesc, NoiseFn, Seedable, Worley},
+};
+
+/// Noise function that pulls the input value toward the nearest edge of a
+/// Voronoi diagram before returning the output value from the source function.
+///
+/// Each input value is moved `strength` times its distance toward the
+/// boundary of its Worley cell. The source is squeezed together along the
+/// cell edges and stretched across the cell interiors, which produces the
+/// look of cracked mud, dry paint or ice. A strength of 0.0 leaves the input
+/// value unchanged and a strength of 1.0 moves every point onto the nearest
+/// edge.
+///
+/// The cells lie in the xy plane, so 3-dimensional input values keep their z
+/// coordinate.
+#[derive(Clone, Debug)]
+pub struct Crackle<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Amount the input value is moved toward the nearest cell edge.
+    pub strength: f64,
+
+    worley: Worley,
+}
+
+impl<Source> Crackle<Source> {
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_FREQUENCY: f64 = 1.0;
+    pub const DEFAULT_STRENGTH: f64 = 0.5;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            strength: Self::DEFAULT_STRENGTH,
+            worley: Worley::new()
+                .set_seed(Self::DEFAULT_SEED)
+                .set_frequency(Self::DEFAULT_FREQUENCY),
+        }
+    }
+
+    /// Sets the number of cells per unit length.
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        Self {
+            worley: self.worley.set_frequency(frequency),
+            ..self
+        }
+    }
+
+    pub fn set_strength(self, strength: f64) -> Self {
+        Self { strength, ..self }
+    }
+
+    pub fn frequency(&self) -> f64 {
+        self.worley.frequency
+    }
+
+    fn crack(&self, x: f64, y: f64) -> [f64; 2] {
+        let offset = self.worley.edge_vector2([x, y]);
+
+        math::add2([x, y], math::mul2(offset, self.strength))
+    }
+}
+
+impl<Source> Seedable for Crackle<Source> {
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            worley: self.worley.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.worley.seed()
+    }
+}
+
+impl<Source> NoiseFn<[f64; 2]> for Crackle<Source>
+where
+    Source: NoiseFn<[f64; 2]>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.source.get(self.crack(point[0], point[1]))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Crackle").set_children(vec![self.source.describe()])
+    }
+}
+
+impl<Source> NoiseFn<[f64; 3]> for Crackle<Source>
+where
+    Source: NoiseFn<[f64; 3]>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let [x, y] = self.crack(point[0], point[1]);
+
+        self.source.get([x, y, point[2]])
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Crackle").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Perlin;
+
+    #[test]
+    fn zero_strength_is_identity() {
+        let perlin = Perlin::new();
+        let crackle = Crackle::new(&perlin).set_strength(0.0).set_frequency(3.0);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.37 - 3.0, i as f64 * 0.91, 0.4];
+
+            assert_eq!(crackle.get(point), perlin.get(point));
+            assert_eq!(
+                crackle.get([point[0], point[1]]),
+                perlin.get([point[0], point[1]])
+            );
+        }
+    }
+
+    #[test]
+    fn full_strength_samples_edges() {
+        let crackle = Crackle::new(Worley::new().set_frequency(2.0)).set_strength(1.0);
+        let worley = crackle.worley;
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.37 - 3.0, i as f64 * 0.91];
+            let cracked = crackle.crack(point[0], point[1]);
+
+            assert!(worley.edge_distance2(cracked).abs() < 1e-9);
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::noise_fns::NoiseFn;
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
 
 /// Noise function that uses multiple source functions to displace each coordinate
 /// of the input value before returning the output value from the `source` function.
@@ -61,6 +63,14 @@ where
         // original input value.
         self.source.get([x, y])
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Displace").set_children(vec![
+            self.source.describe(),
+            self.x_displace.describe(),
+            self.y_displace.describe(),
+        ])
+    }
 }
 
 impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace> NoiseFn<[f64; 3]>
@@ -84,6 +94,15 @@ where
         // original input value.
         self.source.get([x, y, z])
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Displace").set_children(vec![
+            self.source.describe(),
+            self.x_displace.describe(),
+            self.y_displace.describe(),
+            self.z_displace.describe(),
+        ])
+    }
 }
 
 impl<Source, XDisplace, YDisplace, ZDisplace, UDisplace> NoiseFn<[f64; 4]>
@@ -109,4 +128,14 @@ where
         // original input value.
         self.source.get([x, y, z, u])
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Displace").set_children(vec![
+            self.source.describe(),
+            self.x_displace.describe(),
+            self.y_displace.describe(),
+            self.z_displace.describe(),
+            self.u_displace.describe(),
+        ])
+    }
 }
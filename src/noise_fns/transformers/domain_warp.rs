@@ -0,0 +1,147 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that warps the input value by the output of another noise
+/// function before returning the output value from the source function.
+///
+/// Each coordinate of the input value is offset by `warp_strength` times the
+/// output of the warp function. The warp function is sampled at a different,
+/// fixed offset for each axis so that the axes are displaced independently.
+/// Using an fBm for both the source and the warp function produces the
+/// swirly, flow-like patterns of iterated domain warping; nesting several
+/// `DomainWarp`s warps the domain repeatedly.
+#[derive(Clone, Debug)]
+pub struct DomainWarp<Source, Warp> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Function whose output displaces the input value.
+    pub warp_source: Warp,
+
+    /// Scale of the displacement applied to each coordinate. Default is 1.0.
+    pub warp_strength: f64,
+}
+
+// Offsets at which the warp function is sampled for each axis, chosen so that
+// the displacements of the different axes are uncorrelated.
+const AXIS_OFFSETS: [[f64; 4]; 4] = [
+    [0.0, 0.0, 0.0, 0.0],
+    [5.2, 1.3, 2.8, 7.1],
+    [1.7, 9.2, 4.6, 3.3],
+    [8.3, 2.8, 6.1, 9.9],
+];
+
+impl<Source, Warp> DomainWarp<Source, Warp> {
+    pub const DEFAULT_WARP_STRENGTH: f64 = 1.0;
+
+    pub fn new(source: Source, warp_source: Warp) -> Self {
+        Self {
+            source,
+            warp_source,
+            warp_strength: Self::DEFAULT_WARP_STRENGTH,
+        }
+    }
+
+    pub fn set_warp_strength(self, warp_strength: f64) -> Self {
+        Self {
+            warp_strength,
+            ..self
+        }
+    }
+}
+
+impl<Source, Warp> NoiseFn<[f64; 2]> for DomainWarp<Source, Warp>
+where
+    Source: NoiseFn<[f64; 2]>,
+    Warp: NoiseFn<[f64; 2]>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let warp = |axis: usize| {
+            let offset = [AXIS_OFFSETS[axis][0], AXIS_OFFSETS[axis][1]];
+            self.warp_strength * self.warp_source.get(math::add2(point, offset))
+        };
+
+        self.source.get(math::add2(point, [warp(0), warp(1)]))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("DomainWarp")
+            .set_children(vec![self.source.describe(), self.warp_source.describe()])
+    }
+}
+
+impl<Source, Warp> NoiseFn<[f64; 3]> for DomainWarp<Source, Warp>
+where
+    Source: NoiseFn<[f64; 3]>,
+    Warp: NoiseFn<[f64; 3]>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let warp = |axis: usize| {
+            let offset = [
+                AXIS_OFFSETS[axis][0],
+                AXIS_OFFSETS[axis][1],
+                AXIS_OFFSETS[axis][2],
+            ];
+            self.warp_strength * self.warp_source.get(math::add3(point, offset))
+        };
+
+        self.source
+            .get(math::add3(point, [warp(0), warp(1), warp(2)]))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("DomainWarp")
+            .set_children(vec![self.source.describe(), self.warp_source.describe()])
+    }
+}
+
+impl<Source, Warp> NoiseFn<[f64; 4]> for DomainWarp<Source, Warp>
+where
+    Source: NoiseFn<[f64; 4]>,
+    Warp: NoiseFn<[f64; 4]>,
+{
+    fn get(&self, point: [f64; 4]) -> f64 {
+        let warp = |axis: usize| {
+            self.warp_strength * self.warp_source.get(math::add4(point, AXIS_OFFSETS[axis]))
+        };
+
+        self.source
+            .get(math::add4(point, [warp(0), warp(1), warp(2), warp(3)]))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("DomainWarp")
+            .set_children(vec![self.source.describe(), self.warp_source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Fbm, Seedable};
+
+    #[test]
+    fn zero_strength_is_identity() {
+        let fbm = Fbm::new();
+        let warp = Fbm::new().set_seed(1);
+        let warped = fbm.warp(0.0, &warp);
+
+        for &point in &[[0.3, 0.7], [12.5, -3.25], [-100.1, 4.9]] {
+            assert_eq!(warped.get(point), fbm.get(point));
+        }
+    }
+
+    #[test]
+    fn warp_changes_output() {
+        let fbm = Fbm::new();
+        let warp = Fbm::new().set_seed(1);
+        let warped = DomainWarp::new(&fbm, &warp).set_warp_strength(4.0);
+
+        let point = [0.3, 0.7, 1.1];
+        assert_ne!(warped.get(point), fbm.get(point));
+    }
+}
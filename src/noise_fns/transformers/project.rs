@@ -0,0 +1,96 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
+
+/// Noise function that evaluates a 2-dimensional source function with
+/// 3-dimensional input values by dropping the _z_ coordinate.
+///
+/// The output is constant along the _z_ axis, which allows a 2D noise graph to
+/// be used wherever a 3D one is expected.
+pub struct Project2DTo3D<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+}
+
+impl<Source> Project2DTo3D<Source> {
+    pub fn new(source: Source) -> Self {
+        Self { source }
+    }
+}
+
+impl<Source> NoiseFn<[f64; 3]> for Project2DTo3D<Source>
+where
+    Source: NoiseFn<[f64; 2]>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.source.get([point[0], point[1]])
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Project2DTo3D").set_children(vec![self.source.describe()])
+    }
+}
+
+/// Noise function that evaluates a 3-dimensional source function with
+/// 2-dimensional input values, taking a planar slice through the source at a
+/// fixed _z_ coordinate.
+pub struct Slice3D<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// _z_ coordinate of the slice. Default is 0.0.
+    pub z: f64,
+}
+
+impl<Source> Slice3D<Source> {
+    pub const DEFAULT_Z: f64 = 0.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            z: Self::DEFAULT_Z,
+        }
+    }
+
+    pub fn set_z(self, z: f64) -> Self {
+        Self { z, ..self }
+    }
+}
+
+impl<Source> NoiseFn<[f64; 2]> for Slice3D<Source>
+where
+    Source: NoiseFn<[f64; 3]>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.source.get([point[0], point[1], self.z])
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Slice3D").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Perlin;
+
+    #[test]
+    fn slice_matches_source() {
+        let perlin = Perlin::new();
+        let slice = Slice3D::new(&perlin).set_z(0.37);
+
+        for &[x, y] in &[[0.3, 0.7], [12.5, -3.25], [-100.1, 4.9]] {
+            assert_eq!(slice.get([x, y]), perlin.get([x, y, 0.37]));
+        }
+    }
+
+    #[test]
+    fn projection_ignores_z() {
+        let perlin = Perlin::new();
+        let projected = Project2DTo3D::new(&perlin);
+
+        assert_eq!(projected.get([0.3, 0.7, 5.0]), perlin.get([0.3, 0.7]));
+        assert_eq!(projected.get([0.3, 0.7, -2.0]), perlin.get([0.3, 0.7]));
+    }
+}
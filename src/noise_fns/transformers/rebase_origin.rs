@@ -0,0 +1,166 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that samples the source function relative to a distant
+/// origin without losing precision.
+///
+/// Far away from zero, `f64` coordinates can no longer represent small steps,
+/// so noise sampled there looks blocky and stretched. Lattice noise functions
+/// repeat after a fixed number of lattice cells, because their permutation
+/// lookups wrap around. This function takes advantage of that by reducing the
+/// origin modulo `period` once, and then sampling the source at the reduced
+/// origin plus the local input value. The output matches sampling the source
+/// at `origin + point`, but keeps full precision for any origin.
+///
+/// The default period of 256 matches the permutation tables used by the
+/// lattice generators at a frequency of 1.0. For a source whose features
+/// repeat at a different interval, such as a generator with a different
+/// frequency, set the period accordingly. Sources that do not repeat, such as
+/// fractals with a non-integer lacunarity, do not match their unrebased
+/// output, although they still keep their detail.
+#[derive(Clone, Debug)]
+pub struct RebaseOrigin<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    origin: [f64; 4],
+    period: f64,
+    offset: [f64; 4],
+}
+
+impl<Source> RebaseOrigin<Source> {
+    pub const DEFAULT_ORIGIN: [f64; 4] = [0.0; 4];
+    pub const DEFAULT_PERIOD: f64 = 256.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            origin: Self::DEFAULT_ORIGIN,
+            period: Self::DEFAULT_PERIOD,
+            offset: Self::DEFAULT_ORIGIN,
+        }
+    }
+
+    /// Sets the origin that input values are measured from.
+    ///
+    /// Only the first _n_ components are used for _n_-dimensional input.
+    pub fn set_origin(self, origin: [f64; 4]) -> Self {
+        Self {
+            origin,
+            offset: reduce(origin, self.period),
+            ..self
+        }
+    }
+
+    /// Sets the interval after which the source function repeats.
+    pub fn set_period(self, period: f64) -> Self {
+        assert!(period > 0.0);
+
+        Self {
+            period,
+            offset: reduce(self.origin, period),
+            ..self
+        }
+    }
+
+    pub fn origin(&self) -> [f64; 4] {
+        self.origin
+    }
+
+    pub fn period(&self) -> f64 {
+        self.period
+    }
+}
+
+fn reduce(origin: [f64; 4], period: f64) -> [f64; 4] {
+    math::map4(origin, |x| ((x % period) + period) % period)
+}
+
+impl<Source> NoiseFn<[f64; 2]> for RebaseOrigin<Source>
+where
+    Source: NoiseFn<[f64; 2]>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let offset = [self.offset[0], self.offset[1]];
+
+        self.source.get(math::add2(offset, point))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("RebaseOrigin").set_children(vec![self.source.describe()])
+    }
+}
+
+impl<Source> NoiseFn<[f64; 3]> for RebaseOrigin<Source>
+where
+    Source: NoiseFn<[f64; 3]>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        let offset = [self.offset[0], self.offset[1], self.offset[2]];
+
+        self.source.get(math::add3(offset, point))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("RebaseOrigin").set_children(vec![self.source.describe()])
+    }
+}
+
+impl<Source> NoiseFn<[f64; 4]> for RebaseOrigin<Source>
+where
+    Source: NoiseFn<[f64; 4]>,
+{
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.source.get(math::add4(self.offset, point))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("RebaseOrigin").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Perlin;
+
+    fn local_variance(source: &dyn NoiseFn<[f64; 2]>) -> f64 {
+        let values: Vec<f64> = (0..1600)
+            .map(|i| source.get([(i % 40) as f64 * 0.05, (i / 40) as f64 * 0.05]))
+            .collect();
+        let mean = values.iter().sum::<f64>() / values.len() as f64;
+
+        values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+    }
+
+    #[test]
+    fn matches_source_at_origin() {
+        let perlin = Perlin::new();
+        let origin = [1.0e6 + 0.3, -2.0e6 - 0.7, 0.0, 0.0];
+        let rebased = RebaseOrigin::new(&perlin).set_origin(origin);
+
+        for i in 0..50 {
+            let point = [i as f64 * 0.37 - 3.0, i as f64 * 0.19];
+            let direct = perlin.get([origin[0] + point[0], origin[1] + point[1]]);
+
+            assert!((rebased.get(point) - direct).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn keeps_detail_far_from_origin() {
+        let perlin = Perlin::new();
+        let near = local_variance(&perlin);
+
+        for &distance in &[1.0e6, 1.0e15] {
+            let rebased = RebaseOrigin::new(&perlin).set_origin([distance; 4]);
+            let far = local_variance(&rebased);
+
+            assert!(far > near * 0.25 && far < near * 4.0);
+        }
+    }
+}
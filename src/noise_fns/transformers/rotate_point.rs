@@ -1,4 +1,6 @@
-use crate::noise_fns::NoiseFn;
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
 
 /// Noise function that rotates the input value around the origin before
 /// returning the output value from the source function.
@@ -95,6 +97,10 @@ where
         // original input value.
         self.source.get([x2, y2])
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("RotatePoint").set_children(vec![self.source.describe()])
+    }
 }
 
 impl<Source> NoiseFn<[f64; 3]> for RotatePoint<Source>
@@ -129,6 +135,10 @@ where
         // original input value.
         self.source.get([x, y, z])
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("RotatePoint").set_children(vec![self.source.describe()])
+    }
 }
 
 impl<Source> NoiseFn<[f64; 4]> for RotatePoint<Source>
@@ -139,4 +149,8 @@ where
         // 4d rotations are hard.
         unimplemented!();
     }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("RotatePoint").set_children(vec![self.source.describe()])
+    }
 }
@@ -1,4 +1,6 @@
-use crate::noise_fns::NoiseFn;
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
 
 /// Noise function that scales the coordinates of the input value before
 /// returning the output value from the source function.
@@ -83,6 +85,19 @@ impl<Source> ScalePoint<Source> {
             ..self
         }
     }
+
+    // A source that repeats every `period` repeats every `period / scale` in
+    // the scaled input. Scaling an axis by 0.0 makes it constant, not
+    // periodic.
+    fn scaled_period(&self, period: Option<[f64; 2]>) -> Option<[f64; 2]> {
+        let [x, y] = period?;
+
+        if self.x_scale == 0.0 || self.y_scale == 0.0 {
+            return None;
+        }
+
+        Some([(x / self.x_scale).abs(), (y / self.y_scale).abs()])
+    }
 }
 
 impl<Source> NoiseFn<[f64; 2]> for ScalePoint<Source>
@@ -93,6 +108,14 @@ where
         self.source
             .get([point[0] * self.x_scale, point[1] * self.y_scale])
     }
+
+    fn is_periodic(&self) -> Option<[f64; 2]> {
+        self.scaled_period(self.source.is_periodic())
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("ScalePoint").set_children(vec![self.source.describe()])
+    }
 }
 
 impl<Source> NoiseFn<[f64; 3]> for ScalePoint<Source>
@@ -106,6 +129,14 @@ where
             point[2] * self.z_scale,
         ])
     }
+
+    fn is_periodic(&self) -> Option<[f64; 2]> {
+        self.scaled_period(self.source.is_periodic())
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("ScalePoint").set_children(vec![self.source.describe()])
+    }
 }
 
 impl<Source> NoiseFn<[f64; 4]> for ScalePoint<Source>
@@ -120,6 +151,14 @@ where
             point[3] * self.u_scale,
         ])
     }
+
+    fn is_periodic(&self) -> Option<[f64; 2]> {
+        self.scaled_period(self.source.is_periodic())
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("ScalePoint").set_children(vec![self.source.describe()])
+    }
 }
 
 #[cfg(test)]
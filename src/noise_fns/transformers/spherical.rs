@@ -0,0 +1,86 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that samples a 3-dimensional source function on the surface
+/// of a sphere.
+///
+/// The input value is interpreted as a (longitude, latitude) pair in degrees.
+/// The get() method maps it onto a point on a sphere of the given radius,
+/// centered on the origin, and returns the output value from the source
+/// function at that point.
+///
+/// This is useful for generating planetary noise directly from geographic
+/// coordinates.
+pub struct SphericalNoise<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Radius of the sphere. The default radius is 1.0.
+    ///
+    /// Increasing the radius increases the amount of detail on the sphere.
+    pub radius: f64,
+}
+
+impl<Source> SphericalNoise<Source> {
+    pub const DEFAULT_RADIUS: f64 = 1.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            radius: Self::DEFAULT_RADIUS,
+        }
+    }
+
+    /// Sets the radius of the sphere.
+    pub fn set_radius(self, radius: f64) -> Self {
+        Self { radius, ..self }
+    }
+}
+
+impl<Source> NoiseFn<[f64; 2]> for SphericalNoise<Source>
+where
+    Source: NoiseFn<[f64; 3]>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        let [lon, lat] = point;
+
+        self.source
+            .get(math::mul3(math::lat_lon_to_xyz(lat, lon), self.radius))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("SphericalNoise").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Perlin;
+
+    #[test]
+    fn poles_are_single_points() {
+        let sphere = SphericalNoise::new(Perlin::new()).set_radius(3.7);
+
+        for &lat in &[90.0, -90.0] {
+            let expected = sphere.get([0.0, lat]);
+
+            for lon in 0..36 {
+                let value = sphere.get([lon as f64 * 10.0 - 180.0, lat]);
+                assert!((value - expected).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn matches_source_on_equator() {
+        let perlin = Perlin::new();
+        let sphere = SphericalNoise::new(&perlin).set_radius(2.0);
+
+        assert_eq!(sphere.get([0.0, 0.0]), perlin.get([2.0, 0.0, 0.0]));
+    }
+}
@@ -0,0 +1,176 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math::CellRng,
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that averages the output values of the source function over
+/// a small area around the input value.
+///
+/// The area is a square (or cube) with a side length of `footprint`, centered
+/// on the input value, and is divided into `samples` strata along each axis.
+/// The source function is evaluated once in each stratum, at a fixed
+/// pseudo-random position within it, and the results are averaged.
+///
+/// This band-limits the source function. When a map is built with fewer
+/// cells than the source has features, setting `footprint` to the spacing
+/// between the cells removes most of the aliasing, at the cost of
+/// `samples^N` source evaluations per output value for N-dimensional input.
+pub struct Supersample<'a, T> {
+    /// Outputs the values to average.
+    pub source: &'a dyn NoiseFn<T>,
+
+    /// Number of samples along each axis. Default is 2.
+    pub samples: usize,
+
+    /// Side length of the averaged area. Default is 1.0.
+    pub footprint: f64,
+}
+
+impl<'a, T> Supersample<'a, T> {
+    pub const DEFAULT_SAMPLES: usize = 2;
+    pub const DEFAULT_FOOTPRINT: f64 = 1.0;
+
+    pub fn new(source: &'a dyn NoiseFn<T>) -> Self {
+        Self {
+            source,
+            samples: Self::DEFAULT_SAMPLES,
+            footprint: Self::DEFAULT_FOOTPRINT,
+        }
+    }
+
+    /// Sets the number of samples along each axis. With a single sample, the
+    /// source function is evaluated at the input value only.
+    ///
+    /// Panics if `samples` is 0.
+    pub fn set_samples(self, samples: usize) -> Self {
+        assert!(samples > 0, "at least one sample is required");
+
+        Self { samples, ..self }
+    }
+
+    pub fn set_footprint(self, footprint: f64) -> Self {
+        Self { footprint, ..self }
+    }
+
+    // Averages `sample` over the offsets of the strata, passed as slices of
+    // `dims` components.
+    fn average<F>(&self, dims: usize, sample: F) -> f64
+    where
+        F: Fn(&[f64]) -> f64,
+    {
+        let count = self.samples.pow(dims as u32);
+        let mut strata = [0_isize; 4];
+        let mut offset = [0.0; 4];
+        let mut sum = 0.0;
+
+        for index in 0..count {
+            let mut rest = index;
+            for stratum in &mut strata[..dims] {
+                *stratum = (rest % self.samples) as isize;
+                rest /= self.samples;
+            }
+
+            let mut jitter = CellRng::new(0, &strata[..dims]);
+            for axis in 0..dims {
+                let position = (strata[axis] as f64 + jitter.next().unwrap()) / self.samples as f64;
+                offset[axis] = (position - 0.5) * self.footprint;
+            }
+
+            sum += sample(&offset[..dims]);
+        }
+
+        sum / count as f64
+    }
+}
+
+impl<'a> NoiseFn<[f64; 2]> for Supersample<'a, [f64; 2]> {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        if self.samples == 1 {
+            return self.source.get(point);
+        }
+
+        self.average(2, |offset| {
+            self.source
+                .get([point[0] + offset[0], point[1] + offset[1]])
+        })
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Supersample").set_children(vec![self.source.describe()])
+    }
+}
+
+impl<'a> NoiseFn<[f64; 3]> for Supersample<'a, [f64; 3]> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        if self.samples == 1 {
+            return self.source.get(point);
+        }
+
+        self.average(3, |offset| {
+            self.source.get([
+                point[0] + offset[0],
+                point[1] + offset[1],
+                point[2] + offset[2],
+            ])
+        })
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Supersample").set_children(vec![self.source.describe()])
+    }
+}
+
+impl<'a> NoiseFn<[f64; 4]> for Supersample<'a, [f64; 4]> {
+    fn get(&self, point: [f64; 4]) -> f64 {
+        if self.samples == 1 {
+            return self.source.get(point);
+        }
+
+        self.average(4, |offset| {
+            self.source.get([
+                point[0] + offset[0],
+                point[1] + offset[1],
+                point[2] + offset[2],
+                point[3] + offset[3],
+            ])
+        })
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Supersample").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Perlin, ScalePoint};
+
+    #[test]
+    fn more_samples_reduce_variance() {
+        let perlin = Perlin::new();
+        let detailed = ScalePoint::new(&perlin).set_scale(8.0);
+
+        let variance = |samples| {
+            let supersample = Supersample::new(&detailed).set_samples(samples);
+            let values: Vec<f64> = (0..400)
+                .map(|i| supersample.get([(i % 20) as f64 + 0.3, (i / 20) as f64 + 0.7]))
+                .collect();
+            let mean = values.iter().sum::<f64>() / values.len() as f64;
+
+            values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / values.len() as f64
+        };
+
+        let single = variance(1);
+        let averaged = variance(4);
+        assert!(averaged < single * 0.25, "{} vs {}", averaged, single);
+
+        let point = [1.3, 2.1];
+        assert_eq!(
+            Supersample::new(&detailed).set_samples(1).get(point),
+            detailed.get(point)
+        );
+    }
+}
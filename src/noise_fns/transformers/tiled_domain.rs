@@ -0,0 +1,194 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math::{self, interpolate, CellRng},
+    noise_fns::{NodeDesc, NoiseFn, Seedable},
+};
+use core::f64::consts::PI;
+
+/// Noise function that divides the input space into large square tiles and
+/// gives each tile its own random rotation and offset of the input value
+/// before returning the output value from the source function.
+///
+/// Sampling a large area of a single noise function can show repeating
+/// structure, such as the lattice alignment of gradient noise. Rotating and
+/// shifting the input per tile breaks that up while staying deterministic, in
+/// the spirit of texture bombing. The rotation and offset of each tile are
+/// derived from a hash of the tile and the seed.
+///
+/// Near the tile boundaries, the outputs of the neighboring tiles are blended
+/// with a smooth curve, so the output is continuous everywhere. The blending
+/// lowers the contrast of the source function somewhat within the blend
+/// bands. Only the x and y coordinates are tiled; the z coordinate of
+/// 3-dimensional input passes through unchanged.
+#[derive(Clone, Copy, Debug)]
+pub struct TiledDomain<Source> {
+    /// Source function that outputs a value.
+    pub source: Source,
+
+    /// Side length of the tiles. Default is 16.0.
+    pub tile_size: f64,
+
+    /// Width of the band in which neighboring tiles are blended, as a
+    /// fraction of the tile size. Default is 0.25.
+    pub blend: f64,
+
+    seed: u32,
+}
+
+impl<Source> TiledDomain<Source> {
+    pub const DEFAULT_SEED: u32 = 0;
+    pub const DEFAULT_TILE_SIZE: f64 = 16.0;
+    pub const DEFAULT_BLEND: f64 = 0.25;
+
+    // Largest offset applied to a tile, chosen so that the offset tiles
+    // sample unrelated parts of the source.
+    const MAX_OFFSET: f64 = 1024.0;
+
+    pub fn new(source: Source) -> Self {
+        Self {
+            source,
+            tile_size: Self::DEFAULT_TILE_SIZE,
+            blend: Self::DEFAULT_BLEND,
+            seed: Self::DEFAULT_SEED,
+        }
+    }
+
+    /// Panics if `tile_size` is not positive.
+    pub fn set_tile_size(self, tile_size: f64) -> Self {
+        assert!(tile_size > 0.0, "tile size must be positive");
+
+        Self { tile_size, ..self }
+    }
+
+    /// Panics if `blend` is not greater than 0.0 and at most 1.0.
+    pub fn set_blend(self, blend: f64) -> Self {
+        assert!(blend > 0.0 && blend <= 1.0, "blend must be in (0, 1]");
+
+        Self { blend, ..self }
+    }
+
+    /// Returns the input value for the source function within `tile`.
+    fn transform(&self, tile: [isize; 2], point: [f64; 2]) -> [f64; 2] {
+        let mut rng = CellRng::new(self.seed, &tile);
+        let angle = rng.next().unwrap() * 2.0 * PI;
+        let offset = [
+            (rng.next().unwrap() * 2.0 - 1.0) * Self::MAX_OFFSET,
+            (rng.next().unwrap() * 2.0 - 1.0) * Self::MAX_OFFSET,
+        ];
+        let (sin, cos) = angle.sin_cos();
+
+        [
+            point[0] * cos - point[1] * sin + offset[0],
+            point[0] * sin + point[1] * cos + offset[1],
+        ]
+    }
+
+    /// Blends `sample` over the tiles whose centers surround `point`.
+    fn blend_tiles<F>(&self, point: [f64; 2], sample: F) -> f64
+    where
+        F: Fn([f64; 2]) -> f64,
+    {
+        // Position relative to the lattice of tile centers.
+        let relative = math::map2(point, |x| x / self.tile_size - 0.5);
+        let floor = math::map2(relative, f64::floor);
+        let base = math::to_isize2(floor);
+        let weight = |f: f64| {
+            let t = math::clamp((f - 0.5) / self.blend + 0.5, 0.0, 1.0);
+            interpolate::s_curve3(t)
+        };
+        let x_weight = weight(relative[0] - floor[0]);
+        let y_weight = weight(relative[1] - floor[1]);
+
+        let mut result = 0.0;
+        for &(dx, dy) in &[(0, 0), (1, 0), (0, 1), (1, 1)] {
+            let tile_weight = if dx == 0 { 1.0 - x_weight } else { x_weight }
+                * if dy == 0 { 1.0 - y_weight } else { y_weight };
+
+            if tile_weight > 0.0 {
+                let tile = [base[0] + dx, base[1] + dy];
+                result += tile_weight * sample(self.transform(tile, point));
+            }
+        }
+
+        result
+    }
+}
+
+impl<Source> Seedable for TiledDomain<Source> {
+    fn set_seed(self, seed: u32) -> Self {
+        Self { seed, ..self }
+    }
+
+    fn seed(&self) -> u32 {
+        self.seed
+    }
+}
+
+impl<Source> NoiseFn<[f64; 2]> for TiledDomain<Source>
+where
+    Source: NoiseFn<[f64; 2]>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.blend_tiles(point, |p| self.source.get(p))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("TiledDomain").set_children(vec![self.source.describe()])
+    }
+}
+
+impl<Source> NoiseFn<[f64; 3]> for TiledDomain<Source>
+where
+    Source: NoiseFn<[f64; 3]>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.blend_tiles([point[0], point[1]], |p| {
+            self.source.get([p[0], p[1], point[2]])
+        })
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("TiledDomain").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Linear, Perlin};
+
+    #[test]
+    fn continuous_across_tiles() {
+        let tiled = TiledDomain::new(Perlin::new()).set_tile_size(4.0);
+
+        let step = 0.001;
+        let mut previous = tiled.get([0.0, 1.3]);
+        for i in 1..=12_000 {
+            let value = tiled.get([i as f64 * step, 1.3]);
+            assert!(
+                (value - previous).abs() < 0.01,
+                "jump at x = {}",
+                i as f64 * step
+            );
+            previous = value;
+        }
+    }
+
+    #[test]
+    fn tiles_are_rotated() {
+        // The gradient of a linear source points along the rotated x axis.
+        let tiled = TiledDomain::new(Linear::new(1.0, 0.0, 0.0, 0.0));
+        let direction = |center: [f64; 2]| {
+            let value = tiled.get(center);
+            let dx = tiled.get([center[0] + 0.01, center[1]]) - value;
+            let dy = tiled.get([center[0], center[1] + 0.01]) - value;
+
+            dy.atan2(dx)
+        };
+
+        let near = direction([8.0, 8.0]);
+        let far = direction([8.0 + 16.0 * 40.0, 8.0 - 16.0 * 25.0]);
+        assert!((near - far).abs() > 0.1, "{} vs {}", near, far);
+    }
+}
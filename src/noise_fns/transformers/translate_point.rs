@@ -1,4 +1,6 @@
-use crate::noise_fns::NoiseFn;
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{NodeDesc, NoiseFn};
 
 /// Noise function that moves the coordinates of the input value before
 /// returning the output value from the source function.
@@ -111,6 +113,14 @@ where
         self.source
             .get([point[0] + self.x_translation, point[1] + self.y_translation])
     }
+
+    fn is_periodic(&self) -> Option<[f64; 2]> {
+        self.source.is_periodic()
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("TranslatePoint").set_children(vec![self.source.describe()])
+    }
 }
 
 impl<Source> NoiseFn<[f64; 3]> for TranslatePoint<Source>
@@ -124,6 +134,14 @@ where
             point[2] + self.z_translation,
         ])
     }
+
+    fn is_periodic(&self) -> Option<[f64; 2]> {
+        self.source.is_periodic()
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("TranslatePoint").set_children(vec![self.source.describe()])
+    }
 }
 
 impl<Source> NoiseFn<[f64; 4]> for TranslatePoint<Source>
@@ -138,4 +156,12 @@ where
             point[3] + self.u_translation,
         ])
     }
+
+    fn is_periodic(&self) -> Option<[f64; 2]> {
+        self.source.is_periodic()
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("TranslatePoint").set_children(vec![self.source.describe()])
+    }
 }
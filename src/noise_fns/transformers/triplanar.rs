@@ -0,0 +1,128 @@
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{NodeDesc, NoiseFn},
+};
+
+/// Noise function that textures a 3-dimensional surface with a 2-dimensional
+/// source function, blending the three axis-aligned projections of the
+/// surface point by its normal.
+///
+/// The source is sampled at the yz, zx and xy projections of the point, and
+/// each sample is weighted by the matching component of the surface normal
+/// raised to the power of `sharpness`. A surface facing along an axis only
+/// uses the projection onto the plane perpendicular to that axis, so the
+/// texture is never stretched, while surfaces in between get a smooth blend.
+///
+/// `get_with_normal` takes the normal of an arbitrary surface. The
+/// `NoiseFn<[f64; 3]>` implementation uses the direction of the point from
+/// the origin as the normal, which is exact for spheres centered on the
+/// origin, such as those sampled by `SphereMapBuilder`.
+pub struct Triplanar<'a> {
+    /// Outputs the value that is projected onto the surface.
+    pub source: &'a dyn NoiseFn<[f64; 2]>,
+
+    /// Exponent applied to the normal components before blending. Higher
+    /// values make the transitions between the projections narrower.
+    pub sharpness: f64,
+}
+
+impl<'a> Triplanar<'a> {
+    pub const DEFAULT_SHARPNESS: f64 = 4.0;
+
+    pub fn new(source: &'a dyn NoiseFn<[f64; 2]>) -> Self {
+        Self {
+            source,
+            sharpness: Self::DEFAULT_SHARPNESS,
+        }
+    }
+
+    pub fn set_sharpness(self, sharpness: f64) -> Self {
+        Self { sharpness, ..self }
+    }
+
+    /// Returns the value of the surface at `point`, where `normal` is the
+    /// surface normal at that point. The normal doesn't need to be
+    /// normalized.
+    pub fn get_with_normal(&self, point: [f64; 3], normal: [f64; 3]) -> f64 {
+        let weights = math::map3(normal, |n| n.abs().powf(self.sharpness));
+        let total = math::fold3(weights, |a, b| a + b);
+
+        if total <= 0.0 {
+            return 0.0;
+        }
+
+        let [x, y, z] = point;
+        let mut value = 0.0;
+
+        for &(weight, projection) in &[
+            (weights[0], [y, z]),
+            (weights[1], [z, x]),
+            (weights[2], [x, y]),
+        ] {
+            // Skip projections that don't contribute, so a normal along an axis
+            // yields exactly the value of a single projection.
+            if weight > 0.0 {
+                value += weight * self.source.get(projection);
+            }
+        }
+
+        value / total
+    }
+}
+
+impl<'a> NoiseFn<[f64; 3]> for Triplanar<'a> {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.get_with_normal(point, point)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Triplanar").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Perlin;
+
+    #[test]
+    fn axis_normals_use_single_projection() {
+        let perlin = Perlin::new();
+        let triplanar = Triplanar::new(&perlin);
+
+        for i in 0..20 {
+            let [x, y, z] = [i as f64 * 0.37 - 3.0, i as f64 * 0.91, 1.3 - i as f64];
+
+            assert_eq!(
+                triplanar.get_with_normal([x, y, z], [0.0, 0.0, 1.0]),
+                perlin.get([x, y])
+            );
+            assert_eq!(
+                triplanar.get_with_normal([x, y, z], [-2.0, 0.0, 0.0]),
+                perlin.get([y, z])
+            );
+            assert_eq!(
+                triplanar.get_with_normal([x, y, z], [0.0, 0.5, 0.0]),
+                perlin.get([z, x])
+            );
+        }
+    }
+
+    #[test]
+    fn diagonal_normal_blends_projections() {
+        let perlin = Perlin::new();
+        let triplanar = Triplanar::new(&perlin);
+        let [x, y, z] = [0.3, 1.7, -2.2];
+
+        let expected = (perlin.get([y, z]) + perlin.get([z, x]) + perlin.get([x, y])) / 3.0;
+        let value = triplanar.get_with_normal([x, y, z], [1.0, -1.0, 1.0]);
+        assert!((value - expected).abs() < 1e-12);
+
+        assert!(
+            (triplanar.get([x, x, x]) - triplanar.get_with_normal([x, x, x], [1.0; 3])).abs()
+                < 1e-12
+        );
+    }
+}
@@ -1,4 +1,9 @@
-use crate::noise_fns::{Fbm, MultiFractal, NoiseFn, Seedable};
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::{
+    math,
+    noise_fns::{Fbm, MultiFractal, NodeDesc, NoiseFn, Seedable},
+};
 
 /// Noise function that randomly displaces the input value before returning the
 /// output value from the source function.
@@ -23,6 +28,19 @@ pub struct Turbulence<Source> {
     /// Affects the roughness of the turbulence. Higher values are rougher.
     pub roughness: usize,
 
+    /// Largest distance the input value is moved along each axis. The
+    /// displacement along each axis is clamped to this magnitude, which keeps
+    /// a high power from pulling in features from far away. The default is
+    /// unlimited.
+    pub max_displacement: f64,
+
+    /// Smallest feature the displacement may contain. Octaves of the
+    /// displacement whose wavelength would fall below this size are dropped,
+    /// which band-limits the warp and keeps it from shimmering when animated
+    /// or sampled at a coarse resolution. The first octave is always kept.
+    /// The default of 0.0 keeps every octave.
+    pub min_feature_size: f64,
+
     seed: u32,
     x_distort_function: Fbm,
     y_distort_function: Fbm,
@@ -35,6 +53,8 @@ impl<Source> Turbulence<Source> {
     pub const DEFAULT_FREQUENCY: f64 = 1.0;
     pub const DEFAULT_POWER: f64 = 1.0;
     pub const DEFAULT_ROUGHNESS: usize = 3;
+    pub const DEFAULT_MAX_DISPLACEMENT: f64 = core::f64::INFINITY;
+    pub const DEFAULT_MIN_FEATURE_SIZE: f64 = 0.0;
 
     pub fn new(source: Source) -> Self {
         Self {
@@ -43,6 +63,8 @@ impl<Source> Turbulence<Source> {
             frequency: Self::DEFAULT_FREQUENCY,
             power: Self::DEFAULT_POWER,
             roughness: Self::DEFAULT_ROUGHNESS,
+            max_displacement: Self::DEFAULT_MAX_DISPLACEMENT,
+            min_feature_size: Self::DEFAULT_MIN_FEATURE_SIZE,
             x_distort_function: Fbm::new()
                 .set_seed(Self::DEFAULT_SEED)
                 .set_octaves(Self::DEFAULT_ROUGHNESS)
@@ -71,19 +93,57 @@ impl<Source> Turbulence<Source> {
             u_distort_function: self.u_distort_function.set_frequency(frequency),
             ..self
         }
+        .limit_octaves()
     }
 
     pub fn set_power(self, power: f64) -> Self {
         Self { power, ..self }
     }
 
+    pub fn set_max_displacement(self, max_displacement: f64) -> Self {
+        assert!(max_displacement >= 0.0);
+
+        Self {
+            max_displacement,
+            ..self
+        }
+    }
+
     pub fn set_roughness(self, roughness: usize) -> Self {
+        Self { roughness, ..self }.limit_octaves()
+    }
+
+    /// Sets the smallest feature size of the displacement. The number of
+    /// octaves depends on the frequency and roughness, which may be set
+    /// before or after.
+    pub fn set_min_feature_size(self, min_feature_size: f64) -> Self {
+        assert!(min_feature_size >= 0.0);
+
         Self {
-            roughness,
-            x_distort_function: self.x_distort_function.set_octaves(roughness),
-            y_distort_function: self.y_distort_function.set_octaves(roughness),
-            z_distort_function: self.z_distort_function.set_octaves(roughness),
-            u_distort_function: self.u_distort_function.set_octaves(roughness),
+            min_feature_size,
+            ..self
+        }
+        .limit_octaves()
+    }
+
+    /// Sets the octaves of the displacement functions to the roughness,
+    /// dropping the octaves whose frequency exceeds `1 / min_feature_size`.
+    fn limit_octaves(self) -> Self {
+        let max_frequency = 1.0 / self.min_feature_size;
+        let lacunarity = self.x_distort_function.lacunarity;
+        let mut octaves = self.roughness.min(1);
+        let mut frequency = self.frequency * lacunarity;
+
+        while octaves < self.roughness && frequency <= max_frequency {
+            octaves += 1;
+            frequency *= lacunarity;
+        }
+
+        Self {
+            x_distort_function: self.x_distort_function.set_octaves(octaves),
+            y_distort_function: self.y_distort_function.set_octaves(octaves),
+            z_distort_function: self.z_distort_function.set_octaves(octaves),
+            u_distort_function: self.u_distort_function.set_octaves(octaves),
             ..self
         }
     }
@@ -106,11 +166,13 @@ impl<Source> Seedable for Turbulence<Source> {
     }
 }
 
-impl<Source> NoiseFn<[f64; 2]> for Turbulence<Source>
-where
-    Source: NoiseFn<[f64; 2]>,
-{
-    fn get(&self, point: [f64; 2]) -> f64 {
+impl<Source> Turbulence<Source> {
+    /// Returns the offset that is added to `point` before sampling the source
+    /// function.
+    ///
+    /// Each component is the output of that axis' displacement fBm scaled by
+    /// `power`, clamped to `max_displacement`.
+    pub fn displacement2(&self, point: [f64; 2]) -> [f64; 2] {
         // First, create offsets based on the input values to keep the sampled
         // points from being near a integer boundary. This is a result of
         // using perlin noise, which returns zero at integer boundaries.
@@ -120,18 +182,14 @@ where
         let x1 = point[0] + 26519.0 / 65536.0;
         let y1 = point[1] + 18128.0 / 65536.0;
 
-        let x_distort = point[0] + (self.x_distort_function.get([x0, y0]) * self.power);
-        let y_distort = point[1] + (self.y_distort_function.get([x1, y1]) * self.power);
-
-        self.source.get([x_distort, y_distort])
+        [
+            self.displace(self.x_distort_function.get([x0, y0])),
+            self.displace(self.y_distort_function.get([x1, y1])),
+        ]
     }
-}
 
-impl<Source> NoiseFn<[f64; 3]> for Turbulence<Source>
-where
-    Source: NoiseFn<[f64; 3]>,
-{
-    fn get(&self, point: [f64; 3]) -> f64 {
+    /// The 3-dimensional version of `displacement2`.
+    pub fn displacement3(&self, point: [f64; 3]) -> [f64; 3] {
         // First, create offsets based on the input values to keep the sampled
         // points from being near a integer boundary. This is a result of
         // using perlin noise, which returns zero at integer boundaries.
@@ -147,19 +205,15 @@ where
         let y2 = point[1] + 11213.0 / 65536.0;
         let z2 = point[2] + 44845.0 / 65536.0;
 
-        let x_distort = point[0] + (self.x_distort_function.get([x0, y0, z0]) * self.power);
-        let y_distort = point[1] + (self.y_distort_function.get([x1, y1, z1]) * self.power);
-        let z_distort = point[2] + (self.z_distort_function.get([x2, y2, z2]) * self.power);
-
-        self.source.get([x_distort, y_distort, z_distort])
+        [
+            self.displace(self.x_distort_function.get([x0, y0, z0])),
+            self.displace(self.y_distort_function.get([x1, y1, z1])),
+            self.displace(self.z_distort_function.get([x2, y2, z2])),
+        ]
     }
-}
 
-impl<Source> NoiseFn<[f64; 4]> for Turbulence<Source>
-where
-    Source: NoiseFn<[f64; 4]>,
-{
-    fn get(&self, point: [f64; 4]) -> f64 {
+    /// The 4-dimensional version of `displacement2`.
+    pub fn displacement4(&self, point: [f64; 4]) -> [f64; 4] {
         // First, create offsets based on the input values to keep the sampled
         // points from being near a integer boundary. This is a result of
         // using perlin noise, which returns zero at integer boundaries.
@@ -183,12 +237,192 @@ where
         let z3 = point[2] + 12414.0 / 65536.0;
         let u3 = point[3] + 60943.0 / 65536.0;
 
-        let x_distort = point[0] + (self.x_distort_function.get([x0, y0, z0, u0]) * self.power);
-        let y_distort = point[1] + (self.y_distort_function.get([x1, y1, z1, u1]) * self.power);
-        let z_distort = point[2] + (self.z_distort_function.get([x2, y2, z2, u2]) * self.power);
-        let u_distort = point[3] + (self.u_distort_function.get([x3, y3, z3, u3]) * self.power);
+        [
+            self.displace(self.x_distort_function.get([x0, y0, z0, u0])),
+            self.displace(self.y_distort_function.get([x1, y1, z1, u1])),
+            self.displace(self.z_distort_function.get([x2, y2, z2, u2])),
+            self.displace(self.u_distort_function.get([x3, y3, z3, u3])),
+        ]
+    }
+
+    fn displace(&self, distortion: f64) -> f64 {
+        math::clamp(
+            distortion * self.power,
+            -self.max_displacement,
+            self.max_displacement,
+        )
+    }
+}
+
+impl<Source> NoiseFn<[f64; 2]> for Turbulence<Source>
+where
+    Source: NoiseFn<[f64; 2]>,
+{
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.source
+            .get(math::add2(point, self.displacement2(point)))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Turbulence").set_children(vec![self.source.describe()])
+    }
+}
 
+impl<Source> NoiseFn<[f64; 3]> for Turbulence<Source>
+where
+    Source: NoiseFn<[f64; 3]>,
+{
+    fn get(&self, point: [f64; 3]) -> f64 {
         self.source
-            .get([x_distort, y_distort, z_distort, u_distort])
+            .get(math::add3(point, self.displacement3(point)))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Turbulence").set_children(vec![self.source.describe()])
+    }
+}
+
+impl<Source> NoiseFn<[f64; 4]> for Turbulence<Source>
+where
+    Source: NoiseFn<[f64; 4]>,
+{
+    fn get(&self, point: [f64; 4]) -> f64 {
+        self.source
+            .get(math::add4(point, self.displacement4(point)))
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Turbulence").set_children(vec![self.source.describe()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Perlin;
+
+    fn points() -> Vec<[f64; 2]> {
+        (0..200)
+            .map(|i| [i as f64 * 0.137 - 9.0, i as f64 * 0.071 + 3.0])
+            .collect()
+    }
+
+    #[test]
+    fn power_scales_displacement() {
+        let source = Perlin::new();
+        let weak = Turbulence::new(&source).set_power(0.01);
+        let strong = Turbulence::new(&source).set_power(1.0);
+
+        let mean_change = |turbulence: &Turbulence<&Perlin>| {
+            points()
+                .iter()
+                .map(|&point| (turbulence.get(point) - source.get(point)).abs())
+                .sum::<f64>()
+                / 200.0
+        };
+
+        for point in points() {
+            let weak = weak.displacement2(point);
+            let strong = strong.displacement2(point);
+            assert!((strong[0] - weak[0] * 100.0).abs() < 1e-9);
+            assert!((strong[1] - weak[1] * 100.0).abs() < 1e-9);
+        }
+        assert!(mean_change(&strong) > mean_change(&weak) * 10.0);
+    }
+
+    #[test]
+    fn max_displacement_limits_warping() {
+        let source = Perlin::new();
+        let unlimited = Turbulence::new(&source).set_power(10.0);
+        let limited = unlimited.clone().set_max_displacement(0.01);
+
+        let mut clamped = 0;
+        for point in points() {
+            let displacement = limited.displacement2(point);
+            let original = unlimited.displacement2(point);
+
+            for axis in 0..2 {
+                assert!(displacement[axis].abs() <= 0.01);
+                if original[axis].abs() > 0.01 {
+                    assert_eq!(displacement[axis].abs(), 0.01);
+                    clamped += 1;
+                }
+            }
+            assert!((limited.get(point) - source.get(point)).abs() < 0.05);
+        }
+
+        assert!(clamped > 100);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn map_matches_point_query() {
+        use crate::utils::{NoiseMapBuilder, PlaneMapBuilder};
+
+        let source = Perlin::new();
+        let turbulence = Turbulence::new(&source).set_power(0.5);
+        let map = PlaneMapBuilder::new(&turbulence)
+            .set_size(1, 1)
+            .set_x_bounds(0.3, 1.3)
+            .set_y_bounds(-2.1, -1.1)
+            .build();
+
+        assert_eq!(map.get_value(0, 0), turbulence.get([0.3, -2.1, 0.0]));
+    }
+
+    #[test]
+    fn roughness_adds_fine_detail() {
+        let source = Perlin::new();
+
+        // Mean difference between displacements at closely spaced points.
+        let detail = |roughness| {
+            let turbulence = Turbulence::new(&source)
+                .set_roughness(roughness)
+                .set_frequency(0.5);
+            points()
+                .iter()
+                .map(|&point| {
+                    let a = turbulence.displacement2(point)[0];
+                    let b = turbulence.displacement2([point[0] + 0.01, point[1]])[0];
+                    (a - b).abs()
+                })
+                .sum::<f64>()
+        };
+
+        assert!(detail(6) > detail(1));
+    }
+
+    #[test]
+    fn min_feature_size_limits_fine_detail() {
+        let source = Perlin::new();
+        let turbulence = |min_feature_size| {
+            Turbulence::new(&source)
+                .set_roughness(6)
+                .set_min_feature_size(min_feature_size)
+        };
+
+        // Mean difference between the displacements of adjacent samples.
+        let detail = |turbulence: &Turbulence<&Perlin>| {
+            points()
+                .iter()
+                .map(|&point| {
+                    let a = turbulence.displacement2(point)[0];
+                    let b = turbulence.displacement2([point[0] + 0.01, point[1]])[0];
+                    (a - b).abs()
+                })
+                .sum::<f64>()
+        };
+
+        let unlimited = turbulence(0.0);
+        let fine = turbulence(0.2);
+        let coarse = turbulence(0.5);
+        assert_eq!(unlimited.x_distort_function.octaves, 6);
+        assert_eq!(fine.x_distort_function.octaves, 3);
+        assert_eq!(coarse.x_distort_function.octaves, 1);
+        assert!(detail(&fine) < detail(&unlimited));
+        assert!(detail(&coarse) < detail(&fine));
+
+        // The limit follows later changes to the frequency.
+        assert_eq!(coarse.set_frequency(0.5).x_distort_function.octaves, 2);
     }
 }
@@ -1,13 +1,31 @@
+use crate::math;
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+#[cfg(feature = "std")]
+use crate::utils::NoiseMap;
+use core::fmt;
 use rand::seq::SliceRandom;
 use rand::{
     distributions::{Distribution, Standard},
     Rng, SeedableRng,
 };
 use rand_xorshift::XorShiftRng;
-use std::fmt;
 
 const TABLE_SIZE: usize = 256;
 
+/// Method used to hash the integer lattice points of a noise function.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HashMode {
+    /// Looks the coordinates up in a shuffled table of 256 entries. This is
+    /// fast, but makes the noise repeat every 256 units along each axis.
+    Table,
+
+    /// Mixes the coordinates and the seed with an integer hash, so the noise
+    /// doesn't repeat within the range of the coordinates. This is slightly
+    /// slower than the table.
+    Integer,
+}
+
 /// A seed table, required by all noise functions.
 ///
 /// Table creation is expensive, so in most circumstances you'll only want to
@@ -15,6 +33,9 @@ const TABLE_SIZE: usize = 256;
 #[derive(Copy, Clone)]
 pub(crate) struct PermutationTable {
     values: [u8; TABLE_SIZE],
+    hash_mode: HashMode,
+    key: u64,
+    period: [usize; 4],
 }
 
 impl Distribution<PermutationTable> for Standard {
@@ -28,11 +49,15 @@ impl Distribution<PermutationTable> for Standard {
         // it won't matter, as Seed creation will usually be a one-time event.
         let mut perm_table = PermutationTable {
             values: [0; TABLE_SIZE],
+            hash_mode: HashMode::Table,
+            key: 0,
+            period: [0; 4],
         };
         let seq_it = seq.iter();
         for (x, y) in perm_table.values.iter_mut().zip(seq_it) {
             *x = *y
         }
+        perm_table.key = rng.gen();
         perm_table
     }
 }
@@ -41,39 +66,128 @@ impl PermutationTable {
     /// Deterministically generates a new permutation table based on a `u32` seed value.
     ///
     /// Internally this uses a `XorShiftRng`, but we don't really need to worry
-    /// about cryptographic security when working with procedural noise.
+    /// about cryptographic security when working with procedural noise. The
+    /// seed is expanded with SplitMix64 before seeding the generator, as
+    /// XorShift needs a well-mixed state to produce good tables for small
+    /// seeds such as 0, 1 and 2.
     pub fn new(seed: u32) -> Self {
+        let mut state = u64::from(seed);
         let mut real = [0; 16];
-        real[0] = 1;
-        for i in 1..4 {
-            real[i * 4] = seed as u8;
-            real[(i * 4) + 1] = (seed >> 8) as u8;
-            real[(i * 4) + 2] = (seed >> 16) as u8;
-            real[(i * 4) + 3] = (seed >> 24) as u8;
+        for chunk in real.chunks_mut(8) {
+            let value = math::splitmix64(&mut state);
+            for (i, byte) in chunk.iter_mut().enumerate() {
+                *byte = (value >> (i * 8)) as u8;
+            }
         }
         let mut rng: XorShiftRng = SeedableRng::from_seed(real);
         rng.gen()
     }
 
+    pub fn set_hash_mode(self, hash_mode: HashMode) -> Self {
+        Self { hash_mode, ..self }
+    }
+
+    pub fn hash_mode(&self) -> HashMode {
+        self.hash_mode
+    }
+
+    /// Sets the number of lattice points after which the hashes repeat along
+    /// each axis. A period of 0 leaves an axis unbounded.
+    pub fn set_period(self, period: [usize; 4]) -> Self {
+        Self { period, ..self }
+    }
+
+    pub fn period(&self) -> [usize; 4] {
+        self.period
+    }
+
+    fn wrap(&self, axis: usize, x: isize) -> isize {
+        let period = self.period[axis] as isize;
+        if period == 0 {
+            x
+        } else {
+            ((x % period) + period) % period
+        }
+    }
+
+    /// Hashes the lattice point `pos` into the same [0,255] range as the
+    /// table lookups, by feeding each coordinate through SplitMix64 in turn.
+    fn hash_integer(&self, pos: &[isize]) -> usize {
+        let mut hash = self.key;
+        for &coordinate in pos {
+            let mut state = hash ^ coordinate as u64;
+            hash = math::splitmix64(&mut state);
+        }
+        (hash & 0xff) as usize
+    }
+
     pub fn get1(&self, x: isize) -> usize {
+        let x = self.wrap(0, x);
+
+        if self.hash_mode == HashMode::Integer {
+            return self.hash_integer(&[x]);
+        }
+
         let x = (x & 0xff) as usize;
         self.values[x] as usize
     }
 
     pub fn get2(&self, pos: [isize; 2]) -> usize {
-        let y = (pos[1] & 0xff) as usize;
+        if self.hash_mode == HashMode::Integer {
+            return self.hash_integer(&[self.wrap(0, pos[0]), self.wrap(1, pos[1])]);
+        }
+
+        let y = (self.wrap(1, pos[1]) & 0xff) as usize;
         self.values[self.get1(pos[0]) ^ y] as usize
     }
 
     pub fn get3(&self, pos: [isize; 3]) -> usize {
-        let z = (pos[2] & 0xff) as usize;
+        if self.hash_mode == HashMode::Integer {
+            return self.hash_integer(&[
+                self.wrap(0, pos[0]),
+                self.wrap(1, pos[1]),
+                self.wrap(2, pos[2]),
+            ]);
+        }
+
+        let z = (self.wrap(2, pos[2]) & 0xff) as usize;
         self.values[self.get2([pos[0], pos[1]]) ^ z] as usize
     }
 
     pub fn get4(&self, pos: [isize; 4]) -> usize {
-        let w = (pos[3] & 0xff) as usize;
+        if self.hash_mode == HashMode::Integer {
+            return self.hash_integer(&[
+                self.wrap(0, pos[0]),
+                self.wrap(1, pos[1]),
+                self.wrap(2, pos[2]),
+                self.wrap(3, pos[3]),
+            ]);
+        }
+
+        let w = (self.wrap(3, pos[3]) & 0xff) as usize;
         self.values[self.get3([pos[0], pos[1], pos[2]]) ^ w] as usize
     }
+
+    /// Renders the 2D hash of the table over one full period.
+    #[cfg(feature = "std")]
+    pub fn debug_image(&self) -> NoiseMap {
+        NoiseMap::from_fn(TABLE_SIZE, TABLE_SIZE, |x, y| {
+            let hash = self.get2([x as isize, y as isize]);
+            hash as f64 / (TABLE_SIZE - 1) as f64 * 2.0 - 1.0
+        })
+    }
+}
+
+/// Renders the permutation table generated for `seed` as a noise map, to help
+/// diagnose biased or structured tables.
+///
+/// The map is 256 × 256 cells, with the cell at (x, y) holding the hash of the
+/// lattice point `[x, y]` scaled from [0,255] to [-1,1]. A good table looks
+/// like uniform white noise; visible stripes, blocks or gradients indicate a
+/// poorly shuffled table.
+#[cfg(feature = "std")]
+pub fn permutation_table_image(seed: u32) -> NoiseMap {
+    PermutationTable::new(seed).debug_image()
 }
 
 impl fmt::Debug for PermutationTable {
@@ -84,12 +198,13 @@ impl fmt::Debug for PermutationTable {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
     use crate::{NoiseFn, Perlin, Seedable};
-    use rand::random;
 
+    #[cfg(feature = "std")]
     #[test]
     fn test_random_seed() {
-        let perlin = Perlin::new().set_seed(random());
+        let perlin = Perlin::new().set_seed(rand::random());
         let _ = perlin.get([1.0, 2.0, 3.0]);
     }
 
@@ -98,4 +213,49 @@ mod tests {
         let perlin = Perlin::new();
         let _ = perlin.get([-1.0, 2.0, 3.0]);
     }
+
+    #[test]
+    fn small_seeds_are_well_shuffled() {
+        let tables: Vec<_> = (0..3).map(PermutationTable::new).collect();
+
+        for (i, table) in tables.iter().enumerate() {
+            let values = &table.values;
+
+            // A uniformly shuffled table has about two neighboring entries that
+            // differ by one and about one fixed point.
+            let adjacent = values
+                .windows(2)
+                .filter(|pair| (i32::from(pair[0]) - i32::from(pair[1])).abs() == 1)
+                .count();
+            let fixed = (0..TABLE_SIZE).filter(|&x| values[x] as usize == x).count();
+            assert!(adjacent < 8, "seed {}: {} adjacent entries", i, adjacent);
+            assert!(fixed < 6, "seed {}: {} fixed points", i, fixed);
+
+            // Roughly half of the first half of the table should be low values.
+            let low = values[..TABLE_SIZE / 2]
+                .iter()
+                .filter(|&&x| (x as usize) < TABLE_SIZE / 2)
+                .count();
+            assert!(low > 48 && low < 80, "seed {}: {} low values", i, low);
+
+            // Tables for neighboring seeds should be unrelated.
+            let next = &tables[(i + 1) % tables.len()].values;
+            let shared = (0..TABLE_SIZE).filter(|&x| values[x] == next[x]).count();
+            assert!(shared < 6, "seed {}: {} shared entries", i, shared);
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn debug_image() {
+        let image = PermutationTable::new(0).debug_image();
+        let other = permutation_table_image(1);
+
+        assert_eq!(image.size(), (TABLE_SIZE, TABLE_SIZE));
+        assert_eq!(other.size(), (TABLE_SIZE, TABLE_SIZE));
+        assert!(!image.approx_eq(&other, 0.0));
+
+        // Every row is a permutation, so the mean is exactly zero.
+        assert!(image.stats().mean.abs() < 1e-12);
+    }
 }
@@ -0,0 +1,112 @@
+//! Ready-made noise functions configured for common uses.
+//!
+//! Each preset combines several of the building blocks in this crate into a
+//! single noise function that owns everything it needs. They are meant as
+//! starting points: the fields of the returned functions are public, so any
+//! part of the configuration can be adjusted afterwards.
+
+#[cfg(not(feature = "std"))]
+use crate::no_std::*;
+use crate::noise_fns::{palette_entry, NodeDesc, NoiseFn, RangeFunction, Seedable, Worley};
+
+/// Stylized city blocks made of flat-topped regions of different heights.
+///
+/// The regions are the cells of a Worley function that uses the `Manhattan`
+/// range function and a low jitter, which keeps the seed points close to a
+/// regular grid and produces blocky, diamond-shaped cells. Like `RegionFill`,
+/// each cell is filled with an entry of `heights`, so the output is constant
+/// within a block and only changes across block boundaries.
+///
+/// The output lies in the range of `heights`, which is [-1,1] by default.
+#[derive(Clone, Debug)]
+pub struct Cityscape {
+    /// Worley function that divides the input space into blocks.
+    pub worley: Worley,
+
+    /// Heights the blocks are filled with.
+    pub heights: Vec<f64>,
+}
+
+impl Cityscape {
+    pub const DEFAULT_JITTER: f64 = 0.2;
+    pub const DEFAULT_HEIGHTS: [f64; 8] = [-1.0, -0.75, -0.5, -0.25, 0.0, 0.3, 0.65, 1.0];
+
+    fn height(&self, cell_id: usize) -> f64 {
+        palette_entry(&self.heights, cell_id)
+    }
+}
+
+/// Returns a `Cityscape` with the default block heights and jitter.
+pub fn cityscape() -> Cityscape {
+    Cityscape {
+        worley: Worley::new()
+            .set_range_function(RangeFunction::Manhattan)
+            .set_jitter(Cityscape::DEFAULT_JITTER),
+        heights: Cityscape::DEFAULT_HEIGHTS.to_vec(),
+    }
+}
+
+impl NoiseFn<[f64; 2]> for Cityscape {
+    fn get(&self, point: [f64; 2]) -> f64 {
+        self.height(self.worley.sample2(point).cell_id)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Cityscape")
+    }
+}
+
+impl NoiseFn<[f64; 3]> for Cityscape {
+    fn get(&self, point: [f64; 3]) -> f64 {
+        self.height(self.worley.sample3(point).cell_id)
+    }
+
+    fn describe(&self) -> NodeDesc {
+        NodeDesc::new("Cityscape")
+    }
+}
+
+impl Seedable for Cityscape {
+    fn set_seed(self, seed: u32) -> Self {
+        Self {
+            worley: self.worley.set_seed(seed),
+            ..self
+        }
+    }
+
+    fn seed(&self) -> u32 {
+        self.worley.seed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::RegionFill;
+
+    #[test]
+    fn cityscape_is_blocky_and_bounded() {
+        let city = cityscape().set_seed(7);
+        let regions = RegionFill::new(&city.worley, city.heights.clone());
+
+        let samples = 1000;
+        let mut changes = 0;
+        let mut previous = None;
+        for i in 0..samples {
+            let point = [i as f64 * 0.01 - 5.0, 0.37];
+            let value = city.get(point);
+
+            assert_eq!(value, regions.get(point));
+            assert!((-1.0..=1.0).contains(&value));
+            match previous {
+                Some(previous) if previous != value => changes += 1,
+                _ => {}
+            }
+            previous = Some(value);
+        }
+
+        // The line crosses about ten blocks, and each one is a single flat
+        // run of values.
+        assert!(changes > 2 && changes < samples / 20, "{} changes", changes);
+    }
+}
@@ -1,13 +1,31 @@
 pub use self::color_gradient::*;
+pub use self::disk_cache::*;
+pub use self::downsampled::*;
+pub use self::estimate_range::*;
+pub use self::graph::*;
 #[cfg(feature = "image")]
 pub use self::image_renderer::*;
 pub use self::noise_image::*;
 pub use self::noise_map::*;
 pub use self::noise_map_builder::*;
+pub use self::noise_volume::*;
+#[cfg(feature = "image")]
+pub use self::pack_channels::*;
+pub use self::seed_preview::*;
+pub use self::tiled_graph_sampler::*;
 
 mod color_gradient;
+mod disk_cache;
+mod downsampled;
+mod estimate_range;
+mod graph;
 #[cfg(feature = "image")]
 mod image_renderer;
 mod noise_image;
 mod noise_map;
 mod noise_map_builder;
+mod noise_volume;
+#[cfg(feature = "image")]
+mod pack_channels;
+mod seed_preview;
+mod tiled_graph_sampler;
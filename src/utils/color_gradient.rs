@@ -84,13 +84,20 @@ impl ColorGradient {
             .add_gradient_point(1.0, [255, 0, 0, 255])
     }
 
-    pub fn get_color(&self, pos: f64) -> Color {
+    /// Returns the color of the gradient at `value`, linearly interpolated
+    /// between the two nearest control points.
+    ///
+    /// Values outside of the range of the control points are clamped, so they
+    /// get the color of the first or last control point.
+    ///
+    /// Panics if the gradient has fewer than two control points.
+    pub fn sample(&self, value: f64) -> Color {
         // confirm that there's at least 2 control points in the vector.
         assert!(self.gradient_points.len() >= 2);
 
         // we need to clamp the value to the range of pos in the gradient.
         let clamped_pos = math::clamp(
-            pos,
+            value,
             self.gradient_points[0].pos,
             self.gradient_points[self.gradient_points.len() - 1].pos,
         );
@@ -106,7 +113,7 @@ impl ColorGradient {
         if index < 1 {
             println!(
                 "index_pos in curve was less than 1! source value was {}",
-                pos
+                value
             );
         }
 
@@ -126,7 +133,7 @@ impl ColorGradient {
         // Compute the alpha value used for linear interpolation
         let input0 = self.gradient_points[index1].pos;
         let input1 = self.gradient_points[index2].pos;
-        let alpha = (pos - input0) / (input1 - input0);
+        let alpha = (clamped_pos - input0) / (input1 - input0);
 
         // Now perform the linear interpolation and return.
         linerp_color(
@@ -135,6 +142,11 @@ impl ColorGradient {
             alpha,
         )
     }
+
+    /// Same as `sample`.
+    pub fn get_color(&self, pos: f64) -> Color {
+        self.sample(pos)
+    }
 }
 
 fn blend_channels(channel0: u8, channel1: u8, alpha: f64) -> u8 {
@@ -206,4 +218,22 @@ mod tests {
 
         assert_eq!([127, 127, 127, 127], gradient.get_color(0.5));
     }
+
+    #[test]
+    fn sample() {
+        let gradient = ColorGradient::new()
+            .clear_gradient()
+            .add_gradient_point(-1.0, [0, 0, 0, 255])
+            .add_gradient_point(0.0, [200, 100, 0, 255])
+            .add_gradient_point(1.0, [200, 100, 50, 255]);
+
+        assert_eq!(gradient.sample(0.0), [200, 100, 0, 255]);
+        assert_eq!(gradient.sample(1.0), [200, 100, 50, 255]);
+        assert_eq!(gradient.sample(-0.5), [100, 50, 0, 255]);
+
+        // Values outside of the control points get the color of the nearest
+        // endpoint.
+        assert_eq!(gradient.sample(-3.0), [0, 0, 0, 255]);
+        assert_eq!(gradient.sample(3.0), [200, 100, 50, 255]);
+    }
 }
@@ -0,0 +1,172 @@
+use crate::utils::{noise_map::NoiseMap, noise_map_builder::NoiseMapBuilder};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+/// Builds a noise map once and keeps it on disk for later runs.
+///
+/// The first call to `build` builds the map with the wrapped builder and
+/// writes it to `<cache_dir>/<id>.nmap` in the format of
+/// `NoiseMap::write_bin`. Later calls, including those from other runs of the
+/// program, read the file back instead of evaluating the source function
+/// again, which pays off for expensive noise graphs.
+///
+/// The cache is keyed only by the id, so changing the graph or the builder
+/// settings requires a new id or a call to `clear`.
+pub struct DiskCache<'a, B>
+where
+    B: NoiseMapBuilder<'a>,
+{
+    /// Builds the map when it is not cached yet.
+    pub builder: &'a B,
+
+    /// Identifies the cached map. Used as the file name, so it should only
+    /// contain characters that are valid in file names.
+    pub id: String,
+
+    /// Directory the cached maps are stored in. Default is `noise_cache`.
+    pub cache_dir: PathBuf,
+}
+
+impl<'a, B> DiskCache<'a, B>
+where
+    B: NoiseMapBuilder<'a>,
+{
+    pub const DEFAULT_CACHE_DIR: &'static str = "noise_cache";
+
+    pub fn new(builder: &'a B, id: &str) -> Self {
+        Self {
+            builder,
+            id: id.to_owned(),
+            cache_dir: PathBuf::from(Self::DEFAULT_CACHE_DIR),
+        }
+    }
+
+    pub fn set_cache_dir<P: AsRef<Path>>(self, cache_dir: P) -> Self {
+        Self {
+            cache_dir: cache_dir.as_ref().to_path_buf(),
+            ..self
+        }
+    }
+
+    /// Returns the path of the cache file.
+    pub fn path(&self) -> PathBuf {
+        self.cache_dir.join(format!("{}.nmap", self.id))
+    }
+
+    /// Returns whether the map has been written to the cache.
+    pub fn is_cached(&self) -> bool {
+        self.path().is_file()
+    }
+
+    /// Returns the cached map, building and caching it first if needed.
+    ///
+    /// Creates the cache directory if it does not exist. The map is written to
+    /// a temporary file that replaces the cache file once complete, so an
+    /// interrupted run never leaves a partial cache file behind. A cache file
+    /// that is truncated or not in the expected format is rebuilt. Returns an
+    /// error if the cache file cannot be read or written.
+    pub fn build(&self) -> io::Result<NoiseMap> {
+        let path = self.path();
+
+        if path.is_file() {
+            match NoiseMap::read_bin(&path) {
+                Err(ref error)
+                    if error.kind() == io::ErrorKind::InvalidData
+                        || error.kind() == io::ErrorKind::UnexpectedEof => {}
+                result => return result,
+            }
+        }
+
+        let map = self.builder.build();
+        fs::create_dir_all(&self.cache_dir)?;
+
+        // Include the process id so concurrent runs don't write to the same
+        // temporary file.
+        let temp_path = self
+            .cache_dir
+            .join(format!("{}.nmap.{}.tmp", self.id, process::id()));
+        if let Err(error) = map
+            .write_bin(&temp_path)
+            .and_then(|()| fs::rename(&temp_path, &path))
+        {
+            let _ = fs::remove_file(&temp_path);
+            return Err(error);
+        }
+
+        Ok(map)
+    }
+
+    /// Removes the cached map, so the next call to `build` rebuilds it.
+    pub fn clear(&self) -> io::Result<()> {
+        match fs::remove_file(self.path()) {
+            Err(ref error) if error.kind() == io::ErrorKind::NotFound => Ok(()),
+            result => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        noise_fns::{Constant, Perlin},
+        utils::PlaneMapBuilder,
+    };
+
+    #[test]
+    fn second_build_reads_from_disk() {
+        let dir = std::env::temp_dir().join("noise_disk_cache_test");
+        let perlin = Perlin::new();
+        let builder = PlaneMapBuilder::new(&perlin).set_size(16, 16);
+        let cache = DiskCache::new(&builder, "perlin").set_cache_dir(&dir);
+        cache.clear().unwrap();
+
+        assert!(!cache.is_cached());
+        let built = cache.build().unwrap();
+        assert!(cache.is_cached());
+
+        // A cache with the same id reads the stored map instead of building
+        // from its own, different source.
+        let constant = Constant::new(0.5);
+        let other = PlaneMapBuilder::new(&constant).set_size(16, 16);
+        let cached = DiskCache::new(&other, "perlin")
+            .set_cache_dir(&dir)
+            .build()
+            .unwrap();
+        assert!(cached.approx_eq(&built, 0.0));
+        assert!(cached.approx_eq(&builder.build(), 0.0));
+
+        cache.clear().unwrap();
+        assert!(!cache.is_cached());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    #[test]
+    fn rebuilds_damaged_cache_file() {
+        let dir = std::env::temp_dir().join("noise_disk_cache_damaged_test");
+        let perlin = Perlin::new();
+        let builder = PlaneMapBuilder::new(&perlin).set_size(16, 16);
+        let cache = DiskCache::new(&builder, "perlin").set_cache_dir(&dir);
+        let built = cache.build().unwrap();
+
+        // Only the finished cache file is left in the directory.
+        let entries: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(entries, vec![cache.path()]);
+
+        // A cache file cut short, as by a crash while writing it, is rebuilt.
+        let bytes = fs::read(cache.path()).unwrap();
+        fs::write(cache.path(), &bytes[..bytes.len() / 2]).unwrap();
+        assert!(cache.build().unwrap().approx_eq(&built, 0.0));
+        assert_eq!(fs::read(cache.path()).unwrap(), bytes);
+
+        fs::write(cache.path(), b"garbage").unwrap();
+        assert!(cache.build().unwrap().approx_eq(&built, 0.0));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,145 @@
+use crate::{
+    math::interpolate,
+    utils::{
+        noise_map::NoiseMap,
+        noise_map_builder::{NoiseMapBuilder, PlaneMapBuilder},
+    },
+};
+
+/// Builds the map of a `PlaneMapBuilder` from a coarser grid of samples.
+///
+/// Only every `factor`th cell along each axis, plus the last row and column,
+/// is evaluated with the builder. The cells in between are bilinearly
+/// interpolated from those samples. This evaluates the source function about
+/// `factor^2` times less often, which trades detail finer than the coarse
+/// grid for speed on expensive noise graphs. A factor of 1 builds the same
+/// map as the builder itself.
+pub struct Downsampled<'a, 'b> {
+    /// Builder whose map is approximated.
+    pub builder: &'b PlaneMapBuilder<'a>,
+
+    /// Spacing of the evaluated cells. Default is 2.
+    pub factor: usize,
+}
+
+impl<'a, 'b> Downsampled<'a, 'b> {
+    pub const DEFAULT_FACTOR: usize = 2;
+
+    pub fn new(builder: &'b PlaneMapBuilder<'a>) -> Self {
+        Self {
+            builder,
+            factor: Self::DEFAULT_FACTOR,
+        }
+    }
+
+    /// Panics if `factor` is 0.
+    pub fn set_factor(self, factor: usize) -> Self {
+        assert!(factor > 0, "factor must be at least 1");
+
+        Self { factor, ..self }
+    }
+
+    pub fn build(&self) -> NoiseMap {
+        let (width, height) = self.builder.size();
+        if width == 0 || height == 0 {
+            return NoiseMap::new(width, height);
+        }
+
+        let columns = self.positions(width);
+        let rows = self.positions(height);
+        let samples: Vec<Vec<f64>> = rows
+            .iter()
+            .map(|&y| columns.iter().map(|&x| self.builder.get_at(x, y)).collect())
+            .collect();
+
+        NoiseMap::from_fn(width, height, |x, y| {
+            let (column, x_alpha) = self.locate(&columns, x);
+            let (row, y_alpha) = self.locate(&rows, y);
+            let sample = |row: usize, column: usize| samples[row][column];
+
+            let lerp_row = |row: usize| {
+                if x_alpha == 0.0 {
+                    sample(row, column)
+                } else {
+                    interpolate::linear(sample(row, column), sample(row, column + 1), x_alpha)
+                }
+            };
+
+            if y_alpha == 0.0 {
+                lerp_row(row)
+            } else {
+                interpolate::linear(lerp_row(row), lerp_row(row + 1), y_alpha)
+            }
+        })
+    }
+
+    // Indices of the evaluated cells along an axis of `len` cells.
+    fn positions(&self, len: usize) -> Vec<usize> {
+        let mut positions: Vec<usize> = (0..len).step_by(self.factor).collect();
+        if positions.last() != Some(&(len - 1)) {
+            positions.push(len - 1);
+        }
+
+        positions
+    }
+
+    // Index of the evaluated cell at or before `index`, and how far `index`
+    // lies towards the next evaluated cell.
+    fn locate(&self, positions: &[usize], index: usize) -> (usize, f64) {
+        let i = (index / self.factor).min(positions.len().saturating_sub(2));
+        if index == positions[i] {
+            return (i, 0.0);
+        }
+
+        let (start, end) = (positions[i], positions[i + 1]);
+        if index == end {
+            return (i + 1, 0.0);
+        }
+
+        (i, (index - start) as f64 / (end - start) as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Perlin;
+
+    fn roughness(map: &NoiseMap) -> f64 {
+        let (width, height) = map.size();
+        let mut sum = 0.0;
+        for y in 0..height {
+            for x in 1..width - 1 {
+                let second =
+                    map.get_value(x - 1, y) - 2.0 * map.get_value(x, y) + map.get_value(x + 1, y);
+                sum += second.abs();
+            }
+        }
+
+        sum
+    }
+
+    #[test]
+    fn approximates_builder() {
+        let perlin = Perlin::new();
+        let builder = PlaneMapBuilder::new(&perlin)
+            .set_size(33, 20)
+            .set_frequency(2.0);
+        let direct = builder.build();
+
+        let same = Downsampled::new(&builder).set_factor(1).build();
+        assert!(same.approx_eq(&direct, 0.0));
+
+        let coarse = Downsampled::new(&builder).set_factor(2).build();
+        assert_eq!(coarse.size(), (33, 20));
+        assert_eq!(coarse.get_value(4, 6), direct.get_value(4, 6));
+        assert_eq!(coarse.get_value(32, 19), direct.get_value(32, 19));
+        assert!(roughness(&coarse) < roughness(&direct));
+
+        let error = (0..20)
+            .flat_map(|y| (0..33).map(move |x| (x, y)))
+            .map(|(x, y)| (coarse.get_value(x, y) - direct.get_value(x, y)).abs())
+            .fold(0.0, f64::max);
+        assert!(error < 0.2, "error {}", error);
+    }
+}
@@ -0,0 +1,56 @@
+use crate::{math::CellRng, noise_fns::NoiseFn};
+
+// Half the side length of the square the sample points are drawn from. Large
+// enough to cover many features of a function at the usual frequencies.
+const SAMPLE_EXTENT: f64 = 1000.0;
+
+/// Estimates the output range of `source` by evaluating it at `samples`
+/// pseudo-random points and returning the smallest and largest values seen.
+///
+/// The points are spread uniformly over a square centered on the origin with
+/// a side length of 2000.0, and are the same for every call, so the estimate
+/// is deterministic. This is a practical way to find the bounds to pass to a
+/// `ScaleBias` or a color gradient when the analytic range of a composed graph
+/// is unknown. The observed range can only be narrower than the true range,
+/// and gets closer to it as `samples` grows.
+///
+/// Panics if `samples` is 0.
+pub fn estimate_range(source: &dyn NoiseFn<[f64; 2]>, samples: usize) -> (f64, f64) {
+    assert!(samples > 0, "at least one sample is required");
+
+    let mut rng = CellRng::new(0, &[]);
+    let mut coordinate = || (rng.next().unwrap() * 2.0 - 1.0) * SAMPLE_EXTENT;
+
+    let mut min = std::f64::INFINITY;
+    let mut max = std::f64::NEG_INFINITY;
+    for _ in 0..samples {
+        let value = source.get([coordinate(), coordinate()]);
+
+        min = min.min(value);
+        max = max.max(value);
+    }
+
+    (min, max)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Constant, Perlin, ScaleBias};
+
+    #[test]
+    fn perlin_range_is_wide_and_bounded() {
+        let perlin = Perlin::new();
+        let (min, max) = estimate_range(&perlin, 10_000);
+
+        assert!(min >= -1.0 && max <= 1.0);
+        assert!(min < -0.5 && max > 0.5, "range {} to {}", min, max);
+
+        let scaled = ScaleBias::new(&perlin).set_scale(3.0).set_bias(2.0);
+        let (scaled_min, scaled_max) = estimate_range(&scaled, 10_000);
+        assert!((scaled_min - (min * 3.0 + 2.0)).abs() < 1e-12);
+        assert!((scaled_max - (max * 3.0 + 2.0)).abs() < 1e-12);
+
+        assert_eq!(estimate_range(&Constant::new(0.25), 10), (0.25, 0.25));
+    }
+}
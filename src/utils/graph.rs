@@ -0,0 +1,114 @@
+use crate::noise_fns::NodeDesc;
+
+/// Renders a graph of noise functions described by `NoiseFn::describe` in the
+/// Graphviz dot format.
+///
+/// Each node is labeled with the name of its noise function and has an edge
+/// to each of its sources. Functions used as sources more than once show up
+/// as separate nodes.
+pub fn graph_to_dot(root: &NodeDesc) -> String {
+    fn write_node(node: &NodeDesc, next_id: &mut usize, dot: &mut String) -> usize {
+        let id = *next_id;
+        *next_id += 1;
+
+        let label = node.name.replace('\\', "\\\\").replace('"', "\\\"");
+        dot.push_str(&format!("    n{} [label=\"{}\"];\n", id, label));
+
+        for child in &node.children {
+            let child_id = write_node(child, next_id, dot);
+            dot.push_str(&format!("    n{} -> n{};\n", id, child_id));
+        }
+
+        id
+    }
+
+    let mut dot = String::from("digraph noise {\n");
+    write_node(root, &mut 0, &mut dot);
+    dot.push_str("}\n");
+
+    dot
+}
+
+/// Returns the number of nodes on the longest path from `root` to a source
+/// that has no sources of its own, counting both ends.
+///
+/// Evaluating a graph recurses once per level, so very deep graphs, such as
+/// long chains of combiners built in a loop, can overflow the stack. Checking
+/// the depth of `NoiseFn::describe` first allows rejecting or restructuring
+/// them, for example by balancing a chain of `Add`s into a tree.
+pub fn graph_depth(root: &NodeDesc) -> usize {
+    1 + root.children.iter().map(graph_depth).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{
+        Add, Constant, Fbm, NoiseFn, Perlin, ScaleBias, Select, Turbulence, Worley,
+    };
+
+    fn chain_depth(links: usize, source: &dyn NoiseFn<[f64; 2]>) -> usize {
+        if links == 0 {
+            return graph_depth(&source.describe());
+        }
+
+        let constant = Constant::new(1.0);
+        chain_depth(links - 1, &Add::new(source, &constant))
+    }
+
+    #[test]
+    fn describe_add() {
+        let perlin = Perlin::new();
+        let constant = Constant::new(0.5);
+        let add: Add<[f64; 2]> = Add::new(&perlin, &constant);
+
+        let desc = add.describe();
+        assert_eq!(desc.node_count(), 3);
+        assert_eq!(desc.name, "Add");
+        assert_eq!(desc.children[0], NodeDesc::new("Perlin"));
+        assert_eq!(desc.children[1], NodeDesc::new("Constant"));
+
+        assert_eq!(
+            graph_to_dot(&desc),
+            "digraph noise {\n    n0 [label=\"Add\"];\n    n1 [label=\"Perlin\"];\n    n0 -> n1;\n    n2 [label=\"Constant\"];\n    n0 -> n2;\n}\n"
+        );
+    }
+
+    #[test]
+    fn describe_modifiers_and_selectors() {
+        let fbm = Fbm::new();
+        let worley = Worley::new();
+        let scaled = ScaleBias::new(&worley).set_scale(0.5);
+        let control = Turbulence::new(Perlin::new());
+        let select: Select<[f64; 2]> = Select::new(&fbm, &scaled, &control);
+
+        let desc = select.describe();
+        assert_eq!(desc.name, "Select");
+        assert_eq!(desc.children[0], NodeDesc::new("Fbm"));
+        assert_eq!(
+            desc.children[1],
+            NodeDesc::new("ScaleBias").set_children(vec![NodeDesc::new("Worley")])
+        );
+        assert_eq!(
+            desc.children[2],
+            NodeDesc::new("Turbulence").set_children(vec![NodeDesc::new("Perlin")])
+        );
+        assert_eq!(desc.node_count(), 6);
+    }
+
+    #[test]
+    fn depth_of_add_chain() {
+        assert_eq!(graph_depth(&NodeDesc::new("Perlin")), 1);
+        assert_eq!(chain_depth(100, &Perlin::new()), 101);
+    }
+
+    #[test]
+    fn depth_of_modifier_chain() {
+        let perlin = Perlin::new();
+        let scaled: ScaleBias<[f64; 2]> = ScaleBias::new(&perlin).set_scale(0.5);
+        let turbulence = Turbulence::new(&scaled);
+        let rescaled: ScaleBias<[f64; 2]> = ScaleBias::new(&turbulence).set_bias(0.25);
+
+        assert_eq!(graph_depth(&rescaled.describe()), 4);
+    }
+}
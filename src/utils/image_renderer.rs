@@ -1,7 +1,7 @@
 use crate::math::interpolate;
-use std::{self, f64::consts::SQRT_2};
+use std::{self, f64::consts::SQRT_2, fs, path::Path};
 
-use super::{color_gradient::*, noise_image::*, noise_map::*};
+use super::{color_gradient::*, noise_image::*, noise_map::*, noise_volume::*};
 
 pub struct ImageRenderer {
     // The color gradient used to specify the image colors.
@@ -127,7 +127,7 @@ impl ImageRenderer {
             for x in 0..width {
                 let point = noise_map.get_value(x, y);
 
-                let source_color = self.gradient.get_color(point);
+                let source_color = self.gradient.sample(point);
 
                 let mut light_intensity;
 
@@ -225,6 +225,25 @@ impl ImageRenderer {
         ]
     }
 
+    /// Renders each z-slice of `volume` and writes it to `dir` as
+    /// `slice_000.png`, `slice_001.png` and so on, creating `dir` if needed.
+    pub fn render_volume<P: AsRef<Path>>(
+        &mut self,
+        volume: &NoiseVolume,
+        dir: P,
+    ) -> image::ImageResult<()> {
+        let dir = dir.as_ref();
+        fs::create_dir_all(dir)?;
+
+        let (_, _, depth) = volume.size();
+        for z in 0..depth {
+            let image = self.render(&volume.slice_z(z));
+            image.write_to_path(dir.join(format!("slice_{:03}.png", z)))?;
+        }
+
+        Ok(())
+    }
+
     pub fn render_with_background(
         &mut self,
         noise_map: &NoiseMap,
@@ -238,7 +257,7 @@ impl ImageRenderer {
         for y in 0..height {
             for x in 0..width {
                 let point = noise_map.get_value(x, y);
-                let source_color = self.gradient.get_color(point);
+                let source_color = self.gradient.sample(point);
 
                 let mut light_intensity;
 
@@ -502,4 +521,25 @@ mod tests {
         assert_eq!([0.0; 4], u8_array_to_f64_array([0; 4]));
         assert_eq!([1.0; 4], u8_array_to_f64_array([255; 4]));
     }
+
+    #[test]
+    fn render_volume_writes_each_slice() {
+        let dir = std::env::temp_dir().join("noise_render_volume_test");
+        let _ = fs::remove_dir_all(&dir);
+
+        let volume = NoiseVolume::new(4, 3, 5);
+        ImageRenderer::new().render_volume(&volume, &dir).unwrap();
+
+        let mut names: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        names.sort();
+
+        assert_eq!(names.len(), 5);
+        assert_eq!(names[0], "slice_000.png");
+        assert_eq!(names[4], "slice_004.png");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
@@ -102,6 +102,15 @@ impl NoiseImage {
         let directory: String = "example_images/".to_owned();
         let file_path = directory + filename;
 
+        let _ = self.write_to_path(file_path);
+
+        println!("\nFinished generating {}", filename);
+    }
+
+    /// Writes the image to `path`, with the format determined by the path's
+    /// extension.
+    #[cfg(feature = "image")]
+    pub fn write_to_path<P: AsRef<Path>>(&self, path: P) -> image::ImageResult<()> {
         // collect the values from the map vector into an array
         let (width, height) = self.size;
         let mut result = Vec::with_capacity(width * height);
@@ -112,15 +121,13 @@ impl NoiseImage {
             }
         }
 
-        let _ = image::save_buffer(
-            &Path::new(&file_path),
+        image::save_buffer(
+            path.as_ref(),
             &*result,
             self.size.0 as u32,
             self.size.1 as u32,
             image::ColorType::Rgba8,
-        );
-
-        println!("\nFinished generating {}", filename);
+        )
     }
 }
 
@@ -1,14 +1,35 @@
 #[cfg(feature = "image")]
-use crate::math;
-#[cfg(feature = "image")]
-use std::{self, path::Path};
+use super::{ColorGradient, ImageRenderer};
+use crate::{
+    math::{self, interpolate},
+    noise_fns::{resolve_index, EdgeMode, NoiseFn},
+};
+use std::{
+    self,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
 
 const RASTER_MAX_WIDTH: u16 = 32_767;
 const RASTER_MAX_HEIGHT: u16 = 32_767;
 
+// Identifies the files written by `NoiseMap::write_bin`.
+const BINARY_MAGIC: &[u8; 4] = b"NMAP";
+
+/// Summary statistics of the values in a `NoiseMap`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NoiseMapStats {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub variance: f64,
+}
+
 pub struct NoiseMap {
     size: (usize, usize),
     border_value: f64,
+    edge_mode: EdgeMode,
     map: Vec<f64>,
 }
 
@@ -17,6 +38,22 @@ impl NoiseMap {
         Self::initialize().set_size(width, height)
     }
 
+    /// Creates a map of the given size with each cell set to `f(x, y)`.
+    pub fn from_fn<F>(width: usize, height: usize, f: F) -> Self
+    where
+        F: Fn(usize, usize) -> f64,
+    {
+        let mut map = Self::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                map.set_value(x, y, f(x, y));
+            }
+        }
+
+        map
+    }
+
     pub fn set_size(self, width: usize, height: usize) -> Self {
         // Check for invalid width or height.
         assert!(width < RASTER_MAX_WIDTH as usize);
@@ -62,6 +99,17 @@ impl NoiseMap {
         self.border_value
     }
 
+    /// Sets how `blur`, `convolve`, `sharpen` and the gradients read cells
+    /// beyond the edges of the map. `EdgeMode::Wrap` keeps a seamless map
+    /// seamless. Default is `EdgeMode::Clamp`.
+    pub fn set_edge_mode(self, edge_mode: EdgeMode) -> Self {
+        Self { edge_mode, ..self }
+    }
+
+    pub fn edge_mode(&self) -> EdgeMode {
+        self.edge_mode
+    }
+
     pub fn set_value(&mut self, x: usize, y: usize, value: f64) {
         let (width, height) = self.size;
 
@@ -82,6 +130,621 @@ impl NoiseMap {
         }
     }
 
+    /// Sets every value of the map to 0.0 without reallocating, so a map can
+    /// be reused for another build.
+    pub fn reset(&mut self) {
+        for value in self.map.iter_mut() {
+            *value = 0.0;
+        }
+    }
+
+    /// Returns a map with the same size, border value and edge mode as this
+    /// one, but with every value set to 0.0. Cheaper than copying the map
+    /// when the values are about to be overwritten anyway.
+    pub fn clone_empty(&self) -> Self {
+        let (width, height) = self.size;
+
+        Self::new(width, height)
+            .set_border_value(self.border_value)
+            .set_edge_mode(self.edge_mode)
+    }
+
+    /// Returns the values of the map as a flat, row-major `Vec<f32>` scaled by
+    /// `scale`, along with the width and height of the grid.
+    ///
+    /// This layout is suitable for building a grid mesh, where the vertex at
+    /// (x, y) takes its height from index `x + y * width`.
+    pub fn to_heights(&self, scale: f32) -> (Vec<f32>, usize, usize) {
+        let (width, height) = self.size;
+
+        let heights = self
+            .values()
+            .iter()
+            .map(|&value| value as f32 * scale)
+            .collect();
+
+        (heights, width, height)
+    }
+
+    /// Returns the values of the map as a vector of rows, so the value at
+    /// (x, y) is found at `[y][x]`.
+    pub fn values_2d(&self) -> Vec<Vec<f64>> {
+        let (width, height) = self.size;
+
+        if width == 0 {
+            return vec![Vec::new(); height];
+        }
+
+        self.values()
+            .chunks(width)
+            .map(|row| row.to_vec())
+            .collect()
+    }
+
+    /// Renders the map as text for a quick preview in a terminal.
+    ///
+    /// The map is downsampled to `width` × `height` characters by averaging
+    /// the values covered by each character, and each average is mapped from
+    /// [-1,1] onto the ramp ` .:-=+*#%@`, with values outside that range
+    /// clamped. Rows are separated by newlines. Terminal characters are
+    /// roughly twice as tall as they are wide, so a height of about half the
+    /// width keeps the proportions of a square map.
+    pub fn to_ascii(&self, width: usize, height: usize) -> String {
+        const RAMP: &[u8] = b" .:-=+*#%@";
+
+        let (map_width, map_height) = self.size;
+        if map_width == 0 || map_height == 0 {
+            return String::new();
+        }
+
+        // Range of map cells covered by character `index` of `count`.
+        let span = |index: usize, count: usize, size: usize| {
+            let start = index * size / count;
+            start..((index + 1) * size / count).max(start + 1)
+        };
+
+        let mut text = String::with_capacity((width + 1) * height);
+        for row in 0..height {
+            let ys = span(row, height, map_height);
+
+            for column in 0..width {
+                let xs = span(column, width, map_width);
+
+                let mut sum = 0.0;
+                for y in ys.clone() {
+                    for x in xs.clone() {
+                        sum += self.get_value(x, y);
+                    }
+                }
+                let mean = sum / (xs.len() * ys.len()) as f64;
+
+                let level = math::clamp(mean * 0.5 + 0.5, 0.0, 1.0);
+                let index = (level * (RAMP.len() - 1) as f64).round() as usize;
+                text.push(RAMP[index] as char);
+            }
+
+            if row + 1 < height {
+                text.push('\n');
+            }
+        }
+
+        text
+    }
+
+    /// Computes the minimum, maximum, mean and (population) variance of the
+    /// values in the map.
+    ///
+    /// Panics if the map is empty.
+    pub fn stats(&self) -> NoiseMapStats {
+        let values = self.values();
+        assert!(!values.is_empty(), "cannot compute stats of an empty map");
+
+        let count = values.len() as f64;
+        let mut min = std::f64::INFINITY;
+        let mut max = std::f64::NEG_INFINITY;
+        let mut sum = 0.0;
+
+        for &value in values {
+            min = min.min(value);
+            max = max.max(value);
+            sum += value;
+        }
+
+        let mean = sum / count;
+        let variance = values
+            .iter()
+            .map(|&value| (value - mean) * (value - mean))
+            .sum::<f64>()
+            / count;
+
+        NoiseMapStats {
+            min,
+            max,
+            mean,
+            variance,
+        }
+    }
+
+    /// Returns the value below which `p` percent of the values in the map
+    /// fall, linearly interpolating between the closest ranks.
+    ///
+    /// `p` is clamped to the [0,100] range, so a `p` of 0 returns the minimum
+    /// and a `p` of 100 returns the maximum. This is useful for choosing
+    /// thresholds from the data, such as placing the land/water boundary of a
+    /// `Select` at the 60th percentile of a height map.
+    ///
+    /// Panics if the map is empty.
+    pub fn percentile(&self, p: f64) -> f64 {
+        let mut sorted = self.values().to_vec();
+        assert!(
+            !sorted.is_empty(),
+            "cannot compute percentile of an empty map"
+        );
+
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let rank = math::clamp(p, 0.0, 100.0) / 100.0 * (sorted.len() - 1) as f64;
+        let lower = rank.floor() as usize;
+        let upper = rank.ceil() as usize;
+
+        sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+    }
+
+    /// Creates a map by sampling `source` at an arbitrary grid of points.
+    ///
+    /// `coords` holds one point per cell in row-major order, so the cell at
+    /// (x, y) is set to the value of `source` at `coords[x + y * width]`. This
+    /// allows sampling along non-uniform grids, such as warped or log-spaced
+    /// ones.
+    pub fn from_coordinates(
+        width: usize,
+        height: usize,
+        coords: &[[f64; 2]],
+        source: &dyn NoiseFn<[f64; 2]>,
+    ) -> Self {
+        assert_eq!(coords.len(), width * height);
+
+        Self::from_fn(width, height, |x, y| source.get(coords[x + y * width]))
+    }
+
+    /// Creates a map by sampling `source` at the point `projection(x, y)` for
+    /// each cell.
+    ///
+    /// This generalizes `from_coordinates` to coordinates computed on the
+    /// fly, such as map projections that place the cells of an image on a
+    /// sphere or a plane.
+    pub fn from_projection<F>(
+        width: usize,
+        height: usize,
+        projection: F,
+        source: &dyn NoiseFn<[f64; 2]>,
+    ) -> Self
+    where
+        F: Fn(usize, usize) -> [f64; 2],
+    {
+        Self::from_fn(width, height, |x, y| source.get(projection(x, y)))
+    }
+
+    /// Returns true if both maps have the same size and every pair of values
+    /// differs by at most `tolerance`.
+    ///
+    /// Border values are not compared.
+    pub fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        self.size == other.size
+            && self
+                .values()
+                .iter()
+                .zip(other.values())
+                .all(|(a, b)| (a - b).abs() <= tolerance)
+    }
+
+    /// Returns a new map of the same size with `f` applied to every value.
+    ///
+    /// The border value is copied unchanged.
+    pub fn map_values<F>(&self, f: F) -> Self
+    where
+        F: Fn(f64) -> f64,
+    {
+        Self {
+            size: self.size,
+            border_value: self.border_value,
+            edge_mode: self.edge_mode,
+            map: self.values().iter().map(|&value| f(value)).collect(),
+        }
+    }
+
+    /// Returns a map that repeats this map `repeats_x` times horizontally and
+    /// `repeats_y` times vertically.
+    ///
+    /// The cell at (x, y) of the result takes the value at
+    /// (x % width, y % height), so a seamless map tiles without visible
+    /// edges. The border value and edge mode are kept.
+    pub fn tile(&self, repeats_x: usize, repeats_y: usize) -> Self {
+        let (width, height) = self.size;
+
+        Self::from_fn(width * repeats_x, height * repeats_y, |x, y| {
+            self.get_value(x % width, y % height)
+        })
+        .set_border_value(self.border_value)
+        .set_edge_mode(self.edge_mode)
+    }
+
+    /// Returns the `width` by `height` region of the map whose top-left cell
+    /// is at (x, y). The border value and edge mode are kept.
+    ///
+    /// Panics if the region does not lie within the map.
+    pub fn crop(&self, x: usize, y: usize, width: usize, height: usize) -> Self {
+        let (map_width, map_height) = self.size;
+        assert!(
+            x + width <= map_width && y + height <= map_height,
+            "crop region exceeds the map"
+        );
+
+        Self::from_fn(width, height, |cx, cy| self.get_value(x + cx, y + cy))
+            .set_border_value(self.border_value)
+            .set_edge_mode(self.edge_mode)
+    }
+
+    /// Overwrites the map with a checkerboard of 1.0 and -1.0, starting with
+    /// 1.0 in the top-left cell.
+    ///
+    /// The pattern does not depend on any noise function, which makes it
+    /// useful for telling apart bugs in the processing of a map from bugs in
+    /// the noise functions that fill it.
+    pub fn fill_checker(&mut self) {
+        let (width, height) = self.size;
+
+        for y in 0..height {
+            for x in 0..width {
+                self.map[x + y * width] = if (x + y) % 2 == 0 { 1.0 } else { -1.0 };
+            }
+        }
+    }
+
+    /// Blends the outer `margin` cells of the map toward `target`.
+    ///
+    /// The blend is linear in the distance to the nearest edge: the outermost
+    /// cells move `margin / (margin + 1)` of the way toward `target`, and cells
+    /// `margin` or more cells away from the edge are left untouched. Fading
+    /// independently generated tiles toward a common value hides the seams
+    /// between them.
+    pub fn fade_edges(&mut self, margin: usize, target: f64) {
+        let (width, height) = self.size;
+
+        for y in 0..height {
+            for x in 0..width {
+                let distance = x.min(y).min(width - 1 - x).min(height - 1 - y);
+
+                if distance < margin {
+                    let alpha = (distance + 1) as f64 / (margin + 1) as f64;
+                    let index = x + y * width;
+
+                    self.map[index] = interpolate::linear(target, self.map[index], alpha);
+                }
+            }
+        }
+    }
+
+    /// Scales the distance of every value from the mean of the map by
+    /// `amount`.
+    ///
+    /// An amount of 1.0 leaves the map unchanged, larger amounts increase the
+    /// contrast and smaller amounts flatten the map toward its mean. The
+    /// values are not clamped.
+    pub fn adjust_contrast(&mut self, amount: f64) {
+        let mean = self.stats().mean;
+        let (width, height) = self.size;
+
+        for value in &mut self.map[..width * height] {
+            *value = mean + (*value - mean) * amount;
+        }
+    }
+
+    /// Applies gamma correction to every value.
+    ///
+    /// Values are mapped from [-1,1] onto [0,1], clamping anything outside of
+    /// that range, raised to the power of `gamma` and mapped back onto [-1,1].
+    /// Gamma values above 1.0 darken the map and values below 1.0 brighten it.
+    pub fn apply_gamma(&mut self, gamma: f64) {
+        let (width, height) = self.size;
+
+        for value in &mut self.map[..width * height] {
+            let normalized = math::clamp(*value * 0.5 + 0.5, 0.0, 1.0);
+            *value = normalized.powf(gamma) * 2.0 - 1.0;
+        }
+    }
+
+    /// Rescales the values linearly so that the smallest becomes -1.0 and the
+    /// largest 1.0.
+    ///
+    /// Unlike a `ScaleBias` with a fixed scale, this measures the actual range
+    /// of the map, which keeps composed noise that strays outside of [-1,1]
+    /// from clipping when rendered. A map with a single distinct value is
+    /// set to 0.0, and an empty map is left unchanged.
+    pub fn normalize(&mut self) {
+        let (width, height) = self.size;
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let NoiseMapStats { min, max, .. } = self.stats();
+        let range = max - min;
+
+        for value in &mut self.map[..width * height] {
+            *value = if range > 0.0 {
+                (*value - min) / range * 2.0 - 1.0
+            } else {
+                0.0
+            };
+        }
+    }
+
+    /// Returns a copy of the map smoothed with a Gaussian kernel.
+    ///
+    /// The kernel spans `radius` cells on each side of the center and is
+    /// applied separably, first along rows and then along columns. Cells
+    /// beyond the edges of the map are read according to the edge mode.
+    pub fn blur(&self, radius: usize, sigma: f64) -> Self {
+        assert!(sigma > 0.0);
+
+        let (width, height) = self.size;
+        let radius = radius as isize;
+
+        let mut kernel: Vec<f64> = (-radius..=radius)
+            .map(|i| (-((i * i) as f64) / (2.0 * sigma * sigma)).exp())
+            .collect();
+        let total: f64 = kernel.iter().sum();
+        for weight in &mut kernel {
+            *weight /= total;
+        }
+
+        let convolve = |source: &[f64], stride: isize, index: isize, len: isize, base: isize| {
+            kernel
+                .iter()
+                .zip(-radius..=radius)
+                .map(|(weight, offset)| {
+                    let i = resolve_index(index + offset, len as usize, self.edge_mode) as isize;
+                    weight * source[(base + i * stride) as usize]
+                })
+                .sum::<f64>()
+        };
+
+        let (w, h) = (width as isize, height as isize);
+        let mut rows = vec![0.0; width * height];
+        for y in 0..h {
+            for x in 0..w {
+                rows[(x + y * w) as usize] = convolve(self.values(), 1, x, w, y * w);
+            }
+        }
+
+        let mut map = vec![0.0; width * height];
+        for y in 0..h {
+            for x in 0..w {
+                map[(x + y * w) as usize] = convolve(&rows, w, y, h, x);
+            }
+        }
+
+        Self {
+            size: self.size,
+            border_value: self.border_value,
+            edge_mode: self.edge_mode,
+            map,
+        }
+    }
+
+    /// Returns a copy of the map convolved with `kernel`.
+    ///
+    /// `kernel` is a grid of weights given as rows, with an odd number of rows
+    /// and columns, centered on the cell being computed. Each output value is
+    /// the weighted sum of the cells under the kernel. Cells beyond the edges
+    /// of the map are read according to the edge mode. The weights are
+    /// used as-is, so kernels meant to preserve the overall level should sum
+    /// to 1.0.
+    pub fn convolve(&self, kernel: &[Vec<f64>]) -> Self {
+        let kernel_height = kernel.len();
+        let kernel_width = kernel.first().map_or(0, Vec::len);
+        assert!(
+            kernel_height % 2 == 1 && kernel_width % 2 == 1,
+            "kernel dimensions must be odd"
+        );
+        assert!(
+            kernel.iter().all(|row| row.len() == kernel_width),
+            "kernel rows must have the same length"
+        );
+
+        let (width, height) = self.size;
+        let (w, h) = (width as isize, height as isize);
+        let (rx, ry) = ((kernel_width / 2) as isize, (kernel_height / 2) as isize);
+        let mut map = vec![0.0; width * height];
+
+        for y in 0..h {
+            for x in 0..w {
+                let mut sum = 0.0;
+
+                for (row, ky) in kernel.iter().zip(-ry..=ry) {
+                    let sy = resolve_index(y + ky, height, self.edge_mode) as isize;
+
+                    for (weight, kx) in row.iter().zip(-rx..=rx) {
+                        let sx = resolve_index(x + kx, width, self.edge_mode) as isize;
+                        sum += weight * self.map[(sx + sy * w) as usize];
+                    }
+                }
+
+                map[(x + y * w) as usize] = sum;
+            }
+        }
+
+        Self {
+            size: self.size,
+            border_value: self.border_value,
+            edge_mode: self.edge_mode,
+            map,
+        }
+    }
+
+    /// Returns a copy of the map sharpened by unsharp masking.
+    ///
+    /// Each value is pushed away from its blurred surroundings by
+    /// `value + amount * (value - blurred)`, which steepens edges and ridges.
+    /// The blur spans `radius` cells on each side, with a standard deviation
+    /// of half the radius and reads beyond the edges according to the edge
+    /// mode. An amount of 0.0 returns the map unchanged.
+    pub fn sharpen(&self, amount: f64, radius: usize) -> Self {
+        assert!(radius > 0);
+
+        let blurred = self.blur(radius, radius as f64 / 2.0);
+        let map = self
+            .values()
+            .iter()
+            .zip(blurred.values())
+            .map(|(&value, &blurred)| value + amount * (value - blurred))
+            .collect();
+
+        Self {
+            size: self.size,
+            border_value: self.border_value,
+            edge_mode: self.edge_mode,
+            map,
+        }
+    }
+
+    /// Returns a map holding the magnitude of the gradient at each cell.
+    ///
+    /// The gradient is estimated with central differences measured in cells.
+    /// Along the edges of the map the neighbors are read according to the
+    /// edge mode. With `EdgeMode::Clamp` this falls back to a one-sided
+    /// difference.
+    pub fn gradient_magnitude(&self) -> Self {
+        let (width, height) = self.size;
+        let mut map = vec![0.0; width * height];
+
+        for y in 0..height {
+            for x in 0..width {
+                let [dx, dy] = self.gradient_at(x, y);
+                map[x + y * width] = dx.hypot(dy);
+            }
+        }
+
+        Self {
+            size: self.size,
+            border_value: self.border_value,
+            edge_mode: self.edge_mode,
+            map,
+        }
+    }
+
+    /// Returns the x and y components of the gradient at every cell, each in
+    /// row-major order, such as for building a tangent-space normal map.
+    ///
+    /// The components are computed the same way as for `gradient_magnitude`,
+    /// using central differences and the edge mode along the edges.
+    pub fn gradient_field(&self) -> (Vec<f64>, Vec<f64>) {
+        let (width, height) = self.size;
+        let mut dxs = Vec::with_capacity(width * height);
+        let mut dys = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let [dx, dy] = self.gradient_at(x, y);
+                dxs.push(dx);
+                dys.push(dy);
+            }
+        }
+
+        (dxs, dys)
+    }
+
+    fn gradient_at(&self, x: usize, y: usize) -> [f64; 2] {
+        let (width, height) = self.size;
+        let difference = |index: usize, len: usize, value: &dyn Fn(usize) -> f64| {
+            let low = resolve_index(index as isize - 1, len, self.edge_mode);
+            let high = resolve_index(index as isize + 1, len, self.edge_mode);
+            let distance = match self.edge_mode {
+                EdgeMode::Clamp => high - low,
+                EdgeMode::Wrap | EdgeMode::Mirror => 2,
+            };
+
+            if high == low {
+                0.0
+            } else {
+                (value(high) - value(low)) / distance as f64
+            }
+        };
+
+        [
+            difference(x, width, &|i| self.get_value(i, y)),
+            difference(y, height, &|i| self.get_value(x, i)),
+        ]
+    }
+
+    fn values(&self) -> &[f64] {
+        let (width, height) = self.size;
+
+        &self.map[..width * height]
+    }
+
+    /// Writes the map to `path` in a compact binary format.
+    ///
+    /// The file holds the bytes `NMAP`, the width and height as
+    /// little-endian `u32`s, the border value and then the values in
+    /// row-major order, all as little-endian `f64`s. Reading the file back
+    /// with `read_bin` is much faster than regenerating an expensive map.
+    pub fn write_bin<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let (width, height) = self.size;
+
+        writer.write_all(BINARY_MAGIC)?;
+        writer.write_all(&(width as u32).to_le_bytes())?;
+        writer.write_all(&(height as u32).to_le_bytes())?;
+        writer.write_all(&self.border_value.to_bits().to_le_bytes())?;
+        for value in self.values() {
+            writer.write_all(&value.to_bits().to_le_bytes())?;
+        }
+
+        writer.flush()
+    }
+
+    /// Reads a map written by `write_bin` from `path`.
+    ///
+    /// Returns an error of kind `InvalidData` if the file is not in the
+    /// expected format.
+    pub fn read_bin<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != BINARY_MAGIC {
+            return Err(invalid("not a noise map file"));
+        }
+
+        let mut read_u32 = || -> io::Result<usize> {
+            let mut bytes = [0; 4];
+            reader.read_exact(&mut bytes)?;
+            Ok(u32::from_le_bytes(bytes) as usize)
+        };
+        let width = read_u32()?;
+        let height = read_u32()?;
+        if width >= RASTER_MAX_WIDTH as usize || height >= RASTER_MAX_HEIGHT as usize {
+            return Err(invalid("noise map dimensions are too large"));
+        }
+
+        let mut read_f64 = || -> io::Result<f64> {
+            let mut bytes = [0; 8];
+            reader.read_exact(&mut bytes)?;
+            Ok(f64::from_bits(u64::from_le_bytes(bytes)))
+        };
+        let border_value = read_f64()?;
+        let mut map = Self::new(width, height).set_border_value(border_value);
+        for y in 0..height {
+            for x in 0..width {
+                map.set_value(x, y, read_f64()?);
+            }
+        }
+
+        Ok(map)
+    }
+
     #[cfg(feature = "image")]
     pub fn write_to_file(&self, filename: &str) {
         // Create the output directory for the images, if it doesn't already exist
@@ -95,29 +758,51 @@ impl NoiseMap {
         let directory: String = "example_images/".to_owned();
         let file_path = directory + filename;
 
-        // collect the values from f64 into u8 in a separate vec
-        let (width, height) = self.size;
-        let mut pixels: Vec<u8> = Vec::with_capacity(width * height);
+        let _ = self.save_grayscale(file_path);
 
-        for i in &self.map {
-            pixels.push((math::clamp(i * 0.5 + 0.5, 0.0, 1.0) * 255.0) as u8);
-        }
+        println!("\nFinished generating {}", filename);
+    }
 
-        let _ = image::save_buffer(
-            &Path::new(&file_path),
+    /// Writes the map to `path` as a grayscale image, mapping the range
+    /// [-1,1] onto black to white. The format is determined by the path's
+    /// extension.
+    #[cfg(feature = "image")]
+    pub fn save_grayscale<P: AsRef<Path>>(&self, path: P) -> image::ImageResult<()> {
+        // collect the values from f64 into u8 in a separate vec
+        let pixels: Vec<u8> = self
+            .values()
+            .iter()
+            .map(|i| (math::clamp(i * 0.5 + 0.5, 0.0, 1.0) * 255.0) as u8)
+            .collect();
+
+        image::save_buffer(
+            path.as_ref(),
             &*pixels,
             self.size.0 as u32,
             self.size.1 as u32,
             image::ColorType::L8,
-        );
+        )
+    }
 
-        println!("\nFinished generating {}", filename);
+    /// Renders the map with `gradient` and writes it to `path`. The format is
+    /// determined by the path's extension.
+    #[cfg(feature = "image")]
+    pub fn save_with_gradient<P: AsRef<Path>>(
+        &self,
+        path: P,
+        gradient: &ColorGradient,
+    ) -> image::ImageResult<()> {
+        ImageRenderer::new()
+            .set_gradient(gradient.clone())
+            .render(self)
+            .write_to_path(path)
     }
 
     fn initialize() -> Self {
         Self {
             size: (0, 0),
             border_value: 0.0,
+            edge_mode: EdgeMode::Clamp,
             map: Vec::new(),
         }
     }
@@ -128,3 +813,461 @@ impl Default for NoiseMap {
         Self::initialize()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Perlin;
+
+    #[test]
+    fn to_heights() {
+        let mut map = NoiseMap::new(4, 3);
+        map.set_value(2, 1, 0.5);
+
+        let (heights, width, height) = map.to_heights(10.0);
+
+        assert_eq!((width, height), (4, 3));
+        assert_eq!(heights.len(), 12);
+        assert_eq!(heights[2 + 4], 5.0);
+        assert_eq!(heights[0], 0.0);
+    }
+
+    #[test]
+    fn values_2d_is_row_major() {
+        let map = NoiseMap::from_fn(5, 3, |x, y| x as f64 + y as f64 * 10.0);
+        let rows = map.values_2d();
+
+        assert_eq!(rows.len(), 3);
+        for (y, row) in rows.iter().enumerate() {
+            assert_eq!(row.len(), 5);
+            for (x, &value) in row.iter().enumerate() {
+                assert_eq!(value, map.get_value(x, y));
+            }
+        }
+    }
+
+    #[test]
+    fn to_ascii() {
+        let high = NoiseMap::from_fn(40, 30, |_, _| 1.0);
+        let low = NoiseMap::from_fn(40, 30, |_, _| -1.0);
+
+        assert_eq!(high.to_ascii(8, 3), "@@@@@@@@\n@@@@@@@@\n@@@@@@@@");
+        assert_eq!(low.to_ascii(8, 3), "        \n        \n        ");
+
+        let ramp = NoiseMap::from_fn(10, 1, |x, _| x as f64 / 4.5 - 1.0);
+        assert_eq!(ramp.to_ascii(10, 1), " .:-=+*#%@");
+        assert_eq!(ramp.to_ascii(20, 2).lines().count(), 2);
+    }
+
+    #[test]
+    fn stats() {
+        let mut map = NoiseMap::new(2, 2);
+        map.set_value(0, 0, -1.0);
+        map.set_value(1, 0, 0.0);
+        map.set_value(0, 1, 0.5);
+        map.set_value(1, 1, 0.5);
+
+        let stats = map.stats();
+
+        assert_eq!(stats.min, -1.0);
+        assert_eq!(stats.max, 0.5);
+        assert_eq!(stats.mean, 0.0);
+        // ((-1)^2 + 0^2 + 0.5^2 + 0.5^2) / 4
+        assert_eq!(stats.variance, 0.375);
+    }
+
+    #[test]
+    fn percentile() {
+        let mut map = NoiseMap::new(5, 1);
+        for x in 0..5 {
+            map.set_value(x, 0, 4.0 - x as f64);
+        }
+
+        assert_eq!(map.percentile(0.0), 0.0);
+        assert_eq!(map.percentile(50.0), 2.0);
+        assert_eq!(map.percentile(60.0), 2.4);
+        assert_eq!(map.percentile(100.0), 4.0);
+    }
+
+    #[test]
+    fn fade_edges() {
+        let mut map = NoiseMap::new(4, 4);
+        for y in 0..4 {
+            for x in 0..4 {
+                map.set_value(x, y, 1.0);
+            }
+        }
+
+        map.fade_edges(1, 0.0);
+
+        assert_eq!(map.get_value(0, 0), 0.5);
+        assert_eq!(map.get_value(3, 1), 0.5);
+        assert_eq!(map.get_value(2, 3), 0.5);
+        assert_eq!(map.get_value(1, 1), 1.0);
+        assert_eq!(map.get_value(2, 2), 1.0);
+    }
+
+    #[test]
+    fn fill_checker() {
+        let mut map = NoiseMap::from_fn(5, 4, |_, _| 0.3);
+        map.fill_checker();
+
+        assert_eq!(map.get_value(0, 0), 1.0);
+        for y in 0..4 {
+            for x in 0..5 {
+                let value = map.get_value(x, y);
+
+                assert_eq!(value.abs(), 1.0);
+                if x > 0 {
+                    assert_eq!(map.get_value(x - 1, y), -value);
+                }
+                if y > 0 {
+                    assert_eq!(map.get_value(x, y - 1), -value);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn tile() {
+        let map = NoiseMap::from_fn(2, 2, |x, y| x as f64 * 0.5 - y as f64).set_border_value(0.25);
+        let tiled = map.tile(3, 3);
+
+        assert_eq!(tiled.size(), (6, 6));
+        assert_eq!(tiled.border_value(), 0.25);
+        for block_y in 0..3 {
+            for block_x in 0..3 {
+                for y in 0..2 {
+                    for x in 0..2 {
+                        assert_eq!(
+                            tiled.get_value(block_x * 2 + x, block_y * 2 + y),
+                            map.get_value(x, y)
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn crop() {
+        let map = NoiseMap::from_fn(4, 4, |x, y| (x + y * 4) as f64).set_border_value(-1.0);
+        let cropped = map.crop(1, 2, 2, 2);
+
+        assert_eq!(cropped.size(), (2, 2));
+        assert_eq!(cropped.values_2d(), vec![vec![9.0, 10.0], vec![13.0, 14.0]]);
+        assert_eq!(cropped.border_value(), -1.0);
+        assert!(map.crop(0, 0, 4, 4).approx_eq(&map, 0.0));
+    }
+
+    #[test]
+    #[should_panic(expected = "crop region exceeds the map")]
+    fn crop_out_of_bounds() {
+        NoiseMap::new(4, 4).crop(3, 0, 2, 2);
+    }
+
+    #[test]
+    fn map_values() {
+        let mut map = NoiseMap::new(3, 2);
+        for y in 0..2 {
+            for x in 0..3 {
+                map.set_value(x, y, (x + y * 3) as f64 * 0.25 - 0.5);
+            }
+        }
+
+        let halved = map.map_values(|v| v * 0.5);
+
+        assert_eq!(halved.size(), (3, 2));
+        for y in 0..2 {
+            for x in 0..3 {
+                assert_eq!(halved.get_value(x, y), map.get_value(x, y) * 0.5);
+            }
+        }
+    }
+
+    #[test]
+    fn from_fn() {
+        let map = NoiseMap::from_fn(4, 3, |x, y| x as f64 * 0.25 - y as f64 * 0.5);
+
+        assert_eq!(map.size(), (4, 3));
+        assert_eq!(map.get_value(0, 0), 0.0);
+        assert_eq!(map.get_value(3, 0), 0.75);
+        assert_eq!(map.get_value(0, 2), -1.0);
+        assert_eq!(map.get_value(2, 1), 0.0);
+    }
+
+    #[test]
+    fn approx_eq() {
+        let map = NoiseMap::from_fn(3, 3, |x, y| (x * y) as f64 * 0.1);
+        let mut other = NoiseMap::from_fn(3, 3, |x, y| (x * y) as f64 * 0.1);
+
+        assert!(map.approx_eq(&other, 0.0));
+
+        other.set_value(1, 2, map.get_value(1, 2) + 1e-9);
+        assert!(map.approx_eq(&other, 1e-6));
+
+        other.set_value(2, 1, map.get_value(2, 1) + 1e-3);
+        assert!(!map.approx_eq(&other, 1e-6));
+
+        assert!(!map.approx_eq(&NoiseMap::new(3, 2), 1.0));
+    }
+
+    #[test]
+    fn blur() {
+        let spike = NoiseMap::from_fn(5, 5, |x, y| if x == 2 && y == 2 { 1.0 } else { 0.0 });
+        let blurred = spike.blur(1, 1.0);
+
+        assert!(blurred.get_value(2, 2) < 1.0);
+        assert!(blurred.get_value(1, 2) > 0.0);
+        assert!(blurred.get_value(2, 3) > 0.0);
+        assert!(blurred.get_value(1, 1) > 0.0);
+        assert_eq!(blurred.get_value(0, 0), 0.0);
+        assert!((blurred.stats().mean - spike.stats().mean).abs() < 1e-12);
+
+        let constant = NoiseMap::from_fn(4, 3, |_, _| 0.25);
+        assert!(constant.blur(2, 1.5).approx_eq(&constant, 1e-12));
+    }
+
+    #[test]
+    fn convolve() {
+        let map = NoiseMap::from_fn(5, 4, |x, y| (x * 3 + y * y) as f64 * 0.1);
+
+        let identity = vec![
+            vec![0.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 0.0],
+        ];
+        assert!(map.convolve(&identity).approx_eq(&map, 0.0));
+
+        let boxed = map.convolve(&[vec![1.0 / 9.0; 3], vec![1.0 / 9.0; 3], vec![1.0 / 9.0; 3]]);
+        let mut inner = 0.0;
+        let mut corner = 0.0;
+        for dy in 0..3 {
+            for dx in 0..3 {
+                inner += map.get_value(1 + dx, 1 + dy) / 9.0;
+                corner += map.get_value(dx.max(1) - 1, dy.max(1) - 1) / 9.0;
+            }
+        }
+        assert!((boxed.get_value(2, 2) - inner).abs() < 1e-12);
+        assert!((boxed.get_value(0, 0) - corner).abs() < 1e-12);
+
+        let row = map.convolve(&[vec![0.5, 0.0, 0.5]]);
+        assert!(
+            (row.get_value(2, 1) - (map.get_value(1, 1) + map.get_value(3, 1)) / 2.0).abs() < 1e-12
+        );
+    }
+
+    #[test]
+    fn sharpen() {
+        let ridge = NoiseMap::from_fn(9, 5, |x, _| 1.0 - (x as f64 - 4.0).abs() * 0.25);
+
+        assert!(ridge.sharpen(0.0, 2).approx_eq(&ridge, 0.0));
+
+        let sharpened = ridge.sharpen(1.5, 2);
+        let contrast = |map: &NoiseMap| map.get_value(4, 2) - map.get_value(3, 2);
+        assert!(contrast(&sharpened) > contrast(&ridge));
+        assert!(sharpened.get_value(4, 2) > ridge.get_value(4, 2));
+    }
+
+    #[test]
+    fn gradient_field() {
+        let map = NoiseMap::from_fn(5, 4, |x, y| x as f64 * 0.3 - y as f64 * 0.2);
+        let (dxs, dys) = map.gradient_field();
+
+        assert_eq!(dxs.len(), 20);
+        assert_eq!(dys.len(), 20);
+        for (&dx, &dy) in dxs.iter().zip(&dys) {
+            assert!((dx - 0.3).abs() < 1e-12);
+            assert!((dy + 0.2).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn gradient_magnitude() {
+        let ramp = NoiseMap::from_fn(6, 4, |x, y| x as f64 * 0.3 - y as f64 * 0.4);
+        let gradient = ramp.gradient_magnitude();
+
+        for y in 0..4 {
+            for x in 0..6 {
+                assert!((gradient.get_value(x, y) - 0.5).abs() < 1e-12);
+            }
+        }
+
+        let flat = NoiseMap::from_fn(3, 3, |_, _| 0.7);
+        assert_eq!(flat.gradient_magnitude().stats().max, 0.0);
+    }
+
+    #[test]
+    fn wrap_blur_keeps_map_tileable() {
+        use std::f64::consts::PI;
+
+        let tileable = NoiseMap::from_fn(12, 8, |x, y| {
+            (x as f64 * PI / 6.0).sin() + (y as f64 * PI / 4.0).cos() * 0.5
+        });
+
+        // A tileable map blurs the same on its own as inside a tiling of
+        // itself, so blurring and tiling commute.
+        let wrapped = tileable.set_edge_mode(EdgeMode::Wrap);
+        assert!(wrapped
+            .blur(2, 1.5)
+            .tile(2, 2)
+            .approx_eq(&wrapped.tile(2, 2).blur(2, 1.5), 1e-12));
+
+        let clamped = wrapped.set_edge_mode(EdgeMode::Clamp);
+        assert!(!clamped
+            .blur(2, 1.5)
+            .tile(2, 2)
+            .approx_eq(&clamped.tile(2, 2).blur(2, 1.5), 1e-3));
+
+        let mirrored = NoiseMap::from_fn(4, 1, |x, _| x as f64).set_edge_mode(EdgeMode::Mirror);
+        let (dxs, _) = mirrored.gradient_field();
+        assert_eq!(dxs, vec![0.5, 1.0, 1.0, 0.5]);
+    }
+
+    #[test]
+    fn from_coordinates() {
+        let source = Perlin::new();
+        let coords: Vec<_> = (0..6)
+            .map(|i| [(i as f64 * 0.5).exp(), i as f64 * 0.3])
+            .collect();
+        let map = NoiseMap::from_coordinates(3, 2, &coords, &source);
+
+        assert_eq!(map.size(), (3, 2));
+        for y in 0..2 {
+            for x in 0..3 {
+                assert_eq!(map.get_value(x, y), source.get(coords[x + y * 3]));
+            }
+        }
+    }
+
+    #[test]
+    fn from_projection() {
+        use std::f64::consts::PI;
+
+        let source = Perlin::new();
+        // Inverse Mercator: the rows map to latitudes, compressed towards the
+        // poles.
+        let mercator = |x: usize, y: usize| {
+            let longitude = (x as f64 / 8.0 - 0.5) * 2.0 * PI;
+            let latitude = ((y as f64 / 4.0 - 0.5) * PI).sinh().atan();
+            [longitude, latitude]
+        };
+        let map = NoiseMap::from_projection(8, 4, mercator, &source);
+
+        assert_eq!(map.size(), (8, 4));
+        for y in 0..4 {
+            for x in 0..8 {
+                assert_eq!(map.get_value(x, y), source.get(mercator(x, y)));
+            }
+        }
+    }
+
+    #[test]
+    fn adjust_contrast() {
+        let original = NoiseMap::from_fn(4, 4, |x, y| (x as f64 - y as f64) * 0.2);
+
+        let mut map = NoiseMap::from_fn(4, 4, |x, y| (x as f64 - y as f64) * 0.2);
+        map.adjust_contrast(1.0);
+        assert!(map.approx_eq(&original, 1e-12));
+
+        map.adjust_contrast(1.5);
+        let (before, after) = (original.stats(), map.stats());
+        assert!((after.mean - before.mean).abs() < 1e-12);
+        assert!(after.max - after.min > before.max - before.min);
+        assert!((after.variance - before.variance * 2.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn apply_gamma() {
+        let original = NoiseMap::from_fn(3, 1, |x, _| x as f64 - 1.0);
+
+        let mut map = NoiseMap::from_fn(3, 1, |x, _| x as f64 - 1.0);
+        map.apply_gamma(1.0);
+        assert!(map.approx_eq(&original, 1e-12));
+
+        map.apply_gamma(2.0);
+        assert_eq!(map.get_value(0, 0), -1.0);
+        assert_eq!(map.get_value(1, 0), -0.5);
+        assert_eq!(map.get_value(2, 0), 1.0);
+    }
+
+    #[test]
+    fn normalize() {
+        let perlin = Perlin::new();
+        let value = |x: usize, y: usize| perlin.get([x as f64 * 0.3, y as f64 * 0.3]) * 3.0 + 0.5;
+        let original = NoiseMap::from_fn(16, 16, value);
+        let mut map = NoiseMap::from_fn(16, 16, value);
+        map.normalize();
+
+        let stats = map.stats();
+        assert_eq!(stats.min, -1.0);
+        assert_eq!(stats.max, 1.0);
+
+        // The rescaling is linear, so it preserves the relative spacing.
+        let before = original.stats();
+        let expected = (original.get_value(3, 5) - before.min) / (before.max - before.min);
+        assert!(((map.get_value(3, 5) + 1.0) / 2.0 - expected).abs() < 1e-12);
+
+        let mut constant = NoiseMap::from_fn(3, 3, |_, _| 4.0);
+        constant.normalize();
+        assert_eq!(constant.stats().max, 0.0);
+    }
+
+    #[test]
+    fn reset_and_clone_empty() {
+        let mut map =
+            NoiseMap::from_fn(5, 4, |x, y| x as f64 + y as f64 * 0.5 + 1.0).set_border_value(-0.5);
+
+        let empty = map.clone_empty();
+        assert_eq!(empty.size(), (5, 4));
+        assert_eq!(empty.border_value(), -0.5);
+        assert_eq!(empty.stats().max, 0.0);
+        assert_eq!(map.get_value(4, 3), 6.5);
+
+        let capacity = map.map.capacity();
+        map.reset();
+        assert_eq!(map.size(), (5, 4));
+        assert_eq!((map.stats().min, map.stats().max), (0.0, 0.0));
+        assert_eq!(map.map.capacity(), capacity);
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let map = NoiseMap::from_fn(7, 3, |x, y| (x as f64 * 0.3 - y as f64).sin())
+            .set_border_value(-0.25);
+        let path = std::env::temp_dir().join("noise_map_binary_round_trip.bin");
+
+        map.write_bin(&path).unwrap();
+        let read = NoiseMap::read_bin(&path).unwrap();
+
+        assert!(read.approx_eq(&map, 0.0));
+        assert_eq!(read.border_value(), -0.25);
+
+        std::fs::write(&path, b"NMAP\x01\x00").unwrap();
+        assert!(NoiseMap::read_bin(&path).is_err());
+        std::fs::write(&path, b"PNG?").unwrap();
+        assert_eq!(
+            NoiseMap::read_bin(&path).err().unwrap().kind(),
+            io::ErrorKind::InvalidData
+        );
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn save() {
+        let map = NoiseMap::from_fn(8, 4, |x, y| (x as f64 - y as f64) * 0.2);
+        let dir = std::env::temp_dir();
+        let gray = dir.join("noise_map_save_grayscale.png");
+        let colored = dir.join("noise_map_save_with_gradient.png");
+
+        map.save_grayscale(&gray).unwrap();
+        map.save_with_gradient(&colored, &ColorGradient::new().build_terrain_gradient())
+            .unwrap();
+
+        for path in &[gray, colored] {
+            assert!(std::fs::metadata(path).unwrap().len() > 0);
+            std::fs::remove_file(path).unwrap();
+        }
+    }
+}
@@ -1,4 +1,9 @@
-use crate::{math::interpolate, noise_fns::NoiseFn, utils::noise_map::NoiseMap};
+use crate::{
+    math::{self, interpolate},
+    noise_fns::NoiseFn,
+    utils::{noise_map::NoiseMap, noise_volume::NoiseVolume},
+};
+use std::f64::consts::PI;
 
 pub trait NoiseMapBuilder<'a> {
     fn set_size(self, width: usize, height: usize) -> Self;
@@ -8,12 +13,157 @@ pub trait NoiseMapBuilder<'a> {
     fn size(&self) -> (usize, usize);
 
     fn build(&self) -> NoiseMap;
+
+    /// Builds the map into an existing `NoiseMap` instead of allocating a new
+    /// one, so a single buffer can be reused across many builds.
+    ///
+    /// Panics if the size of `map` does not match the size of the builder.
+    fn build_into(&self, map: &mut NoiseMap) {
+        assert_eq!(
+            map.size(),
+            self.size(),
+            "map size does not match builder size"
+        );
+
+        *map = self.build();
+    }
+}
+
+/// Builds a sequence of planar noise maps from a 4-dimensional source, using
+/// the fourth coordinate as time.
+///
+/// Each frame samples the x/y bounds of the plane at a fixed time. The time
+/// bounds are divided evenly between the frames, with the first frame at the
+/// lower time bound.
+pub struct AnimatedPlaneMapBuilder<'a> {
+    frame_count: usize,
+    time_bounds: (f64, f64),
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    size: (usize, usize),
+    source_module: &'a dyn NoiseFn<[f64; 4]>,
+}
+
+impl<'a> AnimatedPlaneMapBuilder<'a> {
+    pub fn new(source_module: &'a dyn NoiseFn<[f64; 4]>) -> Self {
+        AnimatedPlaneMapBuilder {
+            frame_count: 10,
+            time_bounds: (0.0, 1.0),
+            x_bounds: (-1.0, 1.0),
+            y_bounds: (-1.0, 1.0),
+            size: (100, 100),
+            source_module,
+        }
+    }
+
+    pub fn set_size(self, width: usize, height: usize) -> Self {
+        AnimatedPlaneMapBuilder {
+            size: (width, height),
+            ..self
+        }
+    }
+
+    pub fn set_source_module(self, source_module: &'a dyn NoiseFn<[f64; 4]>) -> Self {
+        AnimatedPlaneMapBuilder {
+            source_module,
+            ..self
+        }
+    }
+
+    pub fn set_frame_count(self, frame_count: usize) -> Self {
+        AnimatedPlaneMapBuilder {
+            frame_count,
+            ..self
+        }
+    }
+
+    pub fn set_time_bounds(self, lower_time_bound: f64, upper_time_bound: f64) -> Self {
+        AnimatedPlaneMapBuilder {
+            time_bounds: (lower_time_bound, upper_time_bound),
+            ..self
+        }
+    }
+
+    pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
+        AnimatedPlaneMapBuilder {
+            x_bounds: (lower_x_bound, upper_x_bound),
+            ..self
+        }
+    }
+
+    pub fn set_y_bounds(self, lower_y_bound: f64, upper_y_bound: f64) -> Self {
+        AnimatedPlaneMapBuilder {
+            y_bounds: (lower_y_bound, upper_y_bound),
+            ..self
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    pub fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    pub fn time_bounds(&self) -> (f64, f64) {
+        self.time_bounds
+    }
+
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.x_bounds
+    }
+
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.y_bounds
+    }
+
+    /// Builds a single frame of the animation.
+    pub fn build_frame(&self, frame: usize) -> NoiseMap {
+        let (width, height) = self.size;
+
+        let mut result_map = NoiseMap::new(width, height);
+
+        let x_extent = self.x_bounds.1 - self.x_bounds.0;
+        let y_extent = self.y_bounds.1 - self.y_bounds.0;
+        let time_extent = self.time_bounds.1 - self.time_bounds.0;
+
+        let x_step = x_extent / width as f64;
+        let y_step = y_extent / height as f64;
+        let time_step = time_extent / self.frame_count as f64;
+
+        let current_time = self.time_bounds.0 + time_step * frame as f64;
+
+        for y in 0..height {
+            let current_y = self.y_bounds.0 + y_step * y as f64;
+
+            for x in 0..width {
+                let current_x = self.x_bounds.0 + x_step * x as f64;
+
+                let value = self
+                    .source_module
+                    .get([current_x, current_y, 0.0, current_time]);
+
+                result_map.set_value(x, y, value);
+            }
+        }
+
+        result_map
+    }
+
+    /// Builds every frame of the animation, in order.
+    pub fn build(&self) -> Vec<NoiseMap> {
+        (0..self.frame_count)
+            .map(|frame| self.build_frame(frame))
+            .collect()
+    }
 }
 
 pub struct CylinderMapBuilder<'a> {
     angle_bounds: (f64, f64),
     height_bounds: (f64, f64),
     size: (usize, usize),
+    wrap: bool,
     source_module: &'a dyn NoiseFn<[f64; 3]>,
 }
 
@@ -23,6 +173,7 @@ impl<'a> CylinderMapBuilder<'a> {
             angle_bounds: (-90.0, 90.0),
             height_bounds: (-1.0, 1.0),
             size: (100, 100),
+            wrap: false,
             source_module,
         }
     }
@@ -44,6 +195,21 @@ impl<'a> CylinderMapBuilder<'a> {
         }
     }
 
+    /// Enables or disables wrapping of the angle bounds.
+    ///
+    /// With wrapping enabled, an angular extent of more than 360 degrees is
+    /// reduced to exactly one period starting at the lower angle bound, so the
+    /// width of the map covers the cylinder once and the map tiles
+    /// horizontally. Without wrapping, the bounds are mapped linearly across
+    /// the width of the map and overlapping angles produce duplicated columns.
+    pub fn set_wrap(self, wrap: bool) -> Self {
+        CylinderMapBuilder { wrap, ..self }
+    }
+
+    pub fn wrap(&self) -> bool {
+        self.wrap
+    }
+
     pub fn set_height_bounds(self, lower_bound: f64, upper_bound: f64) -> Self {
         let height_bounds = if lower_bound >= upper_bound {
             eprintln!(
@@ -68,6 +234,32 @@ impl<'a> CylinderMapBuilder<'a> {
     pub fn height_bounds(&self) -> (f64, f64) {
         self.height_bounds
     }
+
+    /// Calculates the value of a single cell of the map without building the
+    /// whole map.
+    ///
+    /// The result is identical to the value at (x, y) in the map returned by
+    /// `build`.
+    pub fn get_at(&self, x: usize, y: usize) -> f64 {
+        let (width, height) = self.size;
+
+        let mut angle_extent = self.angle_bounds.1 - self.angle_bounds.0;
+        if self.wrap {
+            angle_extent = angle_extent.min(360.0);
+        }
+        let height_extent = self.height_bounds.1 - self.height_bounds.0;
+
+        let x_step = angle_extent / width as f64;
+        let y_step = height_extent / height as f64;
+
+        let current_height = self.height_bounds.0 + y_step * y as f64;
+        let current_angle = self.angle_bounds.0 + x_step * x as f64;
+
+        let point_x = current_angle.to_radians().cos();
+        let point_z = current_angle.to_radians().sin();
+
+        self.source_module.get([point_x, current_height, point_z])
+    }
 }
 
 impl<'a> NoiseMapBuilder<'a> for CylinderMapBuilder<'a> {
@@ -92,40 +284,112 @@ impl<'a> NoiseMapBuilder<'a> for CylinderMapBuilder<'a> {
     fn build(&self) -> NoiseMap {
         let mut result_map = NoiseMap::new(self.size.0, self.size.1);
 
-        let (width, height) = self.size;
+        self.build_into(&mut result_map);
 
-        let angle_extent = self.angle_bounds.1 - self.angle_bounds.0;
-        let height_extent = self.height_bounds.1 - self.height_bounds.0;
+        result_map
+    }
 
-        let x_step = angle_extent / width as f64;
-        let y_step = height_extent / height as f64;
+    fn build_into(&self, map: &mut NoiseMap) {
+        assert_eq!(
+            map.size(),
+            self.size,
+            "map size does not match builder size"
+        );
 
-        for y in 0..height {
-            let current_height = self.height_bounds.0 + y_step * y as f64;
+        // Checked here rather than in the setters, which can be called in any
+        // order.
+        if !self.wrap && self.angle_bounds.1 - self.angle_bounds.0 > 360.0 {
+            eprintln!(
+                "angle bounds {:?} span more than 360 degrees, the map will contain duplicated \
+                 columns. Use set_wrap to sample a single period instead",
+                self.angle_bounds
+            );
+        }
 
+        let (width, height) = self.size;
+
+        for y in 0..height {
             for x in 0..width {
-                let current_angle = self.angle_bounds.0 + x_step * x as f64;
+                map.set_value(x, y, self.get_at(x, y));
+            }
+        }
+    }
+}
 
-                let point_x = current_angle.to_radians().cos();
-                let point_z = current_angle.to_radians().sin();
+/// Builds a 1-dimensional strip of values by sampling the source along a
+/// line parallel to the x axis.
+///
+/// The samples are spaced like a single row of a `PlaneMapBuilder` map, with
+/// the first sample at the lower bound and the upper bound excluded. This is
+/// useful for terrain cross-sections or for driving other parameters with a
+/// noise signal.
+pub struct LineMapBuilder<'a> {
+    bounds: (f64, f64),
+    y: f64,
+    length: usize,
+    source_module: &'a dyn NoiseFn<[f64; 3]>,
+}
 
-                let value = self.source_module.get([point_x, current_height, point_z]);
+impl<'a> LineMapBuilder<'a> {
+    pub fn new(source_module: &'a dyn NoiseFn<[f64; 3]>) -> Self {
+        LineMapBuilder {
+            bounds: (-1.0, 1.0),
+            y: 0.0,
+            length: 100,
+            source_module,
+        }
+    }
 
-                println!(
-                    "calculated value {} at {}, {}, {}",
-                    value, point_x, current_height, point_z
-                );
+    /// Sets the number of samples in the strip.
+    pub fn set_length(self, length: usize) -> Self {
+        LineMapBuilder { length, ..self }
+    }
 
-                result_map.set_value(x, y, value);
-            }
+    /// Sets the range of x coordinates covered by the strip.
+    pub fn set_bounds(self, lower_bound: f64, upper_bound: f64) -> Self {
+        LineMapBuilder {
+            bounds: (lower_bound, upper_bound),
+            ..self
         }
+    }
 
-        result_map
+    /// Sets the y coordinate of the line.
+    pub fn set_y(self, y: f64) -> Self {
+        LineMapBuilder { y, ..self }
+    }
+
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    pub fn bounds(&self) -> (f64, f64) {
+        self.bounds
+    }
+
+    pub fn y(&self) -> f64 {
+        self.y
+    }
+
+    pub fn build(&self) -> Vec<f64> {
+        let map = PlaneMapBuilder::new(self.source_module)
+            .set_size(self.length, 1)
+            .set_x_bounds(self.bounds.0, self.bounds.1)
+            .set_y_bounds(self.y, self.y + 1.0)
+            .build();
+
+        (0..self.length).map(|x| map.get_value(x, 0)).collect()
     }
 }
 
+/// Builds a noise map by sampling a 3-dimensional source over a rectangle in
+/// the z = 0 plane.
+///
+/// Each cell is evaluated straight into the map, so building allocates
+/// nothing beyond the map itself. To build a map from a noise function that
+/// only supports 2-dimensional input, use `sample_plane`.
 pub struct PlaneMapBuilder<'a> {
     is_seamless: bool,
+    flip_y: bool,
     x_bounds: (f64, f64),
     y_bounds: (f64, f64),
     size: (usize, usize),
@@ -136,6 +400,7 @@ impl<'a> PlaneMapBuilder<'a> {
     pub fn new(source_module: &'a dyn NoiseFn<[f64; 3]>) -> Self {
         PlaneMapBuilder {
             is_seamless: false,
+            flip_y: false,
             x_bounds: (-1.0, 1.0),
             y_bounds: (-1.0, 1.0),
             size: (100, 100),
@@ -150,6 +415,17 @@ impl<'a> PlaneMapBuilder<'a> {
         }
     }
 
+    /// Sets whether the rows of the map are flipped.
+    ///
+    /// By default, row 0 of the map is sampled at the lower y bound and y
+    /// increases with the row index. Images store row 0 at the top, so a map
+    /// written to an image then shows y increasing downwards. Flipping the
+    /// rows samples row 0 at the upper y bound instead, which matches the
+    /// usual world convention of y pointing up. Off by default.
+    pub fn set_flip_y(self, flip_y: bool) -> Self {
+        PlaneMapBuilder { flip_y, ..self }
+    }
+
     pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
         PlaneMapBuilder {
             x_bounds: (lower_x_bound, upper_x_bound),
@@ -164,6 +440,22 @@ impl<'a> PlaneMapBuilder<'a> {
         }
     }
 
+    /// Sets both bounds to (-0.5 / frequency, 0.5 / frequency), as an
+    /// alternative to setting them directly.
+    ///
+    /// The map then spans `1 / frequency` units along each axis, regardless
+    /// of the map size. For a generator at its default frequency of 1.0,
+    /// whose features are about one unit across, a frequency of 1.0 fits
+    /// about one feature on the map, and higher frequencies zoom in on the
+    /// origin. The default bounds of (-1, 1) correspond to a frequency of
+    /// 0.5.
+    pub fn set_frequency(self, frequency: f64) -> Self {
+        let half_extent = 0.5 / frequency;
+
+        self.set_x_bounds(-half_extent, half_extent)
+            .set_y_bounds(-half_extent, half_extent)
+    }
+
     pub fn x_bounds(&self) -> (f64, f64) {
         self.x_bounds
     }
@@ -171,6 +463,95 @@ impl<'a> PlaneMapBuilder<'a> {
     pub fn y_bounds(&self) -> (f64, f64) {
         self.y_bounds
     }
+
+    /// Calculates the value of a single cell of the map without building the
+    /// whole map.
+    ///
+    /// The result is identical to the value at (x, y) in the map returned by
+    /// `build`.
+    pub fn get_at(&self, x: usize, y: usize) -> f64 {
+        self.value_at(x, y, self.blends_edges())
+    }
+
+    // Whether seamless maps need their edges blended, which `build_into`
+    // decides once for the whole map rather than for every cell.
+    fn blends_edges(&self) -> bool {
+        self.is_seamless && !self.spans_whole_periods()
+    }
+
+    fn value_at(&self, x: usize, y: usize, blend: bool) -> f64 {
+        let x_extent = self.x_bounds.1 - self.x_bounds.0;
+        let y_extent = self.y_bounds.1 - self.y_bounds.0;
+
+        let [current_x, current_y] = self.coordinates_at(x, y);
+
+        if blend {
+            let sw_value = self.source_module.get([current_x, current_y, 0.0]);
+            let se_value = self
+                .source_module
+                .get([current_x + x_extent, current_y, 0.0]);
+            let nw_value = self
+                .source_module
+                .get([current_x, current_y + y_extent, 0.0]);
+            let ne_value =
+                self.source_module
+                    .get([current_x + x_extent, current_y + y_extent, 0.0]);
+
+            let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
+            let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
+
+            let y0 = interpolate::linear(sw_value, se_value, x_blend);
+            let y1 = interpolate::linear(nw_value, ne_value, x_blend);
+
+            interpolate::linear(y0, y1, y_blend)
+        } else {
+            self.source_module.get([current_x, current_y, 0.0])
+        }
+    }
+
+    // A periodic source already tiles when the bounds span a whole number of
+    // its periods, so the blending for seamless maps can be skipped.
+    fn spans_whole_periods(&self) -> bool {
+        let whole = |bounds: (f64, f64), period: f64| {
+            let periods = (bounds.1 - bounds.0) / period;
+
+            periods >= 1.0 && (periods - periods.round()).abs() < 1e-9
+        };
+
+        match self.source_module.is_periodic() {
+            Some([x, y]) => whole(self.x_bounds, x) && whole(self.y_bounds, y),
+            None => false,
+        }
+    }
+
+    /// Returns the input coordinates that the cell at (x, y) is sampled at.
+    ///
+    /// With seamless tiling enabled, the cell also blends in samples taken
+    /// one extent further along each axis.
+    pub fn coordinates_at(&self, x: usize, y: usize) -> [f64; 2] {
+        let (width, height) = self.size;
+
+        let x_step = (self.x_bounds.1 - self.x_bounds.0) / width as f64;
+        let y_step = (self.y_bounds.1 - self.y_bounds.0) / height as f64;
+        let row = if self.flip_y { height - 1 - y } else { y };
+
+        [
+            self.x_bounds.0 + x_step * x as f64,
+            self.y_bounds.0 + y_step * row as f64,
+        ]
+    }
+
+    /// Builds the map along with the input coordinates of each cell, in
+    /// row-major order.
+    pub fn build_with_coords(&self) -> (NoiseMap, Vec<[f64; 2]>) {
+        let (width, height) = self.size;
+        let coords = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self.coordinates_at(x, y))
+            .collect();
+
+        (self.build(), coords)
+    }
 }
 
 impl<'a> NoiseMapBuilder<'a> for PlaneMapBuilder<'a> {
@@ -193,56 +574,106 @@ impl<'a> NoiseMapBuilder<'a> for PlaneMapBuilder<'a> {
     }
 
     fn build(&self) -> NoiseMap {
-        let (width, height) = self.size;
+        let mut result_map = NoiseMap::new(self.size.0, self.size.1);
 
-        let mut result_map = NoiseMap::new(width, height);
+        self.build_into(&mut result_map);
 
-        let x_extent = self.x_bounds.1 - self.x_bounds.0;
-        let y_extent = self.y_bounds.1 - self.y_bounds.0;
+        result_map
+    }
 
-        let x_step = x_extent / width as f64;
-        let y_step = y_extent / height as f64;
+    fn build_into(&self, map: &mut NoiseMap) {
+        assert_eq!(
+            map.size(),
+            self.size,
+            "map size does not match builder size"
+        );
 
-        for y in 0..height {
-            let current_y = self.y_bounds.0 + y_step * y as f64;
+        let (width, height) = self.size;
+        let blend = self.blends_edges();
 
+        for y in 0..height {
             for x in 0..width {
-                let current_x = self.x_bounds.0 + x_step * x as f64;
-
-                let final_value = if self.is_seamless {
-                    let sw_value = self.source_module.get([current_x, current_y, 0.0]);
-                    let se_value = self
-                        .source_module
-                        .get([current_x + x_extent, current_y, 0.0]);
-                    let nw_value = self
-                        .source_module
-                        .get([current_x, current_y + y_extent, 0.0]);
-                    let ne_value =
-                        self.source_module
-                            .get([current_x + x_extent, current_y + y_extent, 0.0]);
-
-                    let x_blend = 1.0 - ((current_x - self.x_bounds.0) / x_extent);
-                    let y_blend = 1.0 - ((current_y - self.y_bounds.0) / y_extent);
-
-                    let y0 = interpolate::linear(sw_value, se_value, x_blend);
-                    let y1 = interpolate::linear(nw_value, ne_value, x_blend);
-
-                    interpolate::linear(y0, y1, y_blend)
-                } else {
-                    self.source_module.get([current_x, current_y, 0.0])
-                };
-
-                result_map.set_value(x, y, final_value);
+                map.set_value(x, y, self.value_at(x, y, blend));
             }
         }
+    }
+}
 
-        result_map
+/// Builds a map of `size` cells by sampling a 2-dimensional noise function
+/// over the given bounds.
+///
+/// The cells are sampled at the same coordinates as `PlaneMapBuilder` would
+/// sample them, without seamless tiling. This provides a way to build maps
+/// from noise functions that only support 2-dimensional input.
+pub fn sample_plane(
+    source: &dyn NoiseFn<[f64; 2]>,
+    size: (usize, usize),
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+) -> NoiseMap {
+    let (width, height) = size;
+
+    let x_step = (x_bounds.1 - x_bounds.0) / width as f64;
+    let y_step = (y_bounds.1 - y_bounds.0) / height as f64;
+
+    NoiseMap::from_fn(width, height, |x, y| {
+        source.get([
+            x_bounds.0 + x_step * x as f64,
+            y_bounds.0 + y_step * y as f64,
+        ])
+    })
+}
+
+/// Samples a 2-dimensional noise function along a polyline, producing a
+/// 1-dimensional profile of the noise along the path, such as for a road or a
+/// river.
+///
+/// Each segment between consecutive points is sampled `samples_per_segment`
+/// times at evenly spaced positions, starting at the segment's first point,
+/// and the last point of the polyline is sampled at the end. The result holds
+/// `(points.len() - 1) * samples_per_segment + 1` values, or a single value
+/// if only one point is given.
+///
+/// Panics if `samples_per_segment` is zero.
+pub fn sample_curve(
+    source: &dyn NoiseFn<[f64; 2]>,
+    points: &[[f64; 2]],
+    samples_per_segment: usize,
+) -> Vec<f64> {
+    assert!(
+        samples_per_segment > 0,
+        "samples_per_segment must be positive"
+    );
+
+    let last = match points.last() {
+        Some(&last) => last,
+        None => return Vec::new(),
+    };
+
+    let mut values = Vec::with_capacity((points.len() - 1) * samples_per_segment + 1);
+    for segment in points.windows(2) {
+        let (start, end) = (segment[0], segment[1]);
+
+        for i in 0..samples_per_segment {
+            let t = i as f64 / samples_per_segment as f64;
+            values.push(source.get([
+                interpolate::linear(start[0], end[0], t),
+                interpolate::linear(start[1], end[1], t),
+            ]));
+        }
     }
+    values.push(source.get(last));
+
+    values
 }
 
 pub struct SphereMapBuilder<'a> {
     latitude_bounds: (f64, f64),
     longitude_bounds: (f64, f64),
+    pole_blend: bool,
+    pole_band: f64,
+    radius: f64,
+    feature_scale: Option<f64>,
     size: (usize, usize),
     source_module: &'a dyn NoiseFn<[f64; 3]>,
 }
@@ -252,6 +683,10 @@ impl<'a> SphereMapBuilder<'a> {
         SphereMapBuilder {
             latitude_bounds: (-1.0, 1.0),
             longitude_bounds: (-1.0, 1.0),
+            pole_blend: false,
+            pole_band: 10.0,
+            radius: 1.0,
+            feature_scale: None,
             size: (100, 100),
             source_module,
         }
@@ -285,6 +720,72 @@ impl<'a> SphereMapBuilder<'a> {
         }
     }
 
+    /// Enables or disables blending toward the poles.
+    ///
+    /// Near the poles the samples of each row crowd onto a small circle of
+    /// the sphere, so an equirectangular map shows the features stretched
+    /// into a pinch. With blending enabled, samples within the pole band are
+    /// faded toward the value at the pole itself, reaching it exactly at
+    /// latitude ±90 degrees, which hides the pinch.
+    pub fn set_pole_blend(self, pole_blend: bool) -> Self {
+        SphereMapBuilder { pole_blend, ..self }
+    }
+
+    /// Sets the width of the band around each pole, in degrees of latitude,
+    /// that is blended toward the pole when pole blending is enabled.
+    pub fn set_pole_band(self, pole_band: f64) -> Self {
+        SphereMapBuilder { pole_band, ..self }
+    }
+
+    /// Sets the radius of the sphere the source is sampled on.
+    ///
+    /// The source's features keep their size in input units, so a larger
+    /// sphere fits more of them: doubling the radius halves the size of the
+    /// features on the map. Use `set_feature_scale` to keep the features the
+    /// same size on the map regardless of the radius.
+    pub fn set_radius(self, radius: f64) -> Self {
+        SphereMapBuilder { radius, ..self }
+    }
+
+    /// Fixes the size of the features on the map, independent of the radius.
+    ///
+    /// The sampled coordinates are scaled by `feature_scale / radius`, which
+    /// cancels the radius out, so the map looks like a sphere of radius
+    /// `feature_scale` at any radius. Larger scales produce smaller, denser
+    /// features.
+    pub fn set_feature_scale(self, feature_scale: f64) -> Self {
+        SphereMapBuilder {
+            feature_scale: Some(feature_scale),
+            ..self
+        }
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn feature_scale(&self) -> Option<f64> {
+        self.feature_scale
+    }
+
+    /// Returns the point on the sampled sphere at the given latitude and
+    /// longitude.
+    fn sphere_point(&self, lat: f64, lon: f64) -> [f64; 3] {
+        // Scaling the sphere of the given radius by `feature_scale / radius`
+        // leaves a sphere of radius `feature_scale`.
+        let scale = self.feature_scale.unwrap_or(self.radius);
+
+        math::mul3(math::lat_lon_to_xyz(lat, lon), scale)
+    }
+
+    pub fn pole_blend(&self) -> bool {
+        self.pole_blend
+    }
+
+    pub fn pole_band(&self) -> f64 {
+        self.pole_band
+    }
+
     pub fn latitude_bounds(&self) -> (f64, f64) {
         self.latitude_bounds
     }
@@ -292,6 +793,41 @@ impl<'a> SphereMapBuilder<'a> {
     pub fn longitude_bounds(&self) -> (f64, f64) {
         self.longitude_bounds
     }
+
+    /// Calculates the value of a single cell of the map without building the
+    /// whole map.
+    ///
+    /// The result is identical to the value at (x, y) in the map returned by
+    /// `build`.
+    pub fn get_at(&self, x: usize, y: usize) -> f64 {
+        let (width, height) = self.size;
+
+        let lon_extent = self.longitude_bounds.1 - self.longitude_bounds.0;
+        let lat_extent = self.latitude_bounds.1 - self.latitude_bounds.0;
+
+        let x_step = lon_extent / width as f64;
+        let y_step = lat_extent / height as f64;
+
+        let current_lat = self.latitude_bounds.0 + y_step * y as f64;
+        let current_lon = self.longitude_bounds.0 + x_step * x as f64;
+
+        let point = self.sphere_point(current_lat, current_lon);
+        let value = self.source_module.get(point);
+
+        let band_start = 90.0 - self.pole_band;
+        if self.pole_blend && self.pole_band > 0.0 && current_lat.abs() > band_start {
+            let pole = self.sphere_point(90.0 * current_lat.signum(), 0.0);
+            let alpha = math::clamp((current_lat.abs() - band_start) / self.pole_band, 0.0, 1.0);
+
+            interpolate::linear(
+                value,
+                self.source_module.get(pole),
+                interpolate::s_curve3(alpha),
+            )
+        } else {
+            value
+        }
+    }
 }
 
 impl<'a> NoiseMapBuilder<'a> for SphereMapBuilder<'a> {
@@ -314,37 +850,606 @@ impl<'a> NoiseMapBuilder<'a> for SphereMapBuilder<'a> {
     }
 
     fn build(&self) -> NoiseMap {
-        let (width, height) = self.size;
+        let mut result_map = NoiseMap::new(self.size.0, self.size.1);
 
-        let mut result_map = NoiseMap::new(width, height);
+        self.build_into(&mut result_map);
 
-        let lon_extent = self.longitude_bounds.1 - self.longitude_bounds.0;
-        let lat_extent = self.latitude_bounds.1 - self.latitude_bounds.0;
+        result_map
+    }
 
-        let x_step = lon_extent / width as f64;
-        let y_step = lat_extent / height as f64;
+    fn build_into(&self, map: &mut NoiseMap) {
+        assert_eq!(
+            map.size(),
+            self.size,
+            "map size does not match builder size"
+        );
+
+        let (width, height) = self.size;
 
         for y in 0..height {
-            let current_lat = self.latitude_bounds.0 + y_step * y as f64;
+            for x in 0..width {
+                map.set_value(x, y, self.get_at(x, y));
+            }
+        }
+    }
+}
 
+/// Builds a noise map by sampling the surface of a torus.
+///
+/// The x axis of the map runs once around the major circle of the torus and
+/// the y axis once around the tube, so the resulting map tiles seamlessly in
+/// both directions. The major radius scales the features along the x axis
+/// and the minor radius the features along the y axis.
+pub struct TorusMapBuilder<'a> {
+    major_radius: f64,
+    minor_radius: f64,
+    size: (usize, usize),
+    source_module: &'a dyn NoiseFn<[f64; 3]>,
+}
+
+impl<'a> TorusMapBuilder<'a> {
+    pub fn new(source_module: &'a dyn NoiseFn<[f64; 3]>) -> Self {
+        TorusMapBuilder {
+            major_radius: 2.0,
+            minor_radius: 1.0,
+            size: (100, 100),
+            source_module,
+        }
+    }
+
+    /// Sets the radius of the circle through the center of the tube and the
+    /// radius of the tube itself.
+    ///
+    /// The minor radius should be smaller than the major radius, otherwise
+    /// the tube intersects itself and parts of the map are mirrored.
+    pub fn set_radii(self, major_radius: f64, minor_radius: f64) -> Self {
+        if minor_radius >= major_radius {
+            eprintln!(
+                "minor radius {:?} is not smaller than major radius {:?}, the torus will \
+                 intersect itself",
+                minor_radius, major_radius
+            );
+        }
+
+        TorusMapBuilder {
+            major_radius,
+            minor_radius,
+            ..self
+        }
+    }
+
+    pub fn major_radius(&self) -> f64 {
+        self.major_radius
+    }
+
+    pub fn minor_radius(&self) -> f64 {
+        self.minor_radius
+    }
+
+    /// Calculates the value of a single cell of the map without building the
+    /// whole map.
+    ///
+    /// The result is identical to the value at (x, y) in the map returned by
+    /// `build`.
+    pub fn get_at(&self, x: usize, y: usize) -> f64 {
+        let (width, height) = self.size;
+
+        let major_angle = 2.0 * PI * x as f64 / width as f64;
+        let minor_angle = 2.0 * PI * y as f64 / height as f64;
+
+        let ring = self.major_radius + self.minor_radius * minor_angle.cos();
+
+        let point = [
+            ring * major_angle.cos(),
+            self.minor_radius * minor_angle.sin(),
+            ring * major_angle.sin(),
+        ];
+
+        self.source_module.get(point)
+    }
+}
+
+impl<'a> NoiseMapBuilder<'a> for TorusMapBuilder<'a> {
+    fn set_size(self, width: usize, height: usize) -> Self {
+        TorusMapBuilder {
+            size: (width, height),
+            ..self
+        }
+    }
+
+    fn set_source_module(self, source_module: &'a dyn NoiseFn<[f64; 3]>) -> Self {
+        TorusMapBuilder {
+            source_module,
+            ..self
+        }
+    }
+
+    fn size(&self) -> (usize, usize) {
+        self.size
+    }
+
+    fn build(&self) -> NoiseMap {
+        let mut result_map = NoiseMap::new(self.size.0, self.size.1);
+
+        self.build_into(&mut result_map);
+
+        result_map
+    }
+
+    fn build_into(&self, map: &mut NoiseMap) {
+        assert_eq!(
+            map.size(),
+            self.size,
+            "map size does not match builder size"
+        );
+
+        let (width, height) = self.size;
+
+        for y in 0..height {
             for x in 0..width {
-                let current_lon = self.longitude_bounds.0 + x_step * x as f64;
+                map.set_value(x, y, self.get_at(x, y));
+            }
+        }
+    }
+}
 
-                let point = lat_lon_to_xyz(current_lat, current_lon);
+/// Builds a `NoiseVolume` by sampling a 3-dimensional source over a box.
+///
+/// The cells are sampled like those of a `PlaneMapBuilder`, extended to a
+/// third axis: each axis is divided evenly between its cells, with the first
+/// cell at the lower bound and the upper bound excluded. The whole volume is
+/// kept, so it can be thresholded with `NoiseVolume::to_voxels`, for example
+/// to carve caves out of a terrain.
+pub struct VolumeMapBuilder<'a> {
+    x_bounds: (f64, f64),
+    y_bounds: (f64, f64),
+    z_bounds: (f64, f64),
+    size: (usize, usize, usize),
+    source_module: &'a dyn NoiseFn<[f64; 3]>,
+}
 
-                result_map.set_value(x, y, self.source_module.get(point));
+impl<'a> VolumeMapBuilder<'a> {
+    pub fn new(source_module: &'a dyn NoiseFn<[f64; 3]>) -> Self {
+        VolumeMapBuilder {
+            x_bounds: (-1.0, 1.0),
+            y_bounds: (-1.0, 1.0),
+            z_bounds: (-1.0, 1.0),
+            size: (32, 32, 32),
+            source_module,
+        }
+    }
+
+    pub fn set_size(self, width: usize, height: usize, depth: usize) -> Self {
+        VolumeMapBuilder {
+            size: (width, height, depth),
+            ..self
+        }
+    }
+
+    pub fn set_source_module(self, source_module: &'a dyn NoiseFn<[f64; 3]>) -> Self {
+        VolumeMapBuilder {
+            source_module,
+            ..self
+        }
+    }
+
+    pub fn set_x_bounds(self, lower_x_bound: f64, upper_x_bound: f64) -> Self {
+        VolumeMapBuilder {
+            x_bounds: (lower_x_bound, upper_x_bound),
+            ..self
+        }
+    }
+
+    pub fn set_y_bounds(self, lower_y_bound: f64, upper_y_bound: f64) -> Self {
+        VolumeMapBuilder {
+            y_bounds: (lower_y_bound, upper_y_bound),
+            ..self
+        }
+    }
+
+    pub fn set_z_bounds(self, lower_z_bound: f64, upper_z_bound: f64) -> Self {
+        VolumeMapBuilder {
+            z_bounds: (lower_z_bound, upper_z_bound),
+            ..self
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize, usize) {
+        self.size
+    }
+
+    pub fn x_bounds(&self) -> (f64, f64) {
+        self.x_bounds
+    }
+
+    pub fn y_bounds(&self) -> (f64, f64) {
+        self.y_bounds
+    }
+
+    pub fn z_bounds(&self) -> (f64, f64) {
+        self.z_bounds
+    }
+
+    /// Returns the input coordinates that the cell at (x, y, z) is sampled
+    /// at.
+    pub fn coordinates_at(&self, x: usize, y: usize, z: usize) -> [f64; 3] {
+        let (width, height, depth) = self.size;
+        let coordinate = |bounds: (f64, f64), index: usize, len: usize| {
+            bounds.0 + (bounds.1 - bounds.0) / len as f64 * index as f64
+        };
+
+        [
+            coordinate(self.x_bounds, x, width),
+            coordinate(self.y_bounds, y, height),
+            coordinate(self.z_bounds, z, depth),
+        ]
+    }
+
+    pub fn build(&self) -> NoiseVolume {
+        let (width, height, depth) = self.size;
+        let mut volume = NoiseVolume::new(width, height, depth);
+
+        for z in 0..depth {
+            for y in 0..height {
+                for x in 0..width {
+                    let value = self.source_module.get(self.coordinates_at(x, y, z));
+                    volume.set_value(x, y, z, value);
+                }
             }
         }
 
-        result_map
+        volume
     }
 }
 
-fn lat_lon_to_xyz(lat: f64, lon: f64) -> [f64; 3] {
-    let r = lat.to_radians().cos();
-    let x = r * lon.to_radians().cos();
-    let y = lat.to_radians().sin();
-    let z = r * lon.to_radians().sin();
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::{Linear, Perlin, Seedable};
+
+    #[test]
+    fn get_at_matches_build() {
+        let perlin = Perlin::new();
+
+        let plane = PlaneMapBuilder::new(&perlin).set_size(16, 8);
+        let seamless = PlaneMapBuilder::new(&perlin)
+            .set_size(16, 8)
+            .set_is_seamless(true);
+        let sphere = SphereMapBuilder::new(&perlin).set_size(16, 8);
+
+        let plane_map = plane.build();
+        let seamless_map = seamless.build();
+        let sphere_map = sphere.build();
+
+        for &(x, y) in &[(0, 0), (3, 5), (15, 7)] {
+            assert_eq!(plane.get_at(x, y), plane_map.get_value(x, y));
+            assert_eq!(seamless.get_at(x, y), seamless_map.get_value(x, y));
+            assert_eq!(sphere.get_at(x, y), sphere_map.get_value(x, y));
+        }
+    }
+
+    #[test]
+    fn seamless_skips_blend_for_periodic_source() {
+        let periodic = Perlin::new().set_period(&[4, 4]);
+        let builder = PlaneMapBuilder::new(&periodic)
+            .set_size(16, 16)
+            .set_x_bounds(0.0, 4.0)
+            .set_y_bounds(-2.0, 6.0);
+        let plain = builder.build();
+        let seamless = builder.set_is_seamless(true).build();
+
+        assert!(seamless.approx_eq(&plain, 0.0));
+
+        // Bounds that don't span whole periods still blend.
+        let partial = PlaneMapBuilder::new(&periodic)
+            .set_size(16, 16)
+            .set_x_bounds(0.0, 3.0);
+        assert!(!partial
+            .build()
+            .approx_eq(&partial.set_is_seamless(true).build(), 1e-6));
+    }
+
+    #[test]
+    fn volume_builder_thresholds() {
+        let linear = Linear::new(1.0, 0.0, 0.5, 0.0);
+        let builder = VolumeMapBuilder::new(&linear)
+            .set_size(4, 3, 2)
+            .set_z_bounds(0.0, 1.0);
+        let volume = builder.build();
+
+        assert_eq!(volume.size(), (4, 3, 2));
+        let [x, y, z] = builder.coordinates_at(3, 2, 1);
+        assert_eq!((x, z), (0.5, 0.5));
+        assert!((y - 1.0 / 3.0).abs() < 1e-12);
+        assert_eq!(volume.get_value(3, 2, 1), 0.75);
+
+        // The x coordinates are -1.0, -0.5, 0.0 and 0.5 and the z coordinates
+        // 0.0 and 0.5, so only the last column and the upper half of the
+        // column before it lie above 0.2.
+        let occupied = |iso| volume.to_voxels(iso).iter().filter(|&&v| v).count();
+        assert_eq!(occupied(0.2), 3 * 2 + 3);
+        assert_eq!(occupied(-2.0), 4 * 3 * 2);
+    }
+
+    #[test]
+    fn flip_y_reverses_rows() {
+        let perlin = Perlin::new();
+        let builder = PlaneMapBuilder::new(&perlin).set_size(9, 6);
+        let map = builder.build();
+        let flipped = builder.set_flip_y(true).build();
+
+        for y in 0..6 {
+            for x in 0..9 {
+                assert_eq!(flipped.get_value(x, y), map.get_value(x, 5 - y));
+            }
+        }
+    }
+
+    #[test]
+    fn frequency_sets_bounds() {
+        let perlin = Perlin::new();
+        let builder = PlaneMapBuilder::new(&perlin)
+            .set_size(16, 8)
+            .set_frequency(4.0);
+
+        assert_eq!(builder.x_bounds(), (-0.125, 0.125));
+        assert_eq!(builder.y_bounds(), (-0.125, 0.125));
+
+        let bounded = PlaneMapBuilder::new(&perlin)
+            .set_size(16, 8)
+            .set_x_bounds(-0.125, 0.125)
+            .set_y_bounds(-0.125, 0.125);
+        assert!(builder.build().approx_eq(&bounded.build(), 0.0));
+
+        let default = PlaneMapBuilder::new(&perlin).set_size(16, 8);
+        let halved = PlaneMapBuilder::new(&perlin)
+            .set_size(16, 8)
+            .set_frequency(0.5);
+        assert!(default.build().approx_eq(&halved.build(), 0.0));
+    }
+
+    #[test]
+    fn feature_scale_ignores_radius() {
+        let perlin = Perlin::new();
+
+        // Mean squared difference between horizontally adjacent cells, which
+        // grows as the features get smaller.
+        let roughness = |builder: SphereMapBuilder| {
+            let map = builder
+                .set_size(64, 32)
+                .set_bounds(-60.0, 60.0, -180.0, 180.0)
+                .build();
+            let mut sum = 0.0;
+            for y in 0..32 {
+                for x in 1..64 {
+                    sum += (map.get_value(x, y) - map.get_value(x - 1, y)).powi(2);
+                }
+            }
+            sum / (32 * 63) as f64
+        };
+
+        let small = roughness(SphereMapBuilder::new(&perlin).set_radius(2.0));
+        let large = roughness(SphereMapBuilder::new(&perlin).set_radius(8.0));
+        assert!(large > small * 2.0);
+
+        let small = roughness(
+            SphereMapBuilder::new(&perlin)
+                .set_radius(2.0)
+                .set_feature_scale(4.0),
+        );
+        let large = roughness(
+            SphereMapBuilder::new(&perlin)
+                .set_radius(8.0)
+                .set_feature_scale(4.0),
+        );
+        assert!((large - small).abs() < small * 0.01);
+    }
+
+    #[test]
+    fn sample_curve_follows_polyline() {
+        let perlin = Perlin::new();
+        let points = [[0.5, -1.0], [2.5, 0.0], [2.5, 3.0]];
+
+        let values = sample_curve(&perlin, &points, 4);
+        assert_eq!(values.len(), 9);
+
+        for i in 0..4 {
+            let t = i as f64 / 4.0;
+            let on_first = [0.5 + 2.0 * t, -1.0 + t];
+            let on_second = [2.5, 3.0 * t];
+
+            assert!((values[i] - perlin.get(on_first)).abs() < 1e-12);
+            assert!((values[i + 4] - perlin.get(on_second)).abs() < 1e-12);
+        }
+        assert_eq!(values[8], perlin.get([2.5, 3.0]));
+
+        assert_eq!(
+            sample_curve(&perlin, &points[..1], 4),
+            vec![perlin.get(points[0])]
+        );
+        assert!(sample_curve(&perlin, &[], 4).is_empty());
+    }
+
+    #[test]
+    fn cylinder_wrap() {
+        let perlin = Perlin::new();
+        let builder = CylinderMapBuilder::new(&perlin)
+            .set_size(8, 4)
+            .set_angle_bounds(-360.0, 360.0)
+            .set_height_bounds(0.3, 1.5);
+
+        // Without wrapping, 720 degrees are spread over 8 columns, so columns 0
+        // and 4 sample the same angle.
+        let map = builder.build();
+        for y in 0..4 {
+            assert!((map.get_value(0, y) - map.get_value(4, y)).abs() < 1e-9);
+        }
+
+        // With wrapping, the 8 columns cover exactly one period, so the column
+        // just past the right edge matches the first column.
+        let builder = builder.set_wrap(true);
+        let map = builder.build();
+        for y in 0..4 {
+            assert!((map.get_value(0, y) - map.get_value(4, y)).abs() > 1e-9);
+            assert!((builder.get_at(8, y) - map.get_value(0, y)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn animated_plane_frames() {
+        let perlin = Perlin::new();
+        let frames = AnimatedPlaneMapBuilder::new(&perlin)
+            .set_size(8, 6)
+            .set_frame_count(3)
+            .set_time_bounds(0.25, 1.75)
+            .build();
+
+        assert_eq!(frames.len(), 3);
+        for frame in &frames {
+            assert_eq!(frame.size(), (8, 6));
+        }
+
+        let differs = |a: &NoiseMap, b: &NoiseMap| {
+            (0..6).any(|y| (0..8).any(|x| a.get_value(x, y) != b.get_value(x, y)))
+        };
+        assert!(differs(&frames[0], &frames[1]));
+        assert!(differs(&frames[1], &frames[2]));
+    }
+
+    #[test]
+    fn build_into_reuses_map() {
+        let perlin = Perlin::new();
+        let other = Perlin::new().set_seed(1);
+
+        let builder = PlaneMapBuilder::new(&perlin).set_size(8, 8);
+        let other_builder = PlaneMapBuilder::new(&other).set_size(8, 8);
+
+        let expected = builder.build();
+        let other_expected = other_builder.build();
 
-    [x, y, z]
+        let mut map = NoiseMap::new(8, 8);
+        builder.build_into(&mut map);
+        assert_eq!(map.to_heights(1.0), expected.to_heights(1.0));
+
+        // Reusing the buffer must overwrite every value from the previous build.
+        other_builder.build_into(&mut map);
+        assert_eq!(map.to_heights(1.0), other_expected.to_heights(1.0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn build_into_size_mismatch() {
+        let perlin = Perlin::new();
+        let mut map = NoiseMap::new(4, 4);
+
+        PlaneMapBuilder::new(&perlin)
+            .set_size(8, 8)
+            .build_into(&mut map);
+    }
+
+    #[test]
+    fn torus_tiles_on_both_axes() {
+        let perlin = Perlin::new();
+        let builder = TorusMapBuilder::new(&perlin)
+            .set_size(16, 8)
+            .set_radii(3.0, 1.25);
+        let map = builder.build();
+
+        // The cell just past each edge wraps around to the opposite edge.
+        for y in 0..8 {
+            assert!((builder.get_at(16, y) - map.get_value(0, y)).abs() < 1e-9);
+        }
+        for x in 0..16 {
+            assert!((builder.get_at(x, 8) - map.get_value(x, 0)).abs() < 1e-9);
+        }
+
+        // Opposite edges are neighbours on the torus, so they differ by no
+        // more than adjacent columns and rows in the interior.
+        let step = |a: f64, b: f64| (a - b).abs();
+        let max_edge_step = (0..8)
+            .map(|y| step(map.get_value(15, y), map.get_value(0, y)))
+            .chain((0..16).map(|x| step(map.get_value(x, 7), map.get_value(x, 0))))
+            .fold(0.0, f64::max);
+        let max_inner_step = (0..8)
+            .flat_map(|y| (1..16).map(move |x| (x, y)))
+            .map(|(x, y)| step(map.get_value(x - 1, y), map.get_value(x, y)))
+            .fold(0.0, f64::max);
+        assert!(max_edge_step <= max_inner_step * 2.0);
+    }
+
+    #[test]
+    fn line_matches_source() {
+        let perlin = Perlin::new();
+        let line = LineMapBuilder::new(&perlin)
+            .set_length(10)
+            .set_bounds(-2.0, 3.0)
+            .set_y(0.7)
+            .build();
+
+        assert_eq!(line.len(), 10);
+        for (i, &value) in line.iter().enumerate() {
+            assert_eq!(value, perlin.get([-2.0 + 0.5 * i as f64, 0.7, 0.0]));
+        }
+    }
+
+    #[test]
+    fn build_with_coords() {
+        let perlin = Perlin::new();
+        let builder = PlaneMapBuilder::new(&perlin)
+            .set_size(8, 4)
+            .set_x_bounds(-2.0, 2.0)
+            .set_y_bounds(1.0, 3.0);
+        let (map, coords) = builder.build_with_coords();
+
+        assert_eq!(coords.len(), 32);
+        for y in 0..4 {
+            for x in 0..8 {
+                let [cx, cy] = coords[x + y * 8];
+
+                assert_eq!(cx, -2.0 + 0.5 * x as f64);
+                assert_eq!(cy, 1.0 + 0.5 * y as f64);
+                assert_eq!(map.get_value(x, y), perlin.get([cx, cy, 0.0]));
+            }
+        }
+    }
+
+    #[test]
+    fn sample_plane_matches_builder() {
+        let linear = Linear::new(0.3, -0.7, 0.9, 0.1);
+        let map = sample_plane(&linear, (6, 5), (-2.0, 1.0), (0.5, 3.0));
+        let built = PlaneMapBuilder::new(&linear)
+            .set_size(6, 5)
+            .set_x_bounds(-2.0, 1.0)
+            .set_y_bounds(0.5, 3.0)
+            .build();
+
+        assert_eq!(map.size(), (6, 5));
+        assert!(map.approx_eq(&built, 0.0));
+    }
+
+    #[test]
+    fn pole_blend_reduces_pinch() {
+        let perlin = Perlin::new().set_seed(5);
+        let builder = SphereMapBuilder::new(&perlin)
+            .set_size(64, 32)
+            .set_bounds(-88.0, 88.0, -180.0, 180.0);
+
+        let row_variance = |map: &NoiseMap, y: usize| {
+            let values: Vec<_> = (0..64).map(|x| map.get_value(x, y)).collect();
+            let mean = values.iter().sum::<f64>() / 64.0;
+            values.iter().map(|v| (v - mean) * (v - mean)).sum::<f64>() / 64.0
+        };
+
+        let plain = builder.build();
+        let blended = builder.set_pole_blend(true).set_pole_band(15.0).build();
+
+        for &y in &[0, 31] {
+            assert!(row_variance(&blended, y) < row_variance(&plain, y) * 0.5);
+        }
+
+        // Rows outside of the pole bands are unchanged.
+        for x in 0..64 {
+            assert_eq!(blended.get_value(x, 16), plain.get_value(x, 16));
+        }
+    }
 }
@@ -0,0 +1,183 @@
+use super::noise_map::NoiseMap;
+use crate::math::{self, interpolate};
+
+/// A 3-dimensional grid of noise values, stored as a stack of z-slices.
+#[derive(Clone, Debug)]
+pub struct NoiseVolume {
+    size: (usize, usize, usize),
+    border_value: f64,
+    map: Vec<f64>,
+}
+
+impl NoiseVolume {
+    pub fn new(width: usize, height: usize, depth: usize) -> Self {
+        Self {
+            size: (width, height, depth),
+            border_value: 0.0,
+            map: vec![0.0; width * height * depth],
+        }
+    }
+
+    pub fn size(&self) -> (usize, usize, usize) {
+        self.size
+    }
+
+    pub fn set_border_value(self, border_value: f64) -> Self {
+        Self {
+            border_value,
+            ..self
+        }
+    }
+
+    pub fn border_value(&self) -> f64 {
+        self.border_value
+    }
+
+    pub fn set_value(&mut self, x: usize, y: usize, z: usize, value: f64) {
+        let (width, height, depth) = self.size;
+
+        if x < width && y < height && z < depth {
+            self.map[x + (y + z * height) * width] = value;
+        } else {
+            eprintln!("input point out of bounds")
+        }
+    }
+
+    pub fn get_value(&self, x: usize, y: usize, z: usize) -> f64 {
+        let (width, height, depth) = self.size;
+
+        if x < width && y < height && z < depth {
+            self.map[x + (y + z * height) * width]
+        } else {
+            self.border_value
+        }
+    }
+
+    /// Returns the value at the fractional grid position (x, y, z),
+    /// interpolated trilinearly among the eight surrounding grid values.
+    ///
+    /// Positions outside the volume are clamped to its bounds, so the volume
+    /// can serve as a continuous density lookup, for example for raymarching.
+    /// An empty volume returns the border value.
+    pub fn sample_trilinear(&self, x: f64, y: f64, z: f64) -> f64 {
+        let (width, height, depth) = self.size;
+
+        if width == 0 || height == 0 || depth == 0 {
+            return self.border_value;
+        }
+
+        // Splits a coordinate into the lower grid index and the blend towards
+        // the next one, clamped to the grid.
+        let split = |value: f64, size: usize| {
+            let value = math::clamp(value, 0.0, (size - 1) as f64);
+            let index = (value.floor() as usize).min(size.saturating_sub(2));
+            (index, value - index as f64, (index + 1).min(size - 1))
+        };
+
+        let (x0, x_blend, x1) = split(x, width);
+        let (y0, y_blend, y1) = split(y, height);
+        let (z0, z_blend, z1) = split(z, depth);
+
+        let lerp_x =
+            |y, z| interpolate::linear(self.get_value(x0, y, z), self.get_value(x1, y, z), x_blend);
+        let lerp_y = |z| interpolate::linear(lerp_x(y0, z), lerp_x(y1, z), y_blend);
+
+        interpolate::linear(lerp_y(z0), lerp_y(z1), z_blend)
+    }
+
+    /// Returns the raw values of the volume.
+    ///
+    /// The value at (x, y, z) is at index `x + (y + z * height) * width`.
+    pub fn to_density(&self) -> &[f64] {
+        &self.map
+    }
+
+    /// Returns an occupancy grid with the same layout as `to_density`, where
+    /// a cell is occupied if its value is greater than `iso`.
+    pub fn to_voxels(&self, iso: f64) -> Vec<bool> {
+        self.map.iter().map(|&value| value > iso).collect()
+    }
+
+    /// Returns the slice of the volume at depth `z` as a `NoiseMap`.
+    ///
+    /// The map shares the volume's border value.
+    pub fn slice_z(&self, z: usize) -> NoiseMap {
+        let (width, height, depth) = self.size;
+        assert!(z < depth);
+
+        let mut slice = NoiseMap::new(width, height).set_border_value(self.border_value);
+
+        for y in 0..height {
+            for x in 0..width {
+                slice.set_value(x, y, self.get_value(x, y, z));
+            }
+        }
+
+        slice
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slice_z() {
+        let mut volume = NoiseVolume::new(3, 2, 4).set_border_value(-1.0);
+        for z in 0..4 {
+            for y in 0..2 {
+                for x in 0..3 {
+                    volume.set_value(x, y, z, (x + y * 3 + z * 6) as f64);
+                }
+            }
+        }
+
+        let slice = volume.slice_z(2);
+
+        assert_eq!(slice.size(), (3, 2));
+        assert_eq!(slice.border_value(), -1.0);
+        assert_eq!(slice.get_value(0, 0), 12.0);
+        assert_eq!(slice.get_value(2, 1), 17.0);
+        assert_eq!(volume.get_value(3, 0, 0), -1.0);
+    }
+
+    #[test]
+    fn sample_trilinear() {
+        let ramp = |x: f64, y: f64, z: f64| 0.5 * x - 0.25 * y + 0.125 * z;
+        let mut volume = NoiseVolume::new(4, 3, 5);
+        for z in 0..5 {
+            for y in 0..3 {
+                for x in 0..4 {
+                    volume.set_value(x, y, z, ramp(x as f64, y as f64, z as f64));
+                }
+            }
+        }
+
+        for i in 0..50 {
+            let (x, y, z) = (i as f64 * 0.061, i as f64 * 0.039, i as f64 * 0.079);
+            assert!((volume.sample_trilinear(x, y, z) - ramp(x, y, z)).abs() < 1e-12);
+        }
+
+        assert_eq!(volume.sample_trilinear(3.0, 2.0, 4.0), ramp(3.0, 2.0, 4.0));
+        assert_eq!(
+            volume.sample_trilinear(-1.0, 7.5, 4.5),
+            volume.sample_trilinear(0.0, 2.0, 4.0)
+        );
+    }
+
+    #[test]
+    fn to_voxels() {
+        let mut volume = NoiseVolume::new(2, 3, 4);
+        for z in 0..4 {
+            for y in 0..3 {
+                for x in 0..2 {
+                    volume.set_value(x, y, z, 0.25);
+                }
+            }
+        }
+
+        assert_eq!(volume.to_density(), &[0.25; 24][..]);
+        assert_eq!(volume.to_voxels(0.0), vec![true; 24]);
+        assert_eq!(volume.to_voxels(0.5), vec![false; 24]);
+    }
+}
@@ -0,0 +1,73 @@
+use super::noise_map::NoiseMap;
+use crate::math;
+
+/// Packs up to four maps into the channels of an RGBA image, such as height,
+/// moisture and temperature in one data texture for a shader.
+///
+/// Each map's range [-1,1] is mapped onto its channel's range [0,255], with
+/// values outside of [-1,1] clamped. Without an alpha map, the alpha channel
+/// is fully opaque.
+///
+/// Panics if the maps don't all have the same size.
+pub fn pack_channels(
+    r: &NoiseMap,
+    g: &NoiseMap,
+    b: &NoiseMap,
+    a: Option<&NoiseMap>,
+) -> image::RgbaImage {
+    let (width, height) = r.size();
+    assert!(
+        [Some(g), Some(b), a]
+            .iter()
+            .flatten()
+            .all(|map| map.size() == (width, height)),
+        "maps must have the same size"
+    );
+
+    let channel = |map: &NoiseMap, x: u32, y: u32| {
+        let value = map.get_value(x as usize, y as usize);
+
+        (math::clamp(value * 0.5 + 0.5, 0.0, 1.0) * 255.0) as u8
+    };
+
+    image::RgbaImage::from_fn(width as u32, height as u32, |x, y| {
+        image::Rgba([
+            channel(r, x, y),
+            channel(g, x, y),
+            channel(b, x, y),
+            a.map_or(255, |a| channel(a, x, y)),
+        ])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn packs_constant_maps() {
+        let constant = |value| NoiseMap::from_fn(3, 2, move |_, _| value);
+        let (r, g, b, a) = (constant(1.0), constant(-1.0), constant(0.0), constant(2.0));
+
+        let image = pack_channels(&r, &g, &b, None);
+        assert_eq!(image.dimensions(), (3, 2));
+        assert!(image
+            .pixels()
+            .all(|&pixel| pixel == image::Rgba([255, 0, 127, 255])));
+
+        let image = pack_channels(&g, &r, &r, Some(&g));
+        assert_eq!(image.get_pixel(2, 1), &image::Rgba([0, 255, 255, 0]));
+        assert_eq!(pack_channels(&r, &g, &b, Some(&a)).get_pixel(0, 0)[3], 255);
+    }
+
+    #[test]
+    #[should_panic(expected = "maps must have the same size")]
+    fn rejects_mismatched_sizes() {
+        pack_channels(
+            &NoiseMap::new(3, 2),
+            &NoiseMap::new(3, 2),
+            &NoiseMap::new(2, 3),
+            None,
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use super::noise_map::NoiseMap;
+
+/// Composites the maps built for each of `seeds` into a single contact sheet,
+/// to compare many seeds at once.
+///
+/// `builder` is called once per seed and must return maps of the same size.
+/// The maps are laid out left to right, top to bottom, in rows of `columns`
+/// maps, so the sheet is `columns` maps wide and as many rows high as needed.
+/// Unused cells in the last row and the border take the border value of the
+/// first map. Without any seeds, the sheet is empty.
+///
+/// Panics if `columns` is zero or the maps differ in size.
+pub fn seed_preview<F>(builder: F, seeds: &[u32], columns: usize) -> NoiseMap
+where
+    F: Fn(u32) -> NoiseMap,
+{
+    assert!(columns > 0, "columns must be positive");
+
+    let maps: Vec<NoiseMap> = seeds.iter().map(|&seed| builder(seed)).collect();
+    let (width, height, border_value) = match maps.first() {
+        Some(map) => (map.size().0, map.size().1, map.border_value()),
+        None => return NoiseMap::new(0, 0),
+    };
+
+    for map in &maps {
+        assert_eq!(map.size(), (width, height), "maps differ in size");
+    }
+
+    let rows = maps.chunks(columns).len();
+
+    NoiseMap::from_fn(width * columns, height * rows, |x, y| {
+        match maps.get(y / height * columns + x / width) {
+            Some(map) => map.get_value(x % width, y % height),
+            None => border_value,
+        }
+    })
+    .set_border_value(border_value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        noise_fns::{Perlin, Seedable},
+        utils::{NoiseMapBuilder, PlaneMapBuilder},
+    };
+
+    #[test]
+    fn contact_sheet_layout() {
+        let builder = |seed| {
+            let perlin = Perlin::new().set_seed(seed);
+            PlaneMapBuilder::new(&perlin).set_size(8, 4).build()
+        };
+
+        let sheet = seed_preview(builder, &[0, 1, 2, 3, 4], 2);
+        assert_eq!(sheet.size(), (16, 12));
+
+        // The fourth seed goes into the second column of the second row.
+        let fourth = builder(3);
+        assert_eq!(sheet.get_value(8 + 5, 4 + 2), fourth.get_value(5, 2));
+        assert_eq!(sheet.get_value(8, 8), sheet.border_value());
+
+        assert_eq!(seed_preview(builder, &[7], 4).size(), (32, 4));
+        assert_eq!(seed_preview(builder, &[], 3).size(), (0, 0));
+    }
+}
@@ -0,0 +1,136 @@
+use crate::{noise_fns::NoiseFn, utils::noise_map::NoiseMap};
+
+/// Samples a noise function one tile at a time, with an overlap margin so
+/// that filters reading neighboring cells work across tile edges.
+///
+/// The input space is divided into a grid of cells `cell_size` units apart,
+/// with cell (0, 0) at the origin, and tiles of `tile_size` by `tile_size`
+/// cells. Each tile is sampled `margin` cells larger on every side, then
+/// passed through a filter such as `NoiseMap::blur`, and finally cropped back
+/// to the tile. As long as the margin covers the reach of the filter, the
+/// filtered tiles join without seams, matching the filtered map of the whole
+/// area away from its outer edges.
+pub struct TiledGraphSampler<'a> {
+    /// Noise function the tiles are sampled from.
+    pub source: &'a dyn NoiseFn<[f64; 2]>,
+
+    /// Width and height of each tile in cells. Default is 64.
+    pub tile_size: usize,
+
+    /// Extra cells sampled on each side of a tile. Default is 0.
+    pub margin: usize,
+
+    /// Distance between neighboring cells in input space. Default is 1/64,
+    /// so each tile covers one unit.
+    pub cell_size: f64,
+}
+
+impl<'a> TiledGraphSampler<'a> {
+    pub const DEFAULT_TILE_SIZE: usize = 64;
+    pub const DEFAULT_MARGIN: usize = 0;
+    pub const DEFAULT_CELL_SIZE: f64 = 1.0 / 64.0;
+
+    pub fn new(source: &'a dyn NoiseFn<[f64; 2]>) -> Self {
+        Self {
+            source,
+            tile_size: Self::DEFAULT_TILE_SIZE,
+            margin: Self::DEFAULT_MARGIN,
+            cell_size: Self::DEFAULT_CELL_SIZE,
+        }
+    }
+
+    /// Panics if `tile_size` is 0.
+    pub fn set_tile_size(self, tile_size: usize) -> Self {
+        assert!(tile_size > 0, "tile size must be at least 1");
+
+        Self { tile_size, ..self }
+    }
+
+    pub fn set_margin(self, margin: usize) -> Self {
+        Self { margin, ..self }
+    }
+
+    pub fn set_cell_size(self, cell_size: f64) -> Self {
+        Self { cell_size, ..self }
+    }
+
+    /// Returns the input coordinates of the cell at (x, y) of the grid.
+    pub fn coordinates_at(&self, x: isize, y: isize) -> [f64; 2] {
+        [x as f64 * self.cell_size, y as f64 * self.cell_size]
+    }
+
+    /// Samples the tile at (tile_x, tile_y) along with its margin, giving a
+    /// map of `tile_size + 2 * margin` cells along each axis.
+    pub fn sample_padded(&self, tile_x: isize, tile_y: isize) -> NoiseMap {
+        let padded = self.tile_size + 2 * self.margin;
+        let origin_x = tile_x * self.tile_size as isize - self.margin as isize;
+        let origin_y = tile_y * self.tile_size as isize - self.margin as isize;
+
+        NoiseMap::from_fn(padded, padded, |x, y| {
+            self.source
+                .get(self.coordinates_at(origin_x + x as isize, origin_y + y as isize))
+        })
+    }
+
+    /// Samples the tile at (tile_x, tile_y) with its margin, applies `filter`
+    /// and crops the result to the tile.
+    ///
+    /// Panics if `filter` changes the size of the map.
+    pub fn build_tile<F>(&self, tile_x: isize, tile_y: isize, filter: F) -> NoiseMap
+    where
+        F: FnOnce(NoiseMap) -> NoiseMap,
+    {
+        let padded = self.sample_padded(tile_x, tile_y);
+        let size = padded.size();
+        let filtered = filter(padded);
+        assert_eq!(filtered.size(), size, "filter must keep the map size");
+
+        filtered.crop(self.margin, self.margin, self.tile_size, self.tile_size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::noise_fns::Perlin;
+
+    #[test]
+    fn margin_removes_seams_from_filtered_tiles() {
+        let perlin = Perlin::new();
+        let cell_size = 1.0 / 16.0;
+        let full = NoiseMap::from_fn(48, 32, |x, y| {
+            perlin.get([x as f64 * cell_size, y as f64 * cell_size])
+        })
+        .blur(2, 1.0);
+
+        // Largest difference to the full map, ignoring the cells near its
+        // outer edges, which the tiles see beyond.
+        let error = |sampler: &TiledGraphSampler| {
+            let mut error: f64 = 0.0;
+
+            for tile_y in 0..2 {
+                for tile_x in 0..3 {
+                    let tile = sampler.build_tile(tile_x, tile_y, |map| map.blur(2, 1.0));
+
+                    for y in 0..16 {
+                        for x in 0..16 {
+                            let (gx, gy) = (tile_x as usize * 16 + x, tile_y as usize * 16 + y);
+                            if (2..46).contains(&gx) && (2..30).contains(&gy) {
+                                let difference = tile.get_value(x, y) - full.get_value(gx, gy);
+                                error = error.max(difference.abs());
+                            }
+                        }
+                    }
+                }
+            }
+
+            error
+        };
+
+        let sampler = TiledGraphSampler::new(&perlin)
+            .set_tile_size(16)
+            .set_cell_size(cell_size);
+        assert!(error(&sampler) > 1e-3);
+        assert!(error(&sampler.set_margin(2)) < 1e-12);
+    }
+}